@@ -0,0 +1,155 @@
+// main.rs
+// The rcrash launcher
+// Runs the course tools by name, for example:
+//   cargo run -- fmt '{:>+#010.3e}' 3.14159
+
+use rcrash_core::cast_matrix;
+use rcrash_core::fmt_spec::{self, Value};
+use rcrash_core::grading::{self, Scale};
+use rcrash_core::type_inspect::{self, FloatReport};
+use std::env;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::process;
+
+const USAGE: &str = "\
+Usage: rcrash <command> [args...]
+
+Commands:
+  fmt <spec> <value>   Explain a format spec and apply it to a value (3.13)
+  cast-matrix [dir]    Write the numeric cast matrix as Markdown and CSV (3.14)
+  inspect [value]      Show a float's IEEE-754 bits, or the primitive type table (3.4)
+  grade [scale] <csv>  Grade a name,score CSV with a scale file (default: A-F) (4.3, 4.7)
+  layout [name=cents]  Run the multi-file project-layout example crate (5.8)
+  help                 Show this message";
+
+/// `rcrash fmt '{:>8.2}' 3.14159`
+fn cmd_fmt(args: &[String]) -> Result<(), String> {
+    let [spec_text, value_text] = args else {
+        return Err("usage: rcrash fmt <spec> <value>".to_string());
+    };
+    let spec = fmt_spec::parse(spec_text).map_err(|e| format!("invalid spec: {}", e))?;
+    let value = Value::parse(value_text).map_err(|e| e.to_string())?;
+
+    println!("spec       {}", spec);
+    for (component, explanation) in spec.explain() {
+        println!("{:<10} {}", component, explanation);
+    }
+    println!("value      {} ({})", value_text, value.type_name());
+    let output = fmt_spec::render(&spec, &value).map_err(|e| e.to_string())?;
+    println!("result     [{}]", output);
+    Ok(())
+}
+
+/// `rcrash cast-matrix target/` writes cast_matrix.md and cast_matrix.csv.
+fn cmd_cast_matrix(args: &[String]) -> Result<(), String> {
+    let dir = match args {
+        [] => "target",
+        [dir] => dir.as_str(),
+        _ => return Err("usage: rcrash cast-matrix [dir]".to_string()),
+    };
+    let dir = Path::new(dir);
+    fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+
+    let rows = cast_matrix::generate();
+    let md_path = dir.join("cast_matrix.md");
+    let csv_path = dir.join("cast_matrix.csv");
+    let write = |path: &Path, csv: bool| -> Result<(), String> {
+        let file = File::create(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        let mut out = BufWriter::new(file);
+        let result = if csv {
+            cast_matrix::write_csv(&rows, &mut out)
+        } else {
+            cast_matrix::write_markdown(&rows, &mut out)
+        };
+        result
+            .and_then(|_| out.flush())
+            .map_err(|e| format!("{}: {}", path.display(), e))
+    };
+    write(&md_path, false)?;
+    write(&csv_path, true)?;
+    println!("{} conversions written to {} and {}", rows.len(), md_path.display(), csv_path.display());
+    Ok(())
+}
+
+fn print_float(report: &FloatReport) {
+    let layout = &report.layout;
+    println!("value      {} ({})", report.value, layout.ty);
+    println!("category   {}", type_inspect::category_name(layout.category));
+    println!("bits       {}", layout.bit_string());
+    println!("sign       {}", layout.sign);
+    println!("exponent   {} (bias {})", layout.exponent, layout.bias());
+    println!("mantissa   {:#x}", layout.mantissa);
+    println!("meaning    {}", layout);
+    if let Some(exact) = &report.exact {
+        println!("exact      {}", exact);
+    }
+    println!("next up    {}", report.next_up);
+    println!("next down  {}", report.next_down);
+}
+
+/// `rcrash inspect 0.1f32` shows the bits; `rcrash inspect` lists the types.
+fn cmd_inspect(args: &[String]) -> Result<(), String> {
+    let text = match args {
+        [] => {
+            println!("{:<6} {:>4} {:>5}  {:<42} max", "type", "size", "align", "min");
+            for info in type_inspect::primitive_types() {
+                println!("{:<6} {:>4} {:>5}  {:<42} {}", info.name, info.size, info.align, info.min, info.max);
+            }
+            return Ok(());
+        }
+        [text] => text,
+        _ => return Err("usage: rcrash inspect [value]".to_string()),
+    };
+    match Value::parse(text).map_err(|e| e.to_string())? {
+        Value::F32(x) => print_float(&type_inspect::inspect_float(x)),
+        Value::F64(x) => print_float(&type_inspect::inspect_float(x)),
+        other => return Err(format!("{} is a {}, not a float", text, other.type_name())),
+    }
+    Ok(())
+}
+
+/// `rcrash grade crates/rcrash-core/data/standard.txt data/grading/scores.csv`
+fn cmd_grade(args: &[String]) -> Result<(), String> {
+    let (scale, csv_path) = match args {
+        [csv] => (Scale::standard(), csv),
+        [scale, csv] => (Scale::load(Path::new(scale)).map_err(|e| format!("{}: {}", scale, e))?, csv),
+        _ => return Err("usage: rcrash grade [scale] <csv>".to_string()),
+    };
+    let csv = fs::read_to_string(csv_path).map_err(|e| format!("{}: {}", csv_path, e))?;
+    let report = grading::grade_csv(&scale, &csv).map_err(|e| format!("{}: {}", csv_path, e))?;
+    println!("{}", report);
+    Ok(())
+}
+
+/// `rcrash layout book=1299 pen=250` runs the examples/project_layout crate.
+fn cmd_layout(args: &[String]) -> Result<(), String> {
+    print!("{}", project_layout::demo(args)?);
+    Ok(())
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let Some((command, rest)) = args.split_first() else {
+        eprintln!("{}", USAGE);
+        process::exit(2);
+    };
+
+    let result = match command.as_str() {
+        "fmt" => cmd_fmt(rest),
+        "cast-matrix" => cmd_cast_matrix(rest),
+        "inspect" => cmd_inspect(rest),
+        "grade" => cmd_grade(rest),
+        "layout" => cmd_layout(rest),
+        "help" | "-h" | "--help" => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        other => Err(format!("unknown command '{}'\n\n{}", other, USAGE)),
+    };
+    if let Err(e) = result {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,482 @@
+// cast_matrix.rs
+// 3.14 Type casting, exhaustively
+// For every pair of primitive numeric types plus `char`, tries edge values
+// (MIN, MAX, 0, -1, NaN, ±inf and values just outside the target's range)
+// and records the `as` result next to the `From`/`TryFrom` outcome.
+
+use std::fmt::Display;
+use std::io::{self, Write};
+use std::marker::PhantomData;
+
+/// A point just outside the range of a target type, seen from a source type.
+#[derive(Debug, Clone, Copy)]
+pub enum Outside {
+    Above(Limit),
+    Below(Limit),
+}
+
+/// A range limit of a target type.
+#[derive(Debug, Clone, Copy)]
+pub enum Limit {
+    Int(i128),
+    UInt(u128),
+    F32(f32),
+}
+
+/// A type that can appear in the matrix, either as source or target.
+pub trait Sample: Copy {
+    const NAME: &'static str;
+
+    /// The type's own interesting values.
+    fn edges() -> Vec<Self>;
+
+    /// The points just outside this type's range.
+    fn outside() -> Vec<Outside>;
+
+    /// A value of this type at (or just past) an `Outside` point, if representable.
+    fn near(point: Outside) -> Option<Self>;
+
+    /// How the value is written in the matrix.
+    fn label(self) -> String;
+}
+
+macro_rules! sample_int {
+    ($($t:ident),*) => {
+        $(impl Sample for $t {
+            const NAME: &'static str = stringify!($t);
+
+            fn edges() -> Vec<Self> {
+                let zero: $t = 0;
+                let mut v = vec![$t::MIN, $t::MAX, zero];
+                if $t::MIN != zero {
+                    v.push(zero.wrapping_sub(1)); // -1 for signed types
+                }
+                v
+            }
+
+            fn outside() -> Vec<Outside> {
+                vec![
+                    Outside::Below(Limit::Int($t::MIN as i128)),
+                    Outside::Above(match i128::try_from($t::MAX) {
+                        Ok(max) => Limit::Int(max),
+                        Err(_) => Limit::UInt($t::MAX as u128),
+                    }),
+                ]
+            }
+
+            fn near(point: Outside) -> Option<Self> {
+                match point {
+                    Outside::Above(Limit::Int(v)) => $t::try_from(v.checked_add(1)?).ok(),
+                    Outside::Below(Limit::Int(v)) => $t::try_from(v.checked_sub(1)?).ok(),
+                    Outside::Above(Limit::UInt(v)) => $t::try_from(v.checked_add(1)?).ok(),
+                    Outside::Below(Limit::UInt(v)) => $t::try_from(v.checked_sub(1)?).ok(),
+                    Outside::Above(Limit::F32(_)) | Outside::Below(Limit::F32(_)) => None,
+                }
+            }
+
+            fn label(self) -> String {
+                self.to_string()
+            }
+        })*
+    };
+}
+
+sample_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+macro_rules! sample_float {
+    ($($t:ident),*) => {
+        $(impl Sample for $t {
+            const NAME: &'static str = stringify!($t);
+
+            fn edges() -> Vec<Self> {
+                vec![$t::MIN, $t::MAX, 0.0, -1.0, $t::NAN, $t::INFINITY, $t::NEG_INFINITY]
+            }
+
+            fn outside() -> Vec<Outside> {
+                // Only f32 has a finite range another float can leave
+                if $t::MAX as f64 == f32::MAX as f64 {
+                    vec![
+                        Outside::Below(Limit::F32(f32::MIN)),
+                        Outside::Above(Limit::F32(f32::MAX)),
+                    ]
+                } else {
+                    Vec::new()
+                }
+            }
+
+            fn near(point: Outside) -> Option<Self> {
+                // Round the limit into this type, then step past it if rounding landed inside
+                let (limit, above) = match point {
+                    Outside::Above(l) => (l, true),
+                    Outside::Below(l) => (l, false),
+                };
+                let (value, inside) = match limit {
+                    Limit::Int(v) => {
+                        let f = v as $t;
+                        (f, if above { f as i128 <= v } else { f as i128 >= v })
+                    }
+                    Limit::UInt(v) => {
+                        let f = v as $t;
+                        (f, f as u128 <= v)
+                    }
+                    Limit::F32(v) => (v as $t, true),
+                };
+                let stepped = match (inside, above) {
+                    (false, _) => value,
+                    (true, true) => value.next_up(),
+                    (true, false) => value.next_down(),
+                };
+                Some(stepped).filter(|v| value.is_finite() && v.is_finite())
+            }
+
+            fn label(self) -> String {
+                format!("{:?}", self)
+            }
+        })*
+    };
+}
+
+sample_float!(f32, f64);
+
+impl Sample for char {
+    const NAME: &'static str = "char";
+
+    fn edges() -> Vec<Self> {
+        vec!['\0', 'A', '\u{ff}', '\u{d7ff}', '\u{e000}', char::MAX]
+    }
+
+    fn outside() -> Vec<Outside> {
+        vec![
+            Outside::Below(Limit::Int(0)),
+            Outside::Above(Limit::Int(0xD7FF)), // start of the surrogate gap
+            Outside::Above(Limit::Int(char::MAX as i128)),
+        ]
+    }
+
+    fn near(point: Outside) -> Option<Self> {
+        match point {
+            Outside::Above(Limit::Int(v)) => char::from_u32(u32::try_from(v.checked_add(1)?).ok()?),
+            _ => None,
+        }
+    }
+
+    fn label(self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+/// `source as Target`, implemented exactly for the pairs the language allows.
+pub trait AsCast<D> {
+    fn cast(self) -> D;
+}
+
+macro_rules! as_cast {
+    ($s:ty => $($d:ty),*) => {
+        $(impl AsCast<$d> for $s {
+            fn cast(self) -> $d {
+                self as $d
+            }
+        })*
+    };
+}
+
+macro_rules! as_cast_numeric {
+    ($($s:ty),*) => {
+        $(as_cast!($s => i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);)*
+    };
+}
+
+as_cast_numeric!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
+as_cast!(char => i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, char);
+as_cast!(u8 => char);
+
+/// A source value and a target type. Which conversions exist is decided by
+/// method resolution: the `Via*` traits are implemented on `Probe` only when
+/// the conversion exists, the `No*` fallbacks on `&Probe` always.
+pub struct Probe<S, D>(pub S, pub PhantomData<D>);
+
+pub trait ViaAs {
+    fn via_as(&self) -> Option<String>;
+}
+
+impl<S: AsCast<D> + Copy, D: Sample> ViaAs for Probe<S, D> {
+    fn via_as(&self) -> Option<String> {
+        Some(self.0.cast().label())
+    }
+}
+
+pub trait NoAs {
+    fn via_as(&self) -> Option<String> {
+        None
+    }
+}
+
+impl<S, D> NoAs for &Probe<S, D> {}
+
+pub trait ViaFrom {
+    fn via_from(&self) -> Option<String>;
+}
+
+impl<S: Copy, D: From<S> + Sample> ViaFrom for Probe<S, D> {
+    fn via_from(&self) -> Option<String> {
+        Some(D::from(self.0).label())
+    }
+}
+
+pub trait NoFrom {
+    fn via_from(&self) -> Option<String> {
+        None
+    }
+}
+
+impl<S, D> NoFrom for &Probe<S, D> {}
+
+pub trait ViaTryFrom {
+    fn via_try_from(&self) -> Option<Result<String, String>>;
+}
+
+impl<S: Copy, D: TryFrom<S> + Sample> ViaTryFrom for Probe<S, D>
+where
+    D::Error: Display,
+{
+    fn via_try_from(&self) -> Option<Result<String, String>> {
+        Some(D::try_from(self.0).map(Sample::label).map_err(|e| e.to_string()))
+    }
+}
+
+pub trait NoTryFrom {
+    fn via_try_from(&self) -> Option<Result<String, String>> {
+        None
+    }
+}
+
+impl<S, D> NoTryFrom for &Probe<S, D> {}
+
+/// One tried conversion.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    pub from: &'static str,
+    pub to: &'static str,
+    pub value: String,
+    /// `None` when `value as To` does not compile.
+    pub as_result: Option<String>,
+    /// `None` when `To: From<From>` is not implemented.
+    pub from_result: Option<String>,
+    /// `None` when `To: TryFrom<From>` is not implemented.
+    pub try_from_result: Option<Result<String, String>>,
+}
+
+impl Row {
+    /// "as" column text.
+    pub fn as_text(&self) -> String {
+        self.as_result.clone().unwrap_or_else(|| "not allowed".to_string())
+    }
+
+    /// "From" column text.
+    pub fn from_text(&self) -> String {
+        self.from_result.clone().unwrap_or_else(|| "—".to_string())
+    }
+
+    /// "TryFrom" column text; notes when it only exists through `From`.
+    pub fn try_from_text(&self) -> String {
+        let via = if self.from_result.is_some() { " (via From)" } else { "" };
+        match &self.try_from_result {
+            None => "—".to_string(),
+            Some(Ok(v)) => format!("Ok({}){}", v, via),
+            Some(Err(e)) => format!("Err({})", e),
+        }
+    }
+}
+
+/// The source's edges plus its values just outside the target's range,
+/// without duplicates (compared by label, so NaN is kept once).
+pub fn values<S: Sample, D: Sample>() -> Vec<S> {
+    let mut out: Vec<S> = Vec::new();
+    let candidates = S::edges().into_iter().chain(D::outside().into_iter().filter_map(S::near));
+    for v in candidates {
+        if !out.iter().any(|o| o.label() == v.label()) {
+            out.push(v);
+        }
+    }
+    out
+}
+
+macro_rules! push_pair {
+    ($rows:ident, $s:ty, $d:ty) => {
+        for v in values::<$s, $d>() {
+            let probe = Probe::<$s, $d>(v, PhantomData);
+            $rows.push(Row {
+                from: <$s as Sample>::NAME,
+                to: <$d as Sample>::NAME,
+                value: v.label(),
+                as_result: (&probe).via_as(),
+                from_result: (&probe).via_from(),
+                try_from_result: (&probe).via_try_from(),
+            });
+        }
+    };
+}
+
+macro_rules! push_source {
+    ($rows:ident, $s:ty) => {
+        push_source!($rows, $s; i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64, char);
+    };
+    ($rows:ident, $s:ty; $($d:ty),*) => {
+        $(push_pair!($rows, $s, $d);)*
+    };
+}
+
+/// Builds the full matrix: every (source, target) pair over 15 types.
+pub fn generate() -> Vec<Row> {
+    let mut rows = Vec::new();
+    push_source!(rows, i8);
+    push_source!(rows, i16);
+    push_source!(rows, i32);
+    push_source!(rows, i64);
+    push_source!(rows, i128);
+    push_source!(rows, isize);
+    push_source!(rows, u8);
+    push_source!(rows, u16);
+    push_source!(rows, u32);
+    push_source!(rows, u64);
+    push_source!(rows, u128);
+    push_source!(rows, usize);
+    push_source!(rows, f32);
+    push_source!(rows, f64);
+    push_source!(rows, char);
+    rows
+}
+
+/// Escapes `|` so labels can sit inside a Markdown table cell.
+fn md_cell(s: &str) -> String {
+    format!("`{}`", s.replace('|', "\\|"))
+}
+
+/// One section per source type, one table row per tried value.
+pub fn write_markdown<W: Write>(rows: &[Row], out: &mut W) -> io::Result<()> {
+    writeln!(out, "# Numeric Cast Matrix")?;
+    writeln!(out)?;
+    writeln!(out, "Generated by `cargo run -- cast-matrix`. `—` means the trait is not implemented;")?;
+    writeln!(out, "\"not allowed\" means the `as` cast does not compile.")?;
+    let mut current = "";
+    for row in rows {
+        if row.from != current {
+            current = row.from;
+            writeln!(out)?;
+            writeln!(out, "## From `{}`", current)?;
+            writeln!(out)?;
+            writeln!(out, "| To | Value | `as` | `From` | `TryFrom` |")?;
+            writeln!(out, "|----|-------|------|--------|-----------|")?;
+        }
+        writeln!(
+            out,
+            "| {} | {} | {} | {} | {} |",
+            row.to,
+            md_cell(&row.value),
+            md_cell(&row.as_text()),
+            md_cell(&row.from_text()),
+            md_cell(&row.try_from_text()),
+        )?;
+    }
+    Ok(())
+}
+
+/// Quotes a CSV field when it contains a comma, quote or newline.
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/// One line per row; empty cells for conversions that don't exist.
+pub fn write_csv<W: Write>(rows: &[Row], out: &mut W) -> io::Result<()> {
+    writeln!(out, "from,to,value,as,from_impl,try_from_ok,try_from_err")?;
+    for row in rows {
+        let (ok, err) = match &row.try_from_result {
+            None => (String::new(), String::new()),
+            Some(Ok(v)) => (v.clone(), String::new()),
+            Some(Err(e)) => (String::new(), e.clone()),
+        };
+        let fields = [
+            row.from.to_string(),
+            row.to.to_string(),
+            row.value.clone(),
+            row.as_result.clone().unwrap_or_default(),
+            row.from_result.clone().unwrap_or_default(),
+            ok,
+            err,
+        ];
+        let line: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+        writeln!(out, "{}", line.join(","))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find(rows: &[Row], from: &str, to: &str, value: &str) -> Row {
+        rows.iter()
+            .find(|r| r.from == from && r.to == to && r.value == value)
+            .unwrap_or_else(|| panic!("no row {} {} -> {}", from, value, to))
+            .clone()
+    }
+
+    #[test]
+    fn covers_every_pair() {
+        let rows = generate();
+        let mut pairs: Vec<(&str, &str)> = rows.iter().map(|r| (r.from, r.to)).collect();
+        pairs.dedup();
+        assert_eq!(pairs.len(), 15 * 15);
+    }
+
+    #[test]
+    fn records_known_conversions() {
+        let rows = generate();
+
+        // 256 is just above u8::MAX and wraps to 0
+        let r = find(&rows, "i32", "u8", "256");
+        assert_eq!(r.as_result.as_deref(), Some("0"));
+        assert_eq!(r.from_result, None);
+        assert!(matches!(r.try_from_result, Some(Err(_))));
+
+        let r = find(&rows, "i32", "u8", "-1");
+        assert_eq!(r.as_result.as_deref(), Some("255"));
+
+        let r = find(&rows, "f64", "i32", "NaN");
+        assert_eq!(r.as_result.as_deref(), Some("0"));
+        assert_eq!(r.try_from_result, None);
+
+        let r = find(&rows, "f64", "u8", "inf");
+        assert_eq!(r.as_result.as_deref(), Some("255"));
+
+        let r = find(&rows, "u8", "char", "255");
+        assert_eq!(r.as_result.as_deref(), Some("'ÿ'"));
+        assert_eq!(r.from_result.as_deref(), Some("'ÿ'"));
+
+        let r = find(&rows, "u32", "char", "55296"); // 0xD800, a surrogate
+        assert_eq!(r.as_result, None);
+        assert!(matches!(r.try_from_result, Some(Err(_))));
+
+        let r = find(&rows, "char", "u8", "'Ā'"); // U+0100
+        assert_eq!(r.as_result.as_deref(), Some("0"));
+        assert!(matches!(r.try_from_result, Some(Err(_))));
+
+        let r = find(&rows, "u8", "i32", "255");
+        assert_eq!(r.from_result.as_deref(), Some("255"));
+        assert_eq!(r.try_from_text(), "Ok(255) (via From)");
+
+        let r = find(&rows, "f32", "char", "0.0");
+        assert_eq!(r.as_result, None);
+    }
+
+    #[test]
+    fn csv_quotes_only_when_needed() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("'\"'"), "\"'\"\"'\"");
+    }
+}
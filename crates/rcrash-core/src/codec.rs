@@ -0,0 +1,448 @@
+// codec.rs
+// 3.14 Type casting without transmute
+// The safe replacement for `unsafe { transmute::<u32, [u8; 4]>(x) }`: explicit
+// byte order for every integer and float width, LEB128 varints, and a
+// bounds-checked ByteReader/ByteWriter pair.
+
+use std::fmt;
+
+/// Byte order of a multi-byte value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Endian {
+    Little,
+    Big,
+}
+
+/// Errors from reading or writing bytes. Offsets are positions in the buffer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CodecError {
+    UnexpectedEof { offset: usize, needed: usize, available: usize },
+    BufferFull { offset: usize, needed: usize, remaining: usize },
+    LengthMismatch { expected: usize, found: usize },
+    VarintTooLong { offset: usize },
+}
+
+impl fmt::Display for CodecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CodecError::UnexpectedEof { offset, needed, available } => write!(
+                f,
+                "unexpected end of input at offset {}: needed {} bytes, {} available",
+                offset, needed, available
+            ),
+            CodecError::BufferFull { offset, needed, remaining } => write!(
+                f,
+                "buffer full at offset {}: needed {} bytes, {} remaining",
+                offset, needed, remaining
+            ),
+            CodecError::LengthMismatch { expected, found } => {
+                write!(f, "expected exactly {} bytes, found {}", expected, found)
+            }
+            CodecError::VarintTooLong { offset } => {
+                write!(f, "varint starting at offset {} does not fit in 64 bits", offset)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CodecError {}
+
+/// A fixed-width number that can be turned into bytes and back.
+pub trait FixedWidth: Copy {
+    const SIZE: usize;
+
+    /// Writes exactly `SIZE` bytes into `out`.
+    fn put(self, endian: Endian, out: &mut [u8]);
+
+    /// Reads from exactly `SIZE` bytes.
+    fn get(endian: Endian, bytes: &[u8]) -> Self;
+}
+
+macro_rules! fixed_width {
+    ($($t:ty),*) => {
+        $(impl FixedWidth for $t {
+            const SIZE: usize = std::mem::size_of::<$t>();
+
+            fn put(self, endian: Endian, out: &mut [u8]) {
+                let bytes = match endian {
+                    Endian::Little => self.to_le_bytes(),
+                    Endian::Big => self.to_be_bytes(),
+                };
+                out.copy_from_slice(&bytes);
+            }
+
+            fn get(endian: Endian, bytes: &[u8]) -> Self {
+                let mut array = [0u8; std::mem::size_of::<$t>()];
+                array.copy_from_slice(bytes);
+                match endian {
+                    Endian::Little => <$t>::from_le_bytes(array),
+                    Endian::Big => <$t>::from_be_bytes(array),
+                }
+            }
+        })*
+    };
+}
+
+fixed_width!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128, f32, f64);
+
+/// `value.to_le_bytes()` for any supported type, as a `Vec<u8>`.
+pub fn to_le_bytes<T: FixedWidth>(value: T) -> Vec<u8> {
+    to_bytes(value, Endian::Little)
+}
+
+/// `value.to_be_bytes()` for any supported type, as a `Vec<u8>`.
+pub fn to_be_bytes<T: FixedWidth>(value: T) -> Vec<u8> {
+    to_bytes(value, Endian::Big)
+}
+
+pub fn to_bytes<T: FixedWidth>(value: T, endian: Endian) -> Vec<u8> {
+    let mut out = vec![0u8; T::SIZE];
+    value.put(endian, &mut out);
+    out
+}
+
+/// Like `T::from_le_bytes`, but takes a slice and checks its length.
+pub fn from_le_bytes<T: FixedWidth>(bytes: &[u8]) -> Result<T, CodecError> {
+    from_bytes(bytes, Endian::Little)
+}
+
+/// Like `T::from_be_bytes`, but takes a slice and checks its length.
+pub fn from_be_bytes<T: FixedWidth>(bytes: &[u8]) -> Result<T, CodecError> {
+    from_bytes(bytes, Endian::Big)
+}
+
+pub fn from_bytes<T: FixedWidth>(bytes: &[u8], endian: Endian) -> Result<T, CodecError> {
+    if bytes.len() != T::SIZE {
+        return Err(CodecError::LengthMismatch { expected: T::SIZE, found: bytes.len() });
+    }
+    Ok(T::get(endian, bytes))
+}
+
+/// Unsigned LEB128: 7 bits per byte, low bits first, high bit set on all but the last byte.
+pub fn encode_uleb128(mut value: u64, out: &mut Vec<u8>) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            out.push(byte);
+            return;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+/// Signed LEB128: like unsigned, but stops once the remaining bits are all sign bits.
+pub fn encode_sleb128(mut value: i64, out: &mut Vec<u8>) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7; // arithmetic shift keeps the sign
+        let sign_bit_clear = byte & 0x40 == 0;
+        if (value == 0 && sign_bit_clear) || (value == -1 && !sign_bit_clear) {
+            out.push(byte);
+            return;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+/// Decodes an unsigned LEB128 value; returns it with the number of bytes used.
+pub fn decode_uleb128(bytes: &[u8]) -> Result<(u64, usize), CodecError> {
+    let mut reader = ByteReader::new(bytes, Endian::Little);
+    let value = reader.read_uleb128()?;
+    Ok((value, reader.position()))
+}
+
+/// Decodes a signed LEB128 value; returns it with the number of bytes used.
+pub fn decode_sleb128(bytes: &[u8]) -> Result<(i64, usize), CodecError> {
+    let mut reader = ByteReader::new(bytes, Endian::Little);
+    let value = reader.read_sleb128()?;
+    Ok((value, reader.position()))
+}
+
+/// Reads numbers from a byte slice, checking bounds on every read.
+#[derive(Debug, Clone)]
+pub struct ByteReader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+    endian: Endian,
+}
+
+impl<'a> ByteReader<'a> {
+    pub fn new(buf: &'a [u8], endian: Endian) -> Self {
+        ByteReader { buf, pos: 0, endian }
+    }
+
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.buf.len() - self.pos
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.remaining() == 0
+    }
+
+    /// Borrows the next `n` bytes and advances past them.
+    pub fn read_bytes(&mut self, n: usize) -> Result<&'a [u8], CodecError> {
+        if n > self.remaining() {
+            return Err(CodecError::UnexpectedEof {
+                offset: self.pos,
+                needed: n,
+                available: self.remaining(),
+            });
+        }
+        let bytes = &self.buf[self.pos..self.pos + n];
+        self.pos += n;
+        Ok(bytes)
+    }
+
+    /// Reads one number in the reader's byte order.
+    pub fn read<T: FixedWidth>(&mut self) -> Result<T, CodecError> {
+        let endian = self.endian;
+        self.read_bytes(T::SIZE).map(|bytes| T::get(endian, bytes))
+    }
+
+    pub fn read_uleb128(&mut self) -> Result<u64, CodecError> {
+        let start = self.pos;
+        let mut value = 0u64;
+        let mut shift = 0;
+        loop {
+            let byte = self.read::<u8>()?;
+            let low = u64::from(byte & 0x7f);
+            // The 10th byte may only contribute the single remaining bit
+            if (shift == 63 && low > 1) || shift > 63 {
+                return Err(CodecError::VarintTooLong { offset: start });
+            }
+            value |= low << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+            shift += 7;
+        }
+    }
+
+    pub fn read_sleb128(&mut self) -> Result<i64, CodecError> {
+        let start = self.pos;
+        let mut value = 0i64;
+        let mut shift = 0;
+        loop {
+            let byte = self.read::<u8>()?;
+            // The 10th byte holds only bit 63, so it must be the last byte and
+            // agree with the sign: 0x00 for non-negative, 0x7f for negative
+            if shift == 63 && byte != 0x00 && byte != 0x7f {
+                return Err(CodecError::VarintTooLong { offset: start });
+            }
+            value |= i64::from(byte & 0x7f) << shift;
+            shift += 7;
+            if byte & 0x80 == 0 {
+                if shift < 64 && byte & 0x40 != 0 {
+                    value |= -1i64 << shift; // sign-extend
+                }
+                return Ok(value);
+            }
+        }
+    }
+}
+
+/// Writes numbers into a fixed-size buffer, checking bounds on every write.
+#[derive(Debug)]
+pub struct ByteWriter<'a> {
+    buf: &'a mut [u8],
+    pos: usize,
+    endian: Endian,
+}
+
+impl<'a> ByteWriter<'a> {
+    pub fn new(buf: &'a mut [u8], endian: Endian) -> Self {
+        ByteWriter { buf, pos: 0, endian }
+    }
+
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.buf.len() - self.pos
+    }
+
+    /// The bytes written so far.
+    pub fn written(&self) -> &[u8] {
+        &self.buf[..self.pos]
+    }
+
+    pub fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), CodecError> {
+        let out = self.reserve(bytes.len())?;
+        out.copy_from_slice(bytes);
+        Ok(())
+    }
+
+    /// Writes one number in the writer's byte order.
+    pub fn write<T: FixedWidth>(&mut self, value: T) -> Result<(), CodecError> {
+        let endian = self.endian;
+        value.put(endian, self.reserve(T::SIZE)?);
+        Ok(())
+    }
+
+    /// A varint is written whole or not at all.
+    pub fn write_uleb128(&mut self, value: u64) -> Result<(), CodecError> {
+        let mut bytes = Vec::new();
+        encode_uleb128(value, &mut bytes);
+        self.write_bytes(&bytes)
+    }
+
+    pub fn write_sleb128(&mut self, value: i64) -> Result<(), CodecError> {
+        let mut bytes = Vec::new();
+        encode_sleb128(value, &mut bytes);
+        self.write_bytes(&bytes)
+    }
+
+    fn reserve(&mut self, n: usize) -> Result<&mut [u8], CodecError> {
+        if n > self.remaining() {
+            return Err(CodecError::BufferFull {
+                offset: self.pos,
+                needed: n,
+                remaining: self.remaining(),
+            });
+        }
+        let start = self.pos;
+        self.pos += n;
+        Ok(&mut self.buf[start..start + n])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn big_endian_is_the_written_order() {
+        // The value from the old transmute demo: "abcd" in ASCII
+        assert_eq!(to_be_bytes(0x61626364u32), b"abcd");
+        assert_eq!(to_le_bytes(0x61626364u32), b"dcba");
+        assert_eq!(to_be_bytes(0x0102u16), [0x01, 0x02]);
+        assert_eq!(to_be_bytes(-2i16), [0xff, 0xfe]);
+        assert_eq!(to_be_bytes(1u64), [0, 0, 0, 0, 0, 0, 0, 1]);
+        assert_eq!(to_be_bytes(1.0f32), [0x3f, 0x80, 0x00, 0x00]);
+        assert_eq!(to_be_bytes(-2.0f64), [0xc0, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(to_be_bytes(1u128)[15], 1);
+        assert_eq!(from_be_bytes::<u32>(b"abcd"), Ok(0x61626364));
+        assert_eq!(from_be_bytes::<i8>(&[0x80]), Ok(i8::MIN));
+    }
+
+    #[test]
+    fn round_trips_every_width() {
+        fn check<T: FixedWidth + PartialEq + fmt::Debug>(values: &[T]) {
+            for &v in values {
+                assert_eq!(from_le_bytes::<T>(&to_le_bytes(v)), Ok(v));
+                assert_eq!(from_be_bytes::<T>(&to_be_bytes(v)), Ok(v));
+                let mut be = to_be_bytes(v);
+                be.reverse();
+                assert_eq!(be, to_le_bytes(v));
+            }
+        }
+        check(&[i8::MIN, -1, 0, i8::MAX]);
+        check(&[i16::MIN, -1, 0, i16::MAX]);
+        check(&[i32::MIN, -1, 0, i32::MAX]);
+        check(&[i64::MIN, -1, 0, i64::MAX]);
+        check(&[i128::MIN, -1, 0, i128::MAX]);
+        check(&[0u8, u8::MAX]);
+        check(&[0u16, u16::MAX]);
+        check(&[0u32, u32::MAX]);
+        check(&[0u64, u64::MAX]);
+        check(&[0u128, u128::MAX]);
+        check(&[0.0f32, -1.5, f32::MIN_POSITIVE, f32::INFINITY]);
+        check(&[0.0f64, -1.5, f64::MAX, f64::NEG_INFINITY]);
+    }
+
+    #[test]
+    fn wrong_length_is_an_error() {
+        assert_eq!(
+            from_le_bytes::<u32>(&[1, 2, 3]),
+            Err(CodecError::LengthMismatch { expected: 4, found: 3 })
+        );
+    }
+
+    #[test]
+    fn leb128_known_encodings() {
+        let mut out = Vec::new();
+        encode_uleb128(624485, &mut out);
+        assert_eq!(out, [0xe5, 0x8e, 0x26]);
+        assert_eq!(decode_uleb128(&out), Ok((624485, 3)));
+
+        out.clear();
+        encode_sleb128(-123456, &mut out);
+        assert_eq!(out, [0xc0, 0xbb, 0x78]);
+        assert_eq!(decode_sleb128(&out), Ok((-123456, 3)));
+
+        for v in [0, 1, 127, 128, 300, u64::MAX] {
+            out.clear();
+            encode_uleb128(v, &mut out);
+            assert_eq!(decode_uleb128(&out), Ok((v, out.len())));
+        }
+        for v in [0, 1, -1, 63, 64, -64, -65, i64::MIN, i64::MAX] {
+            out.clear();
+            encode_sleb128(v, &mut out);
+            assert_eq!(decode_sleb128(&out), Ok((v, out.len())), "value {}", v);
+        }
+    }
+
+    #[test]
+    fn leb128_errors() {
+        assert!(matches!(decode_uleb128(&[0x80, 0x80]), Err(CodecError::UnexpectedEof { .. })));
+        let too_long = [0xff; 10].iter().copied().chain([0x01]).collect::<Vec<u8>>();
+        assert_eq!(decode_uleb128(&too_long), Err(CodecError::VarintTooLong { offset: 0 }));
+        let mut max = [0xff; 10];
+        max[9] = 0x01;
+        assert_eq!(decode_uleb128(&max), Ok((u64::MAX, 10)));
+        max[9] = 0x02;
+        assert_eq!(decode_uleb128(&max), Err(CodecError::VarintTooLong { offset: 0 }));
+    }
+
+    #[test]
+    fn sleb128_rejects_a_bad_tenth_byte() {
+        let mut out = Vec::new();
+        encode_sleb128(i64::MIN, &mut out);
+        assert_eq!(out, [0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x7f]);
+        assert_eq!(decode_sleb128(&out), Ok((i64::MIN, 10)));
+        out.clear();
+        encode_sleb128(i64::MAX, &mut out);
+        assert_eq!(out, [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00]);
+        assert_eq!(decode_sleb128(&out), Ok((i64::MAX, 10)));
+
+        // Only 0x00 and 0x7f fit in bit 63 without overflowing or continuing
+        for last in [0x01, 0x7e, 0x40, 0x3f, 0x80, 0xff] {
+            let mut bytes = [0x80; 10];
+            bytes[9] = last;
+            assert_eq!(decode_sleb128(&bytes), Err(CodecError::VarintTooLong { offset: 0 }), "last byte {:#04x}", last);
+        }
+        let too_long = [0x80; 10].iter().copied().chain([0x00]).collect::<Vec<u8>>();
+        assert_eq!(decode_sleb128(&too_long), Err(CodecError::VarintTooLong { offset: 0 }));
+    }
+
+    #[test]
+    fn reader_and_writer_check_bounds() {
+        let mut buf = [0u8; 8];
+        let mut w = ByteWriter::new(&mut buf, Endian::Big);
+        w.write(0x0102u16).unwrap();
+        w.write_uleb128(300).unwrap();
+        assert_eq!(w.written(), [0x01, 0x02, 0xac, 0x02]);
+        assert_eq!(
+            w.write(1u64),
+            Err(CodecError::BufferFull { offset: 4, needed: 8, remaining: 4 })
+        );
+        w.write(-1i32).unwrap();
+        assert_eq!(w.remaining(), 0);
+
+        let mut r = ByteReader::new(&buf, Endian::Big);
+        assert_eq!(r.read::<u16>(), Ok(0x0102));
+        assert_eq!(r.read_uleb128(), Ok(300));
+        assert_eq!(
+            r.read::<u64>(),
+            Err(CodecError::UnexpectedEof { offset: 4, needed: 8, available: 4 })
+        );
+        assert_eq!(r.read::<i32>(), Ok(-1));
+        assert!(r.is_empty());
+    }
+}
@@ -0,0 +1,269 @@
+// combinators.rs
+// Generic function combinators, growing `apply` / `make_adder` from ch04_11 and
+// `apply_twice` / `make_multiplier` from ch04_13.
+//
+// Every combinator takes closures and returns a new closure. Which Fn trait each
+// one asks for is part of the lesson:
+//   Fn      called through `&self`: may be called any number of times, even shared
+//   FnMut   called through `&mut self`: may change what it captured between calls
+//   FnOnce  called through `self`: may move out of what it captured, so only once
+// A bound should be the loosest one the combinator can work with. `apply_n` calls
+// its function in sequence, so FnMut is enough. `curry` hands out many copies of
+// the same function, so it needs Fn.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::rc::Rc;
+
+/// `compose(f, g)` is `f ∘ g`: it applies `g` first, then `f`.
+///
+/// ```
+/// use rcrash_core::combinators::compose;
+///
+/// let inc_then_double = compose(|x: i32| x * 2, |x: i32| x + 1);
+/// assert_eq!(inc_then_double(5), 12);
+/// ```
+///
+/// Both functions must be `Fn`, so a closure that mutates its captures is rejected:
+///
+/// ```compile_fail
+/// use rcrash_core::combinators::compose;
+///
+/// let mut total = 0;
+/// let running_sum = |x: i32| { total += x; total };
+/// let f = compose(running_sum, |x: i32| x + 1); // expected `Fn`, found `FnMut`
+/// ```
+pub fn compose<A, B, C>(f: impl Fn(B) -> C, g: impl Fn(A) -> B) -> impl Fn(A) -> C {
+    move |x| f(g(x))
+}
+
+/// `pipe(f, g)` applies `f` first, then `g`, in reading order.
+///
+/// The result is only `FnMut` when the inputs are, so a pipeline of stateful
+/// steps still works:
+///
+/// ```
+/// use rcrash_core::combinators::pipe;
+///
+/// let mut seen = Vec::new();
+/// let mut record_then_square = pipe(|x: i32| { seen.push(x); x }, |x: i32| x * x);
+/// assert_eq!(record_then_square(3), 9);
+/// assert_eq!(record_then_square(4), 16);
+/// drop(record_then_square);
+/// assert_eq!(seen, [3, 4]);
+/// ```
+pub fn pipe<A, B, C>(mut f: impl FnMut(A) -> B, mut g: impl FnMut(B) -> C) -> impl FnMut(A) -> C {
+    move |x| g(f(x))
+}
+
+/// Turns a two-argument function into one that takes its arguments one at a time.
+///
+/// Each partial application holds a shared handle to `f`, so `f` must be `Fn`:
+/// any number of partial applications may call it.
+///
+/// ```
+/// use rcrash_core::combinators::curry;
+///
+/// let add = curry(|a: i32, b: i32| a + b);
+/// let add_100 = add(100); // the same as `make_adder(100)` in ch04_11
+/// assert_eq!(add_100(23), 123);
+/// assert_eq!(add(1)(2), 3);
+/// ```
+pub fn curry<A, B, C, F>(f: F) -> impl Fn(A) -> Box<dyn Fn(B) -> C>
+where
+    A: Clone + 'static,
+    F: Fn(A, B) -> C + 'static,
+{
+    let f = Rc::new(f);
+    move |a| {
+        let f = Rc::clone(&f);
+        Box::new(move |b| f(a.clone(), b))
+    }
+}
+
+/// The inverse of `curry`: takes both arguments at once.
+///
+/// ```
+/// use rcrash_core::combinators::uncurry;
+///
+/// let mul = uncurry(|a: i32| move |b: i32| a * b);
+/// assert_eq!(mul(6, 7), 42);
+/// ```
+pub fn uncurry<A, B, C, G>(f: impl Fn(A) -> G) -> impl Fn(A, B) -> C
+where
+    G: FnOnce(B) -> C,
+{
+    // The inner function is used once per call, so FnOnce is enough for it
+    move |a, b| f(a)(b)
+}
+
+/// Swaps the two arguments of `f`.
+///
+/// ```
+/// use rcrash_core::combinators::flip;
+///
+/// let minus = |a: i32, b: i32| a - b;
+/// assert_eq!(flip(minus)(8, 3), -5);
+/// ```
+pub fn flip<A, B, C>(f: impl Fn(A, B) -> C) -> impl Fn(B, A) -> C {
+    move |b, a| f(a, b)
+}
+
+/// Applies `f` to `x` `n` times: `apply_n(f, 2, x)` is `apply_twice(f, x)`.
+///
+/// The calls happen one after another, so `f` only needs to be `FnMut` and may
+/// keep state between them:
+///
+/// ```
+/// use rcrash_core::combinators::apply_n;
+///
+/// assert_eq!(apply_n(|x| x * x, 2, 2), 16);
+///
+/// let mut calls = 0;
+/// let collatz = |n: u64| {
+///     calls += 1;
+///     if n % 2 == 0 { n / 2 } else { 3 * n + 1 }
+/// };
+/// assert_eq!(apply_n(collatz, 4, 6), 16); // 6 -> 3 -> 10 -> 5 -> 16
+/// assert_eq!(calls, 4);
+/// ```
+///
+/// A closure that moves out of its captures is only `FnOnce` and cannot be called twice:
+///
+/// ```compile_fail
+/// use rcrash_core::combinators::apply_n;
+///
+/// let name = String::from("rust");
+/// apply_n(move |s: String| { drop(name); s }, 2, String::new()); // expected `FnMut`, found `FnOnce`
+/// ```
+pub fn apply_n<T>(mut f: impl FnMut(T) -> T, n: usize, x: T) -> T {
+    (0..n).fold(x, |acc, _| f(acc))
+}
+
+/// Wraps `f` with a cache. The `HashMap` is captured by the returned closure and
+/// updated on each new argument, which is why the result is `FnMut` even though
+/// `f` is only `Fn`.
+///
+/// ```
+/// use rcrash_core::combinators::memoize;
+/// use std::cell::Cell;
+///
+/// let calls = Cell::new(0);
+/// let mut slow_square = memoize(|x: u64| {
+///     calls.set(calls.get() + 1);
+///     x * x
+/// });
+/// assert_eq!(slow_square(12), 144);
+/// assert_eq!(slow_square(12), 144);
+/// assert_eq!(calls.get(), 1);
+/// ```
+pub fn memoize<A, R>(f: impl Fn(A) -> R) -> impl FnMut(A) -> R
+where
+    A: Eq + Hash + Clone,
+    R: Clone,
+{
+    let mut cache = HashMap::new();
+    move |a: A| {
+        if let Some(hit) = cache.get(&a) {
+            return R::clone(hit);
+        }
+        let result = f(a.clone());
+        cache.insert(a, result.clone());
+        result
+    }
+}
+
+/// Lets an `FnOnce` be called like an `FnMut`: the first call runs `f`, and every
+/// later call returns `None`.
+///
+/// ```
+/// use rcrash_core::combinators::once;
+///
+/// let greeting = String::from("hello");
+/// // `into_bytes` moves the String out, so this closure is only FnOnce
+/// let mut take_bytes = once(move |()| greeting.into_bytes());
+/// assert_eq!(take_bytes(()), Some(b"hello".to_vec()));
+/// assert_eq!(take_bytes(()), None);
+/// ```
+pub fn once<A, R>(f: impl FnOnce(A) -> R) -> impl FnMut(A) -> Option<R> {
+    let mut f = Some(f);
+    move |a| f.take().map(|f| f(a))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn compose_and_pipe_order() {
+        let add_one = |x: i32| x + 1;
+        let double = |x: i32| x * 2;
+        assert_eq!(compose(add_one, double)(5), 11);
+        assert_eq!(pipe(add_one, double)(5), 12);
+        // compose(f, g) is pipe(g, f)
+        let (mut piped, composed) = (pipe(double, add_one), compose(add_one, double));
+        assert!((-10..10).all(|x| piped(x) == composed(x)));
+        let to_text = compose(|n: usize| format!("{} chars", n), |s: &str| s.chars().count());
+        assert_eq!(to_text("héllo"), "5 chars");
+    }
+
+    #[test]
+    fn curry_uncurry_and_flip_round_trip() {
+        let sub = |a: i32, b: i32| a - b;
+        let curried = curry(sub);
+        let uncurried = uncurry(&curried);
+        let flipped = flip(sub);
+        for (a, b) in [(8, 3), (-2, 5), (0, 0)] {
+            assert_eq!(curried(a)(b), sub(a, b));
+            assert_eq!(uncurried(a, b), sub(a, b));
+            assert_eq!(flipped(a, b), sub(b, a));
+        }
+        // Partial applications outlive each other and share the same function
+        let greet = curry(|greeting: String, name: &str| format!("{}, {}!", greeting, name));
+        let hello = greet("Hello".to_string());
+        let hi = greet("Hi".to_string());
+        assert_eq!((hello("Ferris"), hi("Rust")), ("Hello, Ferris!".into(), "Hi, Rust!".into()));
+    }
+
+    #[test]
+    fn apply_n_counts_calls() {
+        assert_eq!(apply_n(|x: i32| x + 3, 0, 7), 7);
+        let mut log = Vec::new();
+        let doubled = apply_n(
+            |x: u32| {
+                log.push(x);
+                x * 2
+            },
+            4,
+            1,
+        );
+        assert_eq!((doubled, log), (16, vec![1, 2, 4, 8]));
+    }
+
+    #[test]
+    fn memoize_calls_each_argument_once() {
+        let calls = Cell::new(0);
+        let mut len = memoize(|s: String| {
+            calls.set(calls.get() + 1);
+            s.len()
+        });
+        for word in ["a", "bb", "a", "ccc", "bb", "a"] {
+            assert_eq!(len(word.to_string()), word.len());
+        }
+        assert_eq!(calls.get(), 3);
+    }
+
+    #[test]
+    fn once_runs_only_the_first_time() {
+        let calls = Cell::new(0);
+        let mut init = once(|x: i32| {
+            calls.set(calls.get() + 1);
+            x * 10
+        });
+        assert_eq!(init(4), Some(40));
+        assert_eq!(init(5), None);
+        assert_eq!(init(6), None);
+        assert_eq!(calls.get(), 1);
+    }
+}
@@ -0,0 +1,411 @@
+// countdown.rs
+// 4.5 while loops: a countdown that keeps real time
+// `while n > 0 { n -= 1 }` counts down instantly. A real timer ticks once per
+// second, and `sleep(1s)` in a loop drifts: every tick adds the loop's own
+// running time and the OS wake-up delay. Here every deadline is computed from
+// the start instant (start + 1s, start + 2s, ...), so a late wake-up never
+// delays the ticks after it. Pause, resume and stop arrive over a channel.
+
+use std::fmt;
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Why a duration such as `1m30s` could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DurationError {
+    Empty,
+    /// A unit without a number in front of it, as in `m30s`.
+    MissingNumber { unit: String },
+    /// A number without a unit after it, as in `1m30` (a bare `90` is fine).
+    MissingUnit { number: String },
+    UnknownUnit { unit: String },
+    TooLong,
+}
+
+impl fmt::Display for DurationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DurationError::Empty => write!(f, "empty duration"),
+            DurationError::MissingNumber { unit } => write!(f, "'{}' needs a number in front of it", unit),
+            DurationError::MissingUnit { number } => {
+                write!(f, "'{}' needs a unit after it (h, m, s or ms)", number)
+            }
+            DurationError::UnknownUnit { unit } => write!(f, "unknown unit '{}' (expected h, m, s or ms)", unit),
+            DurationError::TooLong => write!(f, "duration is too long"),
+        }
+    }
+}
+
+impl std::error::Error for DurationError {}
+
+/// Parses `90`, `90s`, `1m30s`, `1h2m3s` or `500ms`. A bare number is seconds.
+pub fn parse_duration(text: &str) -> Result<Duration, DurationError> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err(DurationError::Empty);
+    }
+    if let Ok(seconds) = text.parse::<u64>() {
+        return Ok(Duration::from_secs(seconds));
+    }
+    let mut total = Duration::ZERO;
+    let mut rest = text;
+    while !rest.is_empty() {
+        let digits = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+        let (number, after) = rest.split_at(digits);
+        let unit_len = after.find(|c: char| c.is_ascii_digit()).unwrap_or(after.len());
+        let (unit, after) = after.split_at(unit_len);
+        if number.is_empty() {
+            return Err(DurationError::MissingNumber { unit: unit.to_string() });
+        }
+        if unit.is_empty() {
+            return Err(DurationError::MissingUnit { number: number.to_string() });
+        }
+        let n: u64 = number.parse().map_err(|_| DurationError::TooLong)?;
+        let part = match unit {
+            "h" => n.checked_mul(3600).map(Duration::from_secs),
+            "m" => n.checked_mul(60).map(Duration::from_secs),
+            "s" => Some(Duration::from_secs(n)),
+            "ms" => Some(Duration::from_millis(n)),
+            _ => return Err(DurationError::UnknownUnit { unit: unit.to_string() }),
+        };
+        total = part.and_then(|p| total.checked_add(p)).ok_or(DurationError::TooLong)?;
+        rest = after;
+    }
+    Ok(total)
+}
+
+/// `01:30`, or `1:02:03` once there are hours. Fractions of a second are dropped.
+pub fn format_clock(d: Duration) -> String {
+    let secs = d.as_secs();
+    let (h, m, s) = (secs / 3600, secs / 60 % 60, secs % 60);
+    if h > 0 {
+        format!("{}:{:02}:{:02}", h, m, s)
+    } else {
+        format!("{:02}:{:02}", m, s)
+    }
+}
+
+/// Messages the timer accepts while it runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Control {
+    Pause,
+    Resume,
+    /// Pause when running, resume when paused.
+    Toggle,
+    Stop,
+}
+
+/// What a clock's `wait` returned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Wait {
+    Message(Control),
+    /// The deadline passed without a message.
+    Timeout,
+    /// There will never be another message.
+    Closed,
+}
+
+/// Where the timer gets the time from. Tests pass a fake clock that jumps
+/// straight to each deadline instead of sleeping.
+pub trait Clock {
+    fn now(&self) -> Instant;
+
+    /// Waits for a control message until `deadline`, or forever without one.
+    fn wait(&self, controls: &Receiver<Control>, deadline: Option<Instant>) -> Wait;
+}
+
+/// The real clock: `Instant::now` and a blocking channel receive.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn wait(&self, controls: &Receiver<Control>, deadline: Option<Instant>) -> Wait {
+        let Some(deadline) = deadline else {
+            return controls.recv().map_or(Wait::Closed, Wait::Message);
+        };
+        let timeout = deadline.saturating_duration_since(Instant::now());
+        match controls.recv_timeout(timeout) {
+            Ok(control) => Wait::Message(control),
+            Err(RecvTimeoutError::Timeout) => Wait::Timeout,
+            // Nobody can pause us any more, but the deadline still counts
+            Err(RecvTimeoutError::Disconnected) => {
+                thread::sleep(deadline.saturating_duration_since(Instant::now()));
+                Wait::Timeout
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Counts down from the duration to zero.
+    Countdown(Duration),
+    /// Counts up, until stopped or until the optional limit.
+    Stopwatch(Option<Duration>),
+}
+
+/// Reported to the display callback. Each carries the time to show:
+/// remaining time for a countdown, elapsed time for a stopwatch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    Tick(Duration),
+    Paused(Duration),
+    Resumed(Duration),
+    Finished,
+    Stopped(Duration),
+}
+
+/// How `Timer::run` ended, with the total running time (pauses excluded).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Finished(Duration),
+    Stopped(Duration),
+}
+
+pub struct Timer<C: Clock> {
+    clock: C,
+    mode: Mode,
+    interval: Duration,
+}
+
+impl<C: Clock> Timer<C> {
+    /// A timer that ticks once per second.
+    pub fn new(clock: C, mode: Mode) -> Timer<C> {
+        Timer { clock, mode, interval: Duration::from_secs(1) }
+    }
+
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    /// The duration to display after `elapsed` running time. A countdown shows
+    /// whole seconds rounded up, so it reads 00:01 until it really is over.
+    fn shown(&self, elapsed: Duration) -> Duration {
+        match self.mode {
+            Mode::Countdown(total) => {
+                let remaining = total.saturating_sub(elapsed);
+                let whole = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+                Duration::from_secs(whole)
+            }
+            Mode::Stopwatch(_) => elapsed,
+        }
+    }
+
+    fn limit(&self) -> Option<Duration> {
+        match self.mode {
+            Mode::Countdown(total) => Some(total),
+            Mode::Stopwatch(limit) => limit,
+        }
+    }
+
+    /// Runs until the countdown reaches zero, the stopwatch reaches its limit,
+    /// or a `Stop` arrives. Every event goes to `on_event`.
+    pub fn run(&self, controls: &Receiver<Control>, mut on_event: impl FnMut(Event)) -> Outcome {
+        // Running time from earlier segments; a segment ends at each pause
+        let mut banked = Duration::ZERO;
+        let mut segment_start = self.clock.now();
+        let mut paused = false;
+        on_event(Event::Tick(self.shown(banked)));
+
+        loop {
+            if paused {
+                match self.clock.wait(controls, None) {
+                    Wait::Message(Control::Resume | Control::Toggle) => {
+                        paused = false;
+                        segment_start = self.clock.now();
+                        on_event(Event::Resumed(self.shown(banked)));
+                    }
+                    Wait::Message(Control::Pause) | Wait::Timeout => {}
+                    Wait::Message(Control::Stop) | Wait::Closed => {
+                        on_event(Event::Stopped(self.shown(banked)));
+                        return Outcome::Stopped(banked);
+                    }
+                }
+                continue;
+            }
+
+            // The next whole interval since the segment started, computed from
+            // the start instead of from "now", so lateness does not accumulate
+            let in_segment = self.clock.now().saturating_duration_since(segment_start);
+            let ticks_done = (in_segment.as_nanos() / self.interval.as_nanos()) as u32;
+            let mut next = self.interval * (ticks_done + 1);
+            if let Some(limit) = self.limit() {
+                next = next.min(limit.saturating_sub(banked));
+            }
+            let deadline = segment_start + next;
+
+            match self.clock.wait(controls, Some(deadline)) {
+                Wait::Timeout | Wait::Closed => {
+                    let elapsed = banked + next;
+                    if self.limit().is_some_and(|limit| elapsed >= limit) {
+                        if let Mode::Stopwatch(_) = self.mode {
+                            on_event(Event::Tick(self.shown(elapsed)));
+                        }
+                        on_event(Event::Finished);
+                        return Outcome::Finished(elapsed);
+                    }
+                    on_event(Event::Tick(self.shown(elapsed)));
+                }
+                Wait::Message(Control::Pause | Control::Toggle) => {
+                    banked += self.clock.now().saturating_duration_since(segment_start);
+                    paused = true;
+                    on_event(Event::Paused(self.shown(banked)));
+                }
+                Wait::Message(Control::Resume) => {}
+                Wait::Message(Control::Stop) => {
+                    banked += self.clock.now().saturating_duration_since(segment_start);
+                    on_event(Event::Stopped(self.shown(banked)));
+                    return Outcome::Stopped(banked);
+                }
+            }
+        }
+    }
+}
+
+/// The text for one redraw of the status line.
+pub fn status_line(event: Event) -> String {
+    match event {
+        Event::Tick(shown) => format!("⏱  {}", format_clock(shown)),
+        Event::Paused(shown) => format!("⏸  {}  (paused, press Enter to resume)", format_clock(shown)),
+        Event::Resumed(shown) => format!("⏱  {}", format_clock(shown)),
+        Event::Finished => "Liftoff!".to_string(),
+        Event::Stopped(shown) => format!("⏹  {}  (stopped)", format_clock(shown)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::{Cell, RefCell};
+    use std::collections::VecDeque;
+    use std::sync::mpsc;
+
+    /// Jumps to each deadline (plus a fixed lateness) instead of sleeping, and
+    /// delivers scripted controls at given fake times.
+    struct FakeClock {
+        start: Instant,
+        offset: Cell<Duration>,
+        lateness: Duration,
+        script: RefCell<VecDeque<(Duration, Control)>>,
+    }
+
+    impl FakeClock {
+        fn new(lateness: Duration, script: &[(u64, Control)]) -> FakeClock {
+            FakeClock {
+                start: Instant::now(),
+                offset: Cell::new(Duration::ZERO),
+                lateness,
+                script: RefCell::new(script.iter().map(|&(ms, c)| (Duration::from_millis(ms), c)).collect()),
+            }
+        }
+
+        fn elapsed(&self) -> Duration {
+            self.offset.get()
+        }
+    }
+
+    impl Clock for FakeClock {
+        fn now(&self) -> Instant {
+            self.start + self.offset.get()
+        }
+
+        fn wait(&self, _controls: &Receiver<Control>, deadline: Option<Instant>) -> Wait {
+            let mut script = self.script.borrow_mut();
+            if let Some(&(at, control)) = script.front()
+                && deadline.is_none_or(|d| self.start + at <= d)
+            {
+                script.pop_front();
+                self.offset.set(self.offset.get().max(at));
+                return Wait::Message(control);
+            }
+            match deadline {
+                Some(deadline) => {
+                    self.offset.set(deadline - self.start + self.lateness);
+                    Wait::Timeout
+                }
+                None => Wait::Closed,
+            }
+        }
+    }
+
+    fn run(timer: &Timer<FakeClock>) -> (Outcome, Vec<(Event, Duration)>) {
+        let (_tx, rx) = mpsc::channel();
+        let mut events = Vec::new();
+        let outcome = timer.run(&rx, |e| events.push((e, timer.clock().elapsed())));
+        (outcome, events)
+    }
+
+    fn secs(s: u64) -> Duration {
+        Duration::from_secs(s)
+    }
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("1m30s"), Ok(secs(90)));
+        assert_eq!(parse_duration("90"), Ok(secs(90)));
+        assert_eq!(parse_duration("1h2m3s"), Ok(secs(3723)));
+        assert_eq!(parse_duration("1s500ms"), Ok(Duration::from_millis(1500)));
+        assert_eq!(parse_duration(""), Err(DurationError::Empty));
+        assert_eq!(parse_duration("1m30"), Err(DurationError::MissingUnit { number: "30".into() }));
+        assert_eq!(parse_duration("m"), Err(DurationError::MissingNumber { unit: "m".into() }));
+        assert_eq!(parse_duration("3d"), Err(DurationError::UnknownUnit { unit: "d".into() }));
+        assert_eq!(format_clock(secs(90)), "01:30");
+        assert_eq!(format_clock(secs(3723)), "1:02:03");
+    }
+
+    #[test]
+    fn countdown_ticks_without_drift() {
+        // Every wake-up is 30ms late, yet tick k still happens at k seconds + 30ms
+        let timer = Timer::new(FakeClock::new(Duration::from_millis(30), &[]), Mode::Countdown(secs(3)));
+        let (outcome, events) = run(&timer);
+        assert_eq!(outcome, Outcome::Finished(secs(3)));
+        let late = Duration::from_millis(30);
+        assert_eq!(
+            events,
+            [
+                (Event::Tick(secs(3)), secs(0)),
+                (Event::Tick(secs(2)), secs(1) + late),
+                (Event::Tick(secs(1)), secs(2) + late),
+                (Event::Finished, secs(3) + late),
+            ]
+        );
+    }
+
+    #[test]
+    fn pause_and_resume_exclude_paused_time() {
+        // Pause 1.5s in, resume at 10s, then finish 1.5s later
+        let script = [(1500, Control::Pause), (10_000, Control::Resume)];
+        let timer = Timer::new(FakeClock::new(Duration::ZERO, &script), Mode::Countdown(secs(3)));
+        let (outcome, events) = run(&timer);
+        assert_eq!(outcome, Outcome::Finished(secs(3)));
+        let shown: Vec<Event> = events.iter().map(|(e, _)| *e).collect();
+        assert_eq!(
+            shown,
+            [
+                Event::Tick(secs(3)),
+                Event::Tick(secs(2)),
+                Event::Paused(secs(2)), // 1.5s remaining rounds up
+                Event::Resumed(secs(2)),
+                Event::Tick(secs(1)), // 1s after resuming: 0.5s left
+                Event::Finished,
+            ]
+        );
+        assert_eq!(timer.clock().elapsed(), Duration::from_millis(11_500));
+    }
+
+    #[test]
+    fn stopwatch_stops_on_request() {
+        let timer = Timer::new(FakeClock::new(Duration::ZERO, &[(2500, Control::Stop)]), Mode::Stopwatch(None));
+        let (outcome, events) = run(&timer);
+        assert_eq!(outcome, Outcome::Stopped(Duration::from_millis(2500)));
+        assert_eq!(events.last().unwrap().0, Event::Stopped(Duration::from_millis(2500)));
+
+        let timer = Timer::new(FakeClock::new(Duration::ZERO, &[]), Mode::Stopwatch(Some(secs(2))));
+        assert_eq!(run(&timer).0, Outcome::Finished(secs(2)));
+    }
+}
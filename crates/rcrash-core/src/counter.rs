@@ -0,0 +1,339 @@
+// counter.rs
+// The `Counter` from ch04_10 grown into a range-like iterator:
+// a configurable step (negative counts down), an optional exclusive bound,
+// and a policy for what happens when the next value would cross the limit.
+//
+//   Counter::new(0).step(3).until(10)          0, 3, 6, 9        like (0..10).step_by(3)
+//   Counter::new(10).step(-4).until(0)         10, 6, 2
+//   Counter::new(0).step(4).until(10)
+//       .on_limit(OnLimit::Saturate)           0, 4, 8, 9        ends on the last value in range
+//   Counter::new(0).step(4).until(10)
+//       .wrapping()                            0, 4, 8, 2, 6, 0, 4, ...  never ends
+//
+// Without a bound the limit is the edge of i32 itself.
+// A stopping or saturating counter is finite, so `Counter` implements
+// `DoubleEndedIterator` and `ExactSizeIterator`. A wrapping counter never
+// ends, so it is a separate type, `WrappingCounter`, that is only an `Iterator`.
+
+use std::iter::FusedIterator;
+
+/// What a finite counter does when the next value would leave its range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnLimit {
+    /// End the sequence, as `step_by` does.
+    Stop,
+    /// Yield the last value inside the range once more, then end.
+    Saturate,
+}
+
+/// The inclusive range of values a counter may produce, widened to i64 so that
+/// `value + step` never overflows while checking it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Domain {
+    lo: i64,
+    hi: i64,
+}
+
+impl Domain {
+    /// With step > 0 the values are `start..end`; with step < 0 they are `(end+1)..=start`.
+    /// Returns `None` when the bound leaves no room for even `start`.
+    fn new(start: i32, step: i32, end: Option<i32>) -> Option<Domain> {
+        let (start, end) = (i64::from(start), end.map(i64::from));
+        let domain = match end {
+            None => Domain { lo: i64::from(i32::MIN), hi: i64::from(i32::MAX) },
+            Some(end) if step > 0 => Domain { lo: start, hi: end - 1 },
+            Some(end) => Domain { lo: end + 1, hi: start },
+        };
+        (domain.lo <= start && start <= domain.hi).then_some(domain)
+    }
+
+    /// The edge of the domain in the direction of travel.
+    fn edge(&self, step: i32) -> i64 {
+        if step > 0 { self.hi } else { self.lo }
+    }
+}
+
+/// A counter that yields `start, start + step, start + 2*step, ...` until it
+/// reaches its limit. It is also usable as a plain counter through `get` and `inc`.
+/// The builder methods (`step`, `until`, `on_limit`) start the count over.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Counter {
+    start: i32,
+    step: i32,
+    end: Option<i32>,
+    on_limit: OnLimit,
+    /// How many values have been taken from the front and from the back.
+    front: u64,
+    back: u64,
+}
+
+impl Counter {
+    /// Counts up by one from `start`, stopping at `i32::MAX`.
+    pub fn new(start: i32) -> Counter {
+        Counter { start, step: 1, end: None, on_limit: OnLimit::Stop, front: 0, back: 0 }
+    }
+
+    /// Sets the step. A negative step counts down.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is zero, like `Iterator::step_by`.
+    pub fn step(self, step: i32) -> Counter {
+        assert!(step != 0, "Counter step must be non-zero");
+        Counter { step, front: 0, back: 0, ..self }
+    }
+
+    /// Sets an exclusive bound: the counter never yields `end` or anything past it.
+    /// Counting down, `end` is a lower bound.
+    pub fn until(self, end: i32) -> Counter {
+        Counter { end: Some(end), front: 0, back: 0, ..self }
+    }
+
+    pub fn on_limit(self, on_limit: OnLimit) -> Counter {
+        Counter { on_limit, front: 0, back: 0, ..self }
+    }
+
+    /// Turns this into a counter that wraps back to the other end of its range
+    /// instead of stopping. Values already taken from the front are kept.
+    pub fn wrapping(self) -> WrappingCounter {
+        let domain = Domain::new(self.start, self.step, self.end);
+        let value = domain.map(|d| {
+            let offset = i128::from(self.start) - i128::from(d.lo)
+                + i128::from(self.front) * i128::from(self.step);
+            let width = i128::from(d.hi - d.lo + 1);
+            let value = i128::from(d.lo) + offset.rem_euclid(width);
+            i32::try_from(value).expect("wrapped value stays inside the domain")
+        });
+        WrappingCounter { value, step: self.step, domain }
+    }
+
+    /// The next value from the front, without consuming it.
+    pub fn get(&self) -> Option<i32> {
+        (self.remaining() > 0).then(|| self.value_at(self.front))
+    }
+
+    /// Moves to the next value. Returns `false` once the counter is exhausted.
+    pub fn inc(&mut self) -> bool {
+        self.next().is_some()
+    }
+
+    /// How many values the whole sequence has, before anything was taken.
+    fn total(&self) -> u64 {
+        let Some(domain) = Domain::new(self.start, self.step, self.end) else {
+            return 0;
+        };
+        let start = i64::from(self.start);
+        let step = i64::from(self.step);
+        // Whole steps that still land inside the domain
+        let span = (domain.edge(self.step) - start).abs();
+        let steps = span / step.abs();
+        let last = start + steps * step;
+        let saturated = self.on_limit == OnLimit::Saturate && last != domain.edge(self.step);
+        steps as u64 + 1 + u64::from(saturated)
+    }
+
+    fn remaining(&self) -> u64 {
+        self.total() - self.front - self.back
+    }
+
+    /// The value at position `index` of the whole sequence. Only valid for
+    /// `index < total()`; the saturated extra value is the domain edge.
+    fn value_at(&self, index: u64) -> i32 {
+        let start = i64::from(self.start);
+        let step = i64::from(self.step);
+        let domain = Domain::new(self.start, self.step, self.end).expect("a non-empty counter");
+        let edge = domain.edge(self.step);
+        let value = start + index as i64 * step;
+        let inside = if self.step > 0 { value <= edge } else { value >= edge };
+        let value = if inside { value } else { edge };
+        i32::try_from(value).expect("counter values stay inside i32")
+    }
+}
+
+impl Iterator for Counter {
+    type Item = i32;
+
+    fn next(&mut self) -> Option<i32> {
+        self.nth(0)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<i32> {
+        if (n as u64) >= self.remaining() {
+            self.front = self.total() - self.back;
+            return None;
+        }
+        self.front += n as u64;
+        let value = self.value_at(self.front);
+        self.front += 1;
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match usize::try_from(self.remaining()) {
+            Ok(len) => (len, Some(len)),
+            Err(_) => (usize::MAX, None),
+        }
+    }
+}
+
+impl DoubleEndedIterator for Counter {
+    fn next_back(&mut self) -> Option<i32> {
+        self.nth_back(0)
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<i32> {
+        if (n as u64) >= self.remaining() {
+            self.back = self.total() - self.front;
+            return None;
+        }
+        self.back += n as u64 + 1;
+        Some(self.value_at(self.total() - self.back))
+    }
+}
+
+impl ExactSizeIterator for Counter {}
+
+impl FusedIterator for Counter {}
+
+/// A counter that wraps around its range forever, like a clock hand.
+/// Built with `Counter::wrapping`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WrappingCounter {
+    /// `None` only when the range is empty.
+    value: Option<i32>,
+    step: i32,
+    domain: Option<Domain>,
+}
+
+impl WrappingCounter {
+    pub fn get(&self) -> Option<i32> {
+        self.value
+    }
+
+    /// Moves to the next value, wrapping past the limit. Returns `false` only for
+    /// an empty range.
+    pub fn inc(&mut self) -> bool {
+        let (Some(value), Some(domain)) = (self.value, self.domain) else {
+            return false;
+        };
+        let width = domain.hi - domain.lo + 1;
+        let next = i64::from(value) + i64::from(self.step);
+        let wrapped = domain.lo + (next - domain.lo).rem_euclid(width);
+        self.value = Some(i32::try_from(wrapped).expect("wrapped value stays inside the domain"));
+        true
+    }
+}
+
+impl Iterator for WrappingCounter {
+    type Item = i32;
+
+    fn next(&mut self) -> Option<i32> {
+        let value = self.value?;
+        self.inc();
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.value {
+            Some(_) => (usize::MAX, None),
+            None => (0, Some(0)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    const CASES: usize = 500;
+
+    #[test]
+    fn matches_step_by_on_ranges() {
+        let mut rng = StdRng::seed_from_u64(39);
+        for _ in 0..CASES {
+            let start = rng.random_range(-50..50);
+            let end = rng.random_range(-50..50);
+            let step = rng.random_range(1..8);
+            let counter = || Counter::new(start).step(step).until(end);
+            let std_iter = || (start..end).step_by(step as usize);
+
+            assert_eq!(counter().collect::<Vec<_>>(), std_iter().collect::<Vec<_>>());
+            assert_eq!(counter().rev().collect::<Vec<_>>(), std_iter().rev().collect::<Vec<_>>());
+            assert_eq!(counter().len(), std_iter().len());
+
+            // Random interleaving of front, back and skipping
+            let (mut ours, mut theirs) = (counter(), std_iter());
+            for _ in 0..10 {
+                let n = rng.random_range(0..3);
+                match rng.random_range(0..4) {
+                    0 => assert_eq!(ours.next(), theirs.next()),
+                    1 => assert_eq!(ours.next_back(), theirs.next_back()),
+                    2 => assert_eq!(ours.nth(n), theirs.nth(n)),
+                    _ => assert_eq!(ours.nth_back(n), theirs.nth_back(n)),
+                }
+                assert_eq!(ours.size_hint(), theirs.size_hint());
+            }
+        }
+    }
+
+    #[test]
+    fn negative_steps_count_down() {
+        let mut rng = StdRng::seed_from_u64(40);
+        for _ in 0..CASES {
+            let start = rng.random_range(-50..50);
+            let end = rng.random_range(-50..50);
+            let step = rng.random_range(1..8);
+            let ours: Vec<i32> = Counter::new(start).step(-step).until(end).collect();
+            let theirs: Vec<i32> = ((end + 1)..=start).rev().step_by(step as usize).collect();
+            assert_eq!(ours, theirs);
+        }
+        assert_eq!(Counter::new(10).step(-4).until(0).collect::<Vec<_>>(), [10, 6, 2]);
+    }
+
+    #[test]
+    fn limit_policies() {
+        let saturate = Counter::new(0).step(4).until(10).on_limit(OnLimit::Saturate);
+        assert_eq!(saturate.clone().collect::<Vec<_>>(), [0, 4, 8, 9]);
+        assert_eq!(saturate.rev().collect::<Vec<_>>(), [9, 8, 4, 0]);
+        // Already landing on the edge adds nothing
+        let exact = Counter::new(0).step(3).until(10).on_limit(OnLimit::Saturate);
+        assert_eq!(exact.collect::<Vec<_>>(), [0, 3, 6, 9]);
+
+        let wrapping: Vec<i32> = Counter::new(0).step(4).until(10).wrapping().take(7).collect();
+        assert_eq!(wrapping, [0, 4, 8, 2, 6, 0, 4]);
+        let down: Vec<i32> = Counter::new(3).step(-2).until(-1).wrapping().take(5).collect();
+        assert_eq!(down, [3, 1, 3, 1, 3]);
+        assert_eq!(Counter::new(5).until(5).wrapping().next(), None);
+    }
+
+    #[test]
+    fn unbounded_counters_use_the_i32_edge() {
+        let near_max = || Counter::new(i32::MAX - 4).step(3);
+        assert_eq!(near_max().collect::<Vec<_>>(), [i32::MAX - 4, i32::MAX - 1]);
+        let saturated: Vec<i32> = near_max().on_limit(OnLimit::Saturate).collect();
+        assert_eq!(saturated, [i32::MAX - 4, i32::MAX - 1, i32::MAX]);
+        let wrapped: Vec<i32> = near_max().wrapping().take(3).collect();
+        assert_eq!(wrapped, [i32::MAX - 4, i32::MAX - 1, i32::MIN + 1]);
+        assert_eq!(Counter::new(0).len(), 1 << 31);
+        let down: Vec<i32> = Counter::new(i32::MIN + 1).step(-1).collect();
+        assert_eq!(down, [i32::MIN + 1, i32::MIN]);
+    }
+
+    #[test]
+    fn get_and_inc() {
+        let mut c = Counter::new(10).until(12);
+        assert_eq!(c.get(), Some(10));
+        assert!(c.inc());
+        assert_eq!(c.get(), Some(11));
+        assert!(c.inc());
+        assert_eq!(c.get(), None);
+        assert!(!c.inc());
+
+        let mut clock = Counter::new(58).until(60).wrapping();
+        clock.inc();
+        clock.inc();
+        assert_eq!(clock.get(), Some(58));
+    }
+}
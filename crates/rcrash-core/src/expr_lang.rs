@@ -0,0 +1,976 @@
+// expr_lang.rs
+// 4.2 Expressions: a tiny Rust-like expression language
+// Source text goes through a lexer, a Pratt parser that builds an AST, and a
+// tree-walking evaluator. Everything is an expression, so a block's value is
+// its last expression, and a trailing `;` turns that value into `()`.
+//
+//   let x = { let a = 10; a * 2 };
+//   let mut i = 0;
+//   let r = loop { i = i + 1; if i > 5 { break i * 10 } };
+//   match r { 0..=9 => 1, 60 | 70 => 2, _ => 3 }
+
+use std::collections::HashMap;
+use std::fmt;
+
+/// A byte range in the source text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    fn to(self, other: Span) -> Span {
+        Span { start: self.start, end: other.end }
+    }
+}
+
+/// Any lexing, parsing or evaluation error, pointing at the code responsible.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    pub message: String,
+    pub span: Span,
+}
+
+impl Error {
+    fn new(message: impl Into<String>, span: Span) -> Error {
+        Error { message: message.into(), span }
+    }
+
+    /// Formats the error with the offending line and a `^^^` underline:
+    ///
+    /// ```text
+    /// error: attempt to divide by zero
+    ///  --> 1:5
+    ///   |
+    /// 1 | 1 + 2 / 0
+    ///   |     ^^^^^
+    /// ```
+    pub fn render(&self, source: &str) -> String {
+        let start = self.span.start.min(source.len());
+        let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = source[start..].find('\n').map_or(source.len(), |i| start + i);
+        let line_no = source[..start].matches('\n').count() + 1;
+        let column = source[line_start..start].chars().count();
+        let end = self.span.end.clamp(start, line_end);
+        let width = source[start..end].chars().count().max(1);
+        let gutter = " ".repeat(line_no.to_string().len());
+        format!(
+            "error: {}\n{}--> {}:{}\n{} |\n{} | {}\n{} | {}{}",
+            self.message,
+            gutter,
+            line_no,
+            column + 1,
+            gutter,
+            line_no,
+            &source[line_start..line_end],
+            gutter,
+            " ".repeat(column),
+            "^".repeat(width)
+        )
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at {}..{}", self.message, self.span.start, self.span.end)
+    }
+}
+
+impl std::error::Error for Error {}
+
+// ---------------------------------------------------------------------------
+// Lexer
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Token {
+    Int(i64),
+    Ident(String),
+    Let,
+    Mut,
+    If,
+    Else,
+    Match,
+    Loop,
+    Break,
+    True,
+    False,
+    Underscore,
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    Percent,
+    Bang,
+    Eq,
+    EqEq,
+    NotEq,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    AndAnd,
+    OrOr,
+    Pipe,
+    FatArrow,
+    DotDot,
+    DotDotEq,
+    Comma,
+    Semi,
+    LParen,
+    RParen,
+    LBrace,
+    RBrace,
+    Eof,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            Token::Int(n) => return write!(f, "`{}`", n),
+            Token::Ident(name) => return write!(f, "`{}`", name),
+            Token::Eof => return write!(f, "end of input"),
+            Token::Let => "let",
+            Token::Mut => "mut",
+            Token::If => "if",
+            Token::Else => "else",
+            Token::Match => "match",
+            Token::Loop => "loop",
+            Token::Break => "break",
+            Token::True => "true",
+            Token::False => "false",
+            Token::Underscore => "_",
+            Token::Plus => "+",
+            Token::Minus => "-",
+            Token::Star => "*",
+            Token::Slash => "/",
+            Token::Percent => "%",
+            Token::Bang => "!",
+            Token::Eq => "=",
+            Token::EqEq => "==",
+            Token::NotEq => "!=",
+            Token::Lt => "<",
+            Token::Le => "<=",
+            Token::Gt => ">",
+            Token::Ge => ">=",
+            Token::AndAnd => "&&",
+            Token::OrOr => "||",
+            Token::Pipe => "|",
+            Token::FatArrow => "=>",
+            Token::DotDot => "..",
+            Token::DotDotEq => "..=",
+            Token::Comma => ",",
+            Token::Semi => ";",
+            Token::LParen => "(",
+            Token::RParen => ")",
+            Token::LBrace => "{",
+            Token::RBrace => "}",
+        };
+        write!(f, "`{}`", text)
+    }
+}
+
+/// Two- and three-character operators come first so `..=` wins over `..`.
+const OPERATORS: [(&str, Token); 25] = [
+    ("..=", Token::DotDotEq),
+    ("..", Token::DotDot),
+    ("=>", Token::FatArrow),
+    ("==", Token::EqEq),
+    ("!=", Token::NotEq),
+    ("<=", Token::Le),
+    (">=", Token::Ge),
+    ("&&", Token::AndAnd),
+    ("||", Token::OrOr),
+    ("+", Token::Plus),
+    ("-", Token::Minus),
+    ("*", Token::Star),
+    ("/", Token::Slash),
+    ("%", Token::Percent),
+    ("!", Token::Bang),
+    ("=", Token::Eq),
+    ("<", Token::Lt),
+    (">", Token::Gt),
+    ("|", Token::Pipe),
+    (",", Token::Comma),
+    (";", Token::Semi),
+    ("(", Token::LParen),
+    (")", Token::RParen),
+    ("{", Token::LBrace),
+    ("}", Token::RBrace),
+];
+
+fn keyword(word: &str) -> Option<Token> {
+    Some(match word {
+        "let" => Token::Let,
+        "mut" => Token::Mut,
+        "if" => Token::If,
+        "else" => Token::Else,
+        "match" => Token::Match,
+        "loop" => Token::Loop,
+        "break" => Token::Break,
+        "true" => Token::True,
+        "false" => Token::False,
+        "_" => Token::Underscore,
+        _ => return None,
+    })
+}
+
+/// Splits source text into tokens, ending with `Token::Eof`.
+/// `//` comments run to the end of the line.
+pub fn tokenize(source: &str) -> Result<Vec<(Token, Span)>, Error> {
+    let bytes = source.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let start = i;
+        let rest = &source[i..];
+        let c = bytes[i];
+        let token = if c.is_ascii_whitespace() {
+            i += 1;
+            continue;
+        } else if rest.starts_with("//") {
+            i = rest.find('\n').map_or(source.len(), |n| i + n);
+            continue;
+        } else if c.is_ascii_digit() {
+            while i < bytes.len() && (bytes[i].is_ascii_digit() || bytes[i] == b'_') {
+                i += 1;
+            }
+            let value = source[start..i].replace('_', "").parse().map_err(|_| {
+                Error::new("integer literal is too large for i64", Span { start, end: i })
+            })?;
+            Token::Int(value)
+        } else if c.is_ascii_alphabetic() || c == b'_' {
+            while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_') {
+                i += 1;
+            }
+            let word = &source[start..i];
+            keyword(word).unwrap_or_else(|| Token::Ident(word.to_string()))
+        } else if let Some((text, token)) = OPERATORS.iter().find(|(text, _)| rest.starts_with(text)) {
+            i += text.len();
+            token.clone()
+        } else {
+            let ch = rest.chars().next().expect("i is on a char boundary");
+            let span = Span { start, end: start + ch.len_utf8() };
+            return Err(Error::new(format!("unexpected character '{}'", ch), span));
+        };
+        tokens.push((token, Span { start, end: i }));
+    }
+    tokens.push((Token::Eof, Span { start: source.len(), end: source.len() }));
+    Ok(tokens)
+}
+
+// ---------------------------------------------------------------------------
+// AST
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryOp {
+    Neg,
+    Not,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    And,
+    Or,
+}
+
+impl BinaryOp {
+    fn from_token(token: &Token) -> Option<BinaryOp> {
+        Some(match token {
+            Token::Plus => BinaryOp::Add,
+            Token::Minus => BinaryOp::Sub,
+            Token::Star => BinaryOp::Mul,
+            Token::Slash => BinaryOp::Div,
+            Token::Percent => BinaryOp::Rem,
+            Token::EqEq => BinaryOp::Eq,
+            Token::NotEq => BinaryOp::Ne,
+            Token::Lt => BinaryOp::Lt,
+            Token::Le => BinaryOp::Le,
+            Token::Gt => BinaryOp::Gt,
+            Token::Ge => BinaryOp::Ge,
+            Token::AndAnd => BinaryOp::And,
+            Token::OrOr => BinaryOp::Or,
+            _ => return None,
+        })
+    }
+
+    /// Left and right binding power: higher binds tighter. Left < right makes
+    /// an operator left-associative, so `1 - 2 - 3` is `(1 - 2) - 3`.
+    fn binding_power(self) -> (u8, u8) {
+        match self {
+            BinaryOp::Or => (1, 2),
+            BinaryOp::And => (3, 4),
+            BinaryOp::Eq | BinaryOp::Ne | BinaryOp::Lt | BinaryOp::Le | BinaryOp::Gt | BinaryOp::Ge => (5, 6),
+            BinaryOp::Add | BinaryOp::Sub => (7, 8),
+            BinaryOp::Mul | BinaryOp::Div | BinaryOp::Rem => (9, 10),
+        }
+    }
+
+    fn is_comparison(self) -> bool {
+        self.binding_power() == (5, 6)
+    }
+}
+
+/// Prefix operators bind tighter than any binary operator: `-2 * 3` is `(-2) * 3`.
+const PREFIX_POWER: u8 = 11;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expr {
+    pub kind: ExprKind,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExprKind {
+    Int(i64),
+    Bool(bool),
+    Var(String),
+    Unary(UnaryOp, Box<Expr>),
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+    Assign { name: String, value: Box<Expr> },
+    Block(Block),
+    If { condition: Box<Expr>, then_branch: Block, else_branch: Option<Box<Expr>> },
+    Match { scrutinee: Box<Expr>, arms: Vec<Arm> },
+    Loop(Block),
+    Break(Option<Box<Expr>>),
+}
+
+impl ExprKind {
+    /// Block-like expressions can end a statement without a `;`, as in Rust.
+    fn is_block_like(&self) -> bool {
+        matches!(self, ExprKind::Block(_) | ExprKind::If { .. } | ExprKind::Match { .. } | ExprKind::Loop(_))
+    }
+}
+
+/// `{ stmt; stmt; tail }`. Without a tail the block's value is `()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Block {
+    pub stmts: Vec<Stmt>,
+    pub tail: Option<Box<Expr>>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Stmt {
+    Let { name: String, mutable: bool, value: Expr, span: Span },
+    Expr(Expr),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Arm {
+    /// `1 | 2 | 5..=9` is three patterns.
+    pub patterns: Vec<Pattern>,
+    pub body: Expr,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pattern {
+    Int(i64),
+    Range { start: i64, end: i64, inclusive: bool },
+    Wildcard,
+}
+
+impl Pattern {
+    fn matches(self, n: i64) -> bool {
+        match self {
+            Pattern::Int(m) => n == m,
+            Pattern::Range { start, end, inclusive: true } => (start..=end).contains(&n),
+            Pattern::Range { start, end, inclusive: false } => (start..end).contains(&n),
+            Pattern::Wildcard => true,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Parser
+// ---------------------------------------------------------------------------
+
+struct Parser {
+    tokens: Vec<(Token, Span)>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.pos].0
+    }
+
+    fn span(&self) -> Span {
+        self.tokens[self.pos].1
+    }
+
+    fn previous_span(&self) -> Span {
+        self.tokens[self.pos.saturating_sub(1)].1
+    }
+
+    fn advance(&mut self) -> (Token, Span) {
+        let token = self.tokens[self.pos].clone();
+        if token.0 != Token::Eof {
+            self.pos += 1;
+        }
+        token
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.peek() == token {
+            self.advance();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &Token, context: &str) -> Result<Span, Error> {
+        if self.peek() == token {
+            Ok(self.advance().1)
+        } else {
+            Err(self.unexpected(&format!("{} {}", token, context)))
+        }
+    }
+
+    fn unexpected(&self, wanted: &str) -> Error {
+        Error::new(format!("expected {}, found {}", wanted, self.peek()), self.span())
+    }
+
+    /// Statements up to `end` (a `}` or end of input). `start` is where the
+    /// block's span begins.
+    fn block_body(&mut self, end: &Token, start: Span) -> Result<Block, Error> {
+        let mut stmts = Vec::new();
+        let mut tail = None;
+        while self.peek() != end {
+            if let Some(previous) = tail.take() {
+                // Only a block-like expression may be followed by more code
+                // without a `;`; it becomes a statement
+                stmts.push(Stmt::Expr(previous));
+            }
+            if self.peek() == &Token::Let {
+                stmts.push(self.let_statement()?);
+                continue;
+            }
+            let expr = self.expression(0)?;
+            if self.eat(&Token::Semi) {
+                stmts.push(Stmt::Expr(expr));
+            } else if self.peek() == end || expr.kind.is_block_like() {
+                tail = Some(expr);
+            } else {
+                return Err(self.unexpected("`;` or the end of the block"));
+            }
+        }
+        let end_span = self.span();
+        Ok(Block { stmts, tail: tail.map(Box::new), span: start.to(end_span) })
+    }
+
+    fn let_statement(&mut self) -> Result<Stmt, Error> {
+        let start = self.advance().1;
+        let mutable = self.eat(&Token::Mut);
+        let name = match self.advance() {
+            (Token::Ident(name), _) => name,
+            (token, span) => {
+                return Err(Error::new(format!("expected a variable name after `let`, found {}", token), span));
+            }
+        };
+        self.expect(&Token::Eq, "in `let`")?;
+        let value = self.expression(0)?;
+        let end = self.expect(&Token::Semi, "after `let`")?;
+        Ok(Stmt::Let { name, mutable, value, span: start.to(end) })
+    }
+
+    fn block(&mut self) -> Result<Block, Error> {
+        let start = self.expect(&Token::LBrace, "to start a block")?;
+        let block = self.block_body(&Token::RBrace, start)?;
+        self.advance();
+        Ok(block)
+    }
+
+    /// The Pratt loop: parse a prefix expression, then keep folding in binary
+    /// operators whose left binding power is at least `min_power`.
+    fn expression(&mut self, min_power: u8) -> Result<Expr, Error> {
+        let mut left = self.prefix()?;
+
+        // `x = value` has the lowest precedence and only applies to a name
+        if min_power == 0 && self.peek() == &Token::Eq {
+            if let ExprKind::Var(name) = &left.kind {
+                let name = name.clone();
+                self.advance();
+                let value = self.expression(0)?;
+                let span = left.span.to(value.span);
+                return Ok(Expr { kind: ExprKind::Assign { name, value: Box::new(value) }, span });
+            }
+            return Err(Error::new("only a variable can be assigned to", left.span));
+        }
+
+        while let Some(op) = BinaryOp::from_token(self.peek()) {
+            let (left_power, right_power) = op.binding_power();
+            if left_power < min_power {
+                break;
+            }
+            self.advance();
+            let right = self.expression(right_power)?;
+            // Like Rust, `a < b < c` is rejected instead of silently comparing a bool
+            if op.is_comparison() && BinaryOp::from_token(self.peek()).is_some_and(BinaryOp::is_comparison) {
+                return Err(Error::new("comparison operators cannot be chained", self.span()));
+            }
+            let span = left.span.to(right.span);
+            left = Expr { kind: ExprKind::Binary(op, Box::new(left), Box::new(right)), span };
+        }
+        Ok(left)
+    }
+
+    fn prefix(&mut self) -> Result<Expr, Error> {
+        let (token, span) = self.advance();
+        let kind = match token {
+            Token::Int(n) => ExprKind::Int(n),
+            Token::True => ExprKind::Bool(true),
+            Token::False => ExprKind::Bool(false),
+            Token::Ident(name) => ExprKind::Var(name),
+            Token::Minus | Token::Bang => {
+                let op = if token == Token::Minus { UnaryOp::Neg } else { UnaryOp::Not };
+                let operand = self.expression(PREFIX_POWER)?;
+                let span = span.to(operand.span);
+                return Ok(Expr { kind: ExprKind::Unary(op, Box::new(operand)), span });
+            }
+            Token::LParen => {
+                let inner = self.expression(0)?;
+                let end = self.expect(&Token::RParen, "to close `(`")?;
+                return Ok(Expr { kind: inner.kind, span: span.to(end) });
+            }
+            Token::LBrace => {
+                let block = self.block_body(&Token::RBrace, span)?;
+                self.advance();
+                let span = block.span;
+                return Ok(Expr { kind: ExprKind::Block(block), span });
+            }
+            Token::If => return self.if_expression(span),
+            Token::Match => return self.match_expression(span),
+            Token::Loop => {
+                let body = self.block()?;
+                let span = span.to(body.span);
+                return Ok(Expr { kind: ExprKind::Loop(body), span });
+            }
+            Token::Break => {
+                // `break` alone, or `break value`
+                let value = match self.peek() {
+                    Token::Semi | Token::RBrace | Token::Comma | Token::Eof => None,
+                    _ => Some(Box::new(self.expression(0)?)),
+                };
+                let span = value.as_ref().map_or(span, |v| span.to(v.span));
+                return Ok(Expr { kind: ExprKind::Break(value), span });
+            }
+            _ => {
+                self.pos -= usize::from(token != Token::Eof);
+                return Err(self.unexpected("an expression"));
+            }
+        };
+        Ok(Expr { kind, span })
+    }
+
+    fn if_expression(&mut self, start: Span) -> Result<Expr, Error> {
+        let condition = self.expression(0)?;
+        let then_branch = self.block()?;
+        let else_branch = if self.eat(&Token::Else) {
+            let else_start = self.span();
+            // `else if` chains are an `if` expression in the else branch
+            if self.eat(&Token::If) {
+                Some(Box::new(self.if_expression(else_start)?))
+            } else {
+                let block = self.block()?;
+                let span = block.span;
+                Some(Box::new(Expr { kind: ExprKind::Block(block), span }))
+            }
+        } else {
+            None
+        };
+        let end = else_branch.as_ref().map_or(then_branch.span, |e| e.span);
+        Ok(Expr {
+            kind: ExprKind::If { condition: Box::new(condition), then_branch, else_branch },
+            span: start.to(end),
+        })
+    }
+
+    fn match_expression(&mut self, start: Span) -> Result<Expr, Error> {
+        let scrutinee = self.expression(0)?;
+        self.expect(&Token::LBrace, "after the `match` value")?;
+        let mut arms = Vec::new();
+        while self.peek() != &Token::RBrace {
+            let mut patterns = vec![self.pattern()?];
+            while self.eat(&Token::Pipe) {
+                patterns.push(self.pattern()?);
+            }
+            self.expect(&Token::FatArrow, "after the pattern")?;
+            let body = self.expression(0)?;
+            // The comma is optional after a block body and before `}`
+            if !self.eat(&Token::Comma) && self.peek() != &Token::RBrace && !body.kind.is_block_like() {
+                return Err(self.unexpected("`,` between match arms"));
+            }
+            arms.push(Arm { patterns, body });
+        }
+        let end = self.advance().1;
+        Ok(Expr { kind: ExprKind::Match { scrutinee: Box::new(scrutinee), arms }, span: start.to(end) })
+    }
+
+    fn pattern_int(&mut self) -> Result<i64, Error> {
+        let negative = self.eat(&Token::Minus);
+        match self.advance() {
+            (Token::Int(n), _) => Ok(if negative { -n } else { n }),
+            (token, span) => Err(Error::new(format!("expected an integer pattern, found {}", token), span)),
+        }
+    }
+
+    fn pattern(&mut self) -> Result<Pattern, Error> {
+        if self.eat(&Token::Underscore) {
+            return Ok(Pattern::Wildcard);
+        }
+        let start_span = self.span();
+        let start = self.pattern_int()?;
+        let inclusive = match self.peek() {
+            Token::DotDotEq => true,
+            Token::DotDot => false,
+            _ => return Ok(Pattern::Int(start)),
+        };
+        self.advance();
+        let end = self.pattern_int()?;
+        let empty = if inclusive { start > end } else { start >= end };
+        if empty {
+            return Err(Error::new("this range pattern matches nothing", start_span.to(self.previous_span())));
+        }
+        Ok(Pattern::Range { start, end, inclusive })
+    }
+}
+
+/// Parses a whole program: statements and an optional final expression,
+/// like the inside of a block.
+pub fn parse(source: &str) -> Result<Block, Error> {
+    let mut parser = Parser { tokens: tokenize(source)?, pos: 0 };
+    let start = parser.span();
+    parser.block_body(&Token::Eof, start)
+}
+
+// ---------------------------------------------------------------------------
+// Evaluator
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Value {
+    Int(i64),
+    Bool(bool),
+    Unit,
+}
+
+impl Value {
+    fn type_name(self) -> &'static str {
+        match self {
+            Value::Int(_) => "integer",
+            Value::Bool(_) => "bool",
+            Value::Unit => "()",
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Int(n) => write!(f, "{}", n),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::Unit => write!(f, "()"),
+        }
+    }
+}
+
+/// How evaluation leaves an expression: normally with an error, or early
+/// through `break` on its way to the nearest `loop`.
+enum Unwind {
+    Error(Error),
+    Break(Value, Span),
+}
+
+impl From<Error> for Unwind {
+    fn from(e: Error) -> Unwind {
+        Unwind::Error(e)
+    }
+}
+
+struct Binding {
+    value: Value,
+    mutable: bool,
+}
+
+/// Runs programs, keeping top-level `let` bindings between calls so a REPL
+/// can build on earlier lines.
+pub struct Interpreter {
+    scopes: Vec<HashMap<String, Binding>>,
+    /// Guards against `loop {}` running forever.
+    max_iterations: u64,
+}
+
+impl Default for Interpreter {
+    fn default() -> Self {
+        Interpreter::new()
+    }
+}
+
+impl Interpreter {
+    pub fn new() -> Interpreter {
+        Interpreter { scopes: vec![HashMap::new()], max_iterations: 1_000_000 }
+    }
+
+    /// Parses and evaluates `source`.
+    pub fn run(&mut self, source: &str) -> Result<Value, Error> {
+        let program = parse(source)?;
+        self.run_block_in_scope(&program, false).map_err(|unwind| match unwind {
+            Unwind::Error(e) => e,
+            Unwind::Break(_, span) => Error::new("`break` outside of a loop", span),
+        })
+    }
+
+    fn run_block_in_scope(&mut self, block: &Block, new_scope: bool) -> Result<Value, Unwind> {
+        if new_scope {
+            self.scopes.push(HashMap::new());
+        }
+        let result = self.run_block(block);
+        if new_scope {
+            self.scopes.pop();
+        }
+        result
+    }
+
+    fn run_block(&mut self, block: &Block) -> Result<Value, Unwind> {
+        for stmt in &block.stmts {
+            match stmt {
+                Stmt::Let { name, mutable, value, .. } => {
+                    let value = self.eval(value)?;
+                    let scope = self.scopes.last_mut().expect("there is always a scope");
+                    // Shadowing: a new `let` replaces the old binding
+                    scope.insert(name.clone(), Binding { value, mutable: *mutable });
+                }
+                Stmt::Expr(expr) => {
+                    self.eval(expr)?;
+                }
+            }
+        }
+        match &block.tail {
+            Some(tail) => self.eval(tail),
+            None => Ok(Value::Unit),
+        }
+    }
+
+    fn lookup(&mut self, name: &str, span: Span) -> Result<&mut Binding, Error> {
+        self.scopes
+            .iter_mut()
+            .rev()
+            .find_map(|scope| scope.get_mut(name))
+            .ok_or_else(|| Error::new(format!("cannot find value `{}` in this scope", name), span))
+    }
+
+    fn int(&mut self, expr: &Expr) -> Result<i64, Unwind> {
+        match self.eval(expr)? {
+            Value::Int(n) => Ok(n),
+            other => Err(Error::new(format!("expected integer, found {}", other.type_name()), expr.span).into()),
+        }
+    }
+
+    fn bool(&mut self, expr: &Expr) -> Result<bool, Unwind> {
+        match self.eval(expr)? {
+            Value::Bool(b) => Ok(b),
+            other => Err(Error::new(format!("expected bool, found {}", other.type_name()), expr.span).into()),
+        }
+    }
+
+    fn eval(&mut self, expr: &Expr) -> Result<Value, Unwind> {
+        let span = expr.span;
+        match &expr.kind {
+            ExprKind::Int(n) => Ok(Value::Int(*n)),
+            ExprKind::Bool(b) => Ok(Value::Bool(*b)),
+            ExprKind::Var(name) => Ok(self.lookup(name, span)?.value),
+            ExprKind::Unary(UnaryOp::Neg, operand) => {
+                let n = self.int(operand)?;
+                n.checked_neg()
+                    .map(Value::Int)
+                    .ok_or_else(|| Error::new("attempt to negate with overflow", span).into())
+            }
+            ExprKind::Unary(UnaryOp::Not, operand) => Ok(Value::Bool(!self.bool(operand)?)),
+            ExprKind::Binary(op, left, right) => self.binary(*op, left, right, span),
+            ExprKind::Assign { name, value } => {
+                let value = self.eval(value)?;
+                let binding = self.lookup(name, span)?;
+                if !binding.mutable {
+                    let message = format!("cannot assign twice to immutable variable `{}` (declare it with `let mut`)", name);
+                    return Err(Error::new(message, span).into());
+                }
+                binding.value = value;
+                Ok(Value::Unit)
+            }
+            ExprKind::Block(block) => self.run_block_in_scope(block, true),
+            ExprKind::If { condition, then_branch, else_branch } => {
+                if self.bool(condition)? {
+                    let value = self.run_block_in_scope(then_branch, true)?;
+                    if else_branch.is_none() && value != Value::Unit {
+                        let message = format!("`if` without `else` must be `()`, but this branch is {}", value.type_name());
+                        return Err(Error::new(message, then_branch.span).into());
+                    }
+                    Ok(value)
+                } else {
+                    match else_branch {
+                        Some(e) => self.eval(e),
+                        None => Ok(Value::Unit),
+                    }
+                }
+            }
+            ExprKind::Match { scrutinee, arms } => {
+                let n = self.int(scrutinee)?;
+                match arms.iter().find(|arm| arm.patterns.iter().any(|p| p.matches(n))) {
+                    Some(arm) => self.eval(&arm.body),
+                    None => Err(Error::new(format!("no match arm covers {} (add a `_` arm)", n), span).into()),
+                }
+            }
+            ExprKind::Loop(body) => {
+                for _ in 0..self.max_iterations {
+                    match self.run_block_in_scope(body, true) {
+                        Ok(_) => {}
+                        Err(Unwind::Break(value, _)) => return Ok(value),
+                        Err(e) => return Err(e),
+                    }
+                }
+                let message = format!("loop ran {} times without `break`", self.max_iterations);
+                Err(Error::new(message, span).into())
+            }
+            ExprKind::Break(value) => {
+                let value = match value {
+                    Some(v) => self.eval(v)?,
+                    None => Value::Unit,
+                };
+                Err(Unwind::Break(value, span))
+            }
+        }
+    }
+
+    fn binary(&mut self, op: BinaryOp, left: &Expr, right: &Expr, span: Span) -> Result<Value, Unwind> {
+        // `&&` and `||` short-circuit: the right side may never run
+        match op {
+            BinaryOp::And => return Ok(Value::Bool(self.bool(left)? && self.bool(right)?)),
+            BinaryOp::Or => return Ok(Value::Bool(self.bool(left)? || self.bool(right)?)),
+            BinaryOp::Eq | BinaryOp::Ne => {
+                let (a, b) = (self.eval(left)?, self.eval(right)?);
+                if a.type_name() != b.type_name() {
+                    let message = format!("cannot compare {} with {}", a.type_name(), b.type_name());
+                    return Err(Error::new(message, span).into());
+                }
+                return Ok(Value::Bool((a == b) == (op == BinaryOp::Eq)));
+            }
+            _ => {}
+        }
+        let (a, b) = (self.int(left)?, self.int(right)?);
+        let overflow = |what: &str| Error::new(format!("attempt to {} with overflow", what), span);
+        let value = match op {
+            BinaryOp::Add => Value::Int(a.checked_add(b).ok_or_else(|| overflow("add"))?),
+            BinaryOp::Sub => Value::Int(a.checked_sub(b).ok_or_else(|| overflow("subtract"))?),
+            BinaryOp::Mul => Value::Int(a.checked_mul(b).ok_or_else(|| overflow("multiply"))?),
+            BinaryOp::Div | BinaryOp::Rem if b == 0 => {
+                let what = if op == BinaryOp::Div { "divide" } else { "calculate the remainder" };
+                return Err(Error::new(format!("attempt to {} by zero", what), span).into());
+            }
+            BinaryOp::Div => Value::Int(a.checked_div(b).ok_or_else(|| overflow("divide"))?),
+            BinaryOp::Rem => Value::Int(a.checked_rem(b).ok_or_else(|| overflow("calculate the remainder"))?),
+            BinaryOp::Lt => Value::Bool(a < b),
+            BinaryOp::Le => Value::Bool(a <= b),
+            BinaryOp::Gt => Value::Bool(a > b),
+            BinaryOp::Ge => Value::Bool(a >= b),
+            BinaryOp::And | BinaryOp::Or | BinaryOp::Eq | BinaryOp::Ne => unreachable!("handled above"),
+        };
+        Ok(value)
+    }
+}
+
+/// Evaluates a program in a fresh interpreter.
+pub fn eval(source: &str) -> Result<Value, Error> {
+    Interpreter::new().run(source)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn int(source: &str) -> i64 {
+        match eval(source) {
+            Ok(Value::Int(n)) => n,
+            other => panic!("{:?} evaluated to {:?}", source, other),
+        }
+    }
+
+    fn error(source: &str) -> (String, &str) {
+        let e = eval(source).expect_err(source);
+        (e.message, &source[e.span.start..e.span.end])
+    }
+
+    #[test]
+    fn precedence_and_associativity() {
+        assert_eq!(int("1 + 2 * 3"), 7);
+        assert_eq!(int("(1 + 2) * 3"), 9);
+        assert_eq!(int("10 - 4 - 3"), 3);
+        assert_eq!(int("-2 * 3 + 17 % 5"), -4);
+        assert_eq!(eval("1 + 1 == 2 && !(3 < 2) || false"), Ok(Value::Bool(true)));
+    }
+
+    #[test]
+    fn blocks_value_is_their_last_expression() {
+        assert_eq!(int("let x = { let a = 10; let b = 20; a + b }; x"), 30);
+        assert_eq!(eval("{ 1 + 2; }"), Ok(Value::Unit));
+        assert_eq!(int("let x = 1; { let x = 2; x } + x"), 3);
+        assert_eq!(int("let x = 5; let x = x * 2; x"), 10);
+    }
+
+    #[test]
+    fn if_match_and_loop() {
+        assert_eq!(int("let cond = true; if cond { 42 } else { 0 }"), 42);
+        assert_eq!(int("let n = 7; if n < 5 { 1 } else if n < 10 { 2 } else { 3 }"), 2);
+        let classify = |n: i64| int(&format!("match {} {{ 1 => 10, 2 | 3 => 20, 4..=9 => 30, -5..0 => 40, _ => 50 }}", n));
+        assert_eq!([1, 3, 9, -5, -1, 10].map(classify), [10, 20, 30, 40, 40, 50]);
+        assert_eq!(int("let mut i = 0; let res = loop { i = i + 1; if i > 5 { break i * 10; } }; res"), 60);
+        assert_eq!(eval("loop { break }"), Ok(Value::Unit));
+    }
+
+    #[test]
+    fn errors_point_at_the_right_code() {
+        assert_eq!(error("1 + 2 / 0"), ("attempt to divide by zero".into(), "2 / 0"));
+        assert_eq!(error("1 + true"), ("expected integer, found bool".into(), "true"));
+        assert_eq!(error("let x = 1; x = 2"), ("cannot assign twice to immutable variable `x` (declare it with `let mut`)".into(), "x = 2"));
+        assert_eq!(error("y + 1").1, "y");
+        assert_eq!(error("match 4 { 1 => 2 }").0, "no match arm covers 4 (add a `_` arm)");
+        assert_eq!(error("if true { 1 }").1, "{ 1 }");
+        assert_eq!(error("break 3").0, "`break` outside of a loop");
+        assert_eq!(error("1 < 2 < 3").0, "comparison operators cannot be chained");
+        assert_eq!(error("1 + * 2"), ("expected an expression, found `*`".into(), "*"));
+        assert_eq!(error("1 2"), ("expected `;` or the end of the block, found `2`".into(), "2"));
+        assert_eq!(error("9223372036854775807 + 1").0, "attempt to add with overflow");
+        assert_eq!(error("1 $ 2"), ("unexpected character '$'".into(), "$"));
+    }
+
+    #[test]
+    fn render_underlines_the_span() {
+        let source = "let a = 1;\nlet b = a / 0;";
+        let e = eval(source).unwrap_err();
+        assert_eq!(
+            e.render(source),
+            "error: attempt to divide by zero\n --> 2:9\n  |\n2 | let b = a / 0;\n  |         ^^^^^"
+        );
+    }
+
+    #[test]
+    fn interpreter_keeps_bindings_between_runs() {
+        let mut interp = Interpreter::new();
+        assert_eq!(interp.run("let mut total = 1;"), Ok(Value::Unit));
+        assert_eq!(interp.run("total = total * 6; total"), Ok(Value::Int(6)));
+    }
+}
@@ -0,0 +1,83 @@
+// fatal.rs
+// Ending the process on an unrecoverable error, built on the never type from ch04_14.
+//
+// `die` and `usage_error` return `!`: they print to stderr and exit, so they never
+// come back. Because `!` coerces into any type, they can stand in for a value:
+//
+//   let n: u32 = match text.parse() { Ok(n) => n, Err(e) => die(ExitCode::DataErr, e) };
+//
+// `OrDie` wraps that match for `Result` and `Option`, as a replacement for
+// `expect` that exits with a chosen code and a message instead of panicking.
+//
+// The codes follow the BSD sysexits.h convention (64..=78) where one fits, so a
+// shell script can tell "bad input" from "missing file" from "I/O failure".
+
+use std::fmt::Display;
+use std::process;
+
+/// The exit status of a failed run. Success is not listed: returning from `main`
+/// already exits with 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitCode {
+    /// Generic failure.
+    Failure,
+    /// The command line was wrong (2, as used by most Unix tools).
+    Usage,
+    /// The input data was malformed (EX_DATAERR).
+    DataErr,
+    /// An input file did not exist or there was no input (EX_NOINPUT).
+    NoInput,
+    /// Reading or writing failed (EX_IOERR).
+    IoErr,
+}
+
+impl ExitCode {
+    pub fn code(self) -> i32 {
+        match self {
+            ExitCode::Failure => 1,
+            ExitCode::Usage => 2,
+            ExitCode::DataErr => 65,
+            ExitCode::NoInput => 66,
+            ExitCode::IoErr => 74,
+        }
+    }
+}
+
+/// Prints `error: msg` to stderr and exits with `code`.
+pub fn die(code: ExitCode, msg: impl Display) -> ! {
+    eprintln!("error: {}", msg);
+    process::exit(code.code())
+}
+
+/// Prints `error: msg` and the usage text to stderr and exits with `ExitCode::Usage`.
+pub fn usage_error(usage: &str, msg: impl Display) -> ! {
+    eprintln!("error: {}\n\n{}", msg, usage.trim_end());
+    process::exit(ExitCode::Usage.code())
+}
+
+/// `expect` that exits instead of panicking: no backtrace, a chosen exit code,
+/// and a message meant for the user rather than the programmer.
+pub trait OrDie<T> {
+    /// Returns the value, or dies with `code`. For a `Result` the message is
+    /// `context: error`; for an `Option` it is just `context`.
+    fn or_die(self, code: ExitCode, context: impl Display) -> T;
+}
+
+impl<T, E: Display> OrDie<T> for Result<T, E> {
+    fn or_die(self, code: ExitCode, context: impl Display) -> T {
+        match self {
+            Ok(value) => value,
+            // `die` returns `!`, which coerces to `T`
+            Err(e) => die(code, format_args!("{}: {}", context, e)),
+        }
+    }
+}
+
+impl<T> OrDie<T> for Option<T> {
+    fn or_die(self, code: ExitCode, context: impl Display) -> T {
+        match self {
+            Some(value) => value,
+            None => die(code, context),
+        }
+    }
+}
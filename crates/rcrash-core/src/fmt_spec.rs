@@ -0,0 +1,844 @@
+// fmt_spec.rs
+// 3.13 The println! format-spec mini-language
+// Parses specs such as `{:>+#010.3e}` with a hand-written grammar, explains
+// each component and renders a single value the same way `format!` would.
+//
+//   format   := '{' [argument] [':' spec] '}'
+//   spec     := [[fill] align] [sign] ['#'] ['0'] [width] ['.' precision] [type]
+//   align    := '<' | '^' | '>'
+//   sign     := '+' | '-'
+//   type     := '' | '?' | 'x?' | 'X?' | 'x' | 'X' | 'o' | 'b' | 'e' | 'E'
+
+use crate::int_type::IntType;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    Center,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sign {
+    Plus,
+    Minus,
+}
+
+/// The formatting trait selected by the type character.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Display,
+    Debug,
+    DebugLowerHex,
+    DebugUpperHex,
+    LowerHex,
+    UpperHex,
+    Octal,
+    Binary,
+    LowerExp,
+    UpperExp,
+}
+
+impl Kind {
+    fn trait_name(self) -> &'static str {
+        match self {
+            Kind::Display => "Display",
+            Kind::Debug => "Debug",
+            Kind::DebugLowerHex => "Debug (lower-case hex integers)",
+            Kind::DebugUpperHex => "Debug (upper-case hex integers)",
+            Kind::LowerHex => "LowerHex",
+            Kind::UpperHex => "UpperHex",
+            Kind::Octal => "Octal",
+            Kind::Binary => "Binary",
+            Kind::LowerExp => "LowerExp",
+            Kind::UpperExp => "UpperExp",
+        }
+    }
+
+    fn symbol(self) -> &'static str {
+        match self {
+            Kind::Display => "",
+            Kind::Debug => "?",
+            Kind::DebugLowerHex => "x?",
+            Kind::DebugUpperHex => "X?",
+            Kind::LowerHex => "x",
+            Kind::UpperHex => "X",
+            Kind::Octal => "o",
+            Kind::Binary => "b",
+            Kind::LowerExp => "e",
+            Kind::UpperExp => "E",
+        }
+    }
+}
+
+/// Which argument the placeholder refers to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Argument {
+    Next,
+    Index(usize),
+    Name(String),
+}
+
+/// A parsed placeholder. `fill` is `None` when it was not written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Spec {
+    pub argument: Argument,
+    pub fill: Option<char>,
+    pub align: Option<Align>,
+    pub sign: Option<Sign>,
+    pub alternate: bool,
+    pub zero: bool,
+    pub width: Option<usize>,
+    pub precision: Option<usize>,
+    pub kind: Kind,
+}
+
+/// Errors from parsing a spec or rendering a value with it.
+/// Positions are 0-based char offsets into the spec text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpecError {
+    Unclosed,
+    UnexpectedChar { ch: char, pos: usize },
+    UnknownType { text: String, pos: usize },
+    NumberTooLarge { pos: usize },
+    ParameterNotSupported { pos: usize },
+    InvalidValue(String),
+    TraitNotImplemented { kind: Kind, value_type: &'static str },
+}
+
+impl fmt::Display for SpecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpecError::Unclosed => write!(f, "placeholder is missing its closing '}}'"),
+            SpecError::UnexpectedChar { ch, pos } => {
+                write!(f, "unexpected '{}' at position {}", ch, pos)
+            }
+            SpecError::UnknownType { text, pos } => {
+                write!(f, "unknown format type '{}' at position {}", text, pos)
+            }
+            SpecError::NumberTooLarge { pos } => {
+                write!(f, "number at position {} does not fit in usize", pos)
+            }
+            SpecError::ParameterNotSupported { pos } => write!(
+                f,
+                "width/precision taken from another argument ('$' or '*') at position {} \
+                 is not supported with a single value",
+                pos
+            ),
+            SpecError::InvalidValue(s) => write!(f, "cannot understand value '{}'", s),
+            SpecError::TraitNotImplemented { kind, value_type } => write!(
+                f,
+                "`{}` does not implement {} (type '{}')",
+                value_type,
+                kind.trait_name(),
+                kind.symbol()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SpecError {}
+
+/// A cursor over the spec characters.
+struct Cursor {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Cursor {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn integer(&mut self) -> Result<Option<usize>, SpecError> {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        if start == self.pos {
+            return Ok(None);
+        }
+        let text: String = self.chars[start..self.pos].iter().collect();
+        text.parse().map(Some).map_err(|_| SpecError::NumberTooLarge { pos: start })
+    }
+
+    fn identifier(&mut self) -> Option<String> {
+        let start = self.pos;
+        match self.peek() {
+            Some(c) if c.is_alphabetic() || c == '_' => self.pos += 1,
+            _ => return None,
+        }
+        while self.peek().is_some_and(|c| c.is_alphanumeric() || c == '_') {
+            self.pos += 1;
+        }
+        Some(self.chars[start..self.pos].iter().collect())
+    }
+
+    /// Width or precision: a plain count; `N$`, `name$` and `*` are rejected.
+    fn count(&mut self) -> Result<Option<usize>, SpecError> {
+        let start = self.pos;
+        if self.peek() == Some('*') {
+            return Err(SpecError::ParameterNotSupported { pos: start });
+        }
+        if let Some(n) = self.integer()? {
+            if self.peek() == Some('$') {
+                return Err(SpecError::ParameterNotSupported { pos: start });
+            }
+            return Ok(Some(n));
+        }
+        if self.identifier().is_some() {
+            if self.peek() == Some('$') {
+                return Err(SpecError::ParameterNotSupported { pos: start });
+            }
+            // Not a count after all: it is the type, parsed by the caller.
+            self.pos = start;
+        }
+        Ok(None)
+    }
+}
+
+fn align_of(c: char) -> Option<Align> {
+    match c {
+        '<' => Some(Align::Left),
+        '^' => Some(Align::Center),
+        '>' => Some(Align::Right),
+        _ => None,
+    }
+}
+
+/// Parses either a full placeholder (`{0:>5}`) or a bare spec (`>5` or `:>5`).
+pub fn parse(text: &str) -> Result<Spec, SpecError> {
+    let mut cur = Cursor { chars: text.chars().collect(), pos: 0 };
+    let braced = cur.eat('{');
+    let mut argument = Argument::Next;
+
+    if braced {
+        if let Some(n) = cur.integer()? {
+            argument = Argument::Index(n);
+        } else if let Some(name) = cur.identifier() {
+            argument = Argument::Name(name);
+        }
+        if !cur.eat(':') {
+            return finish(cur, braced, Spec::display(argument));
+        }
+    } else {
+        cur.eat(':');
+    }
+
+    let mut spec = Spec::display(argument);
+    if let Some(align) = cur.peek_at(1).and_then(align_of) {
+        spec.fill = cur.peek();
+        spec.align = Some(align);
+        cur.pos += 2;
+    } else if let Some(align) = cur.peek().and_then(align_of) {
+        spec.align = Some(align);
+        cur.pos += 1;
+    }
+    if cur.eat('+') {
+        spec.sign = Some(Sign::Plus);
+    } else if cur.eat('-') {
+        spec.sign = Some(Sign::Minus);
+    }
+    spec.alternate = cur.eat('#');
+    if cur.peek() == Some('0') && cur.peek_at(1) != Some('$') {
+        spec.zero = true;
+        cur.pos += 1;
+    }
+    spec.width = cur.count()?;
+    if cur.eat('.') {
+        match cur.count()? {
+            Some(p) => spec.precision = Some(p),
+            None => {
+                return Err(match cur.peek() {
+                    Some(ch) => SpecError::UnexpectedChar { ch, pos: cur.pos },
+                    None => SpecError::Unclosed,
+                });
+            }
+        }
+    }
+
+    let type_start = cur.pos;
+    let kind_text = match cur.identifier() {
+        Some(t) if cur.eat('?') => t + "?",
+        Some(t) => t,
+        None if cur.eat('?') => "?".to_string(),
+        None => String::new(),
+    };
+    spec.kind = match kind_text.as_str() {
+        "" => Kind::Display,
+        "?" => Kind::Debug,
+        "x?" => Kind::DebugLowerHex,
+        "X?" => Kind::DebugUpperHex,
+        "x" => Kind::LowerHex,
+        "X" => Kind::UpperHex,
+        "o" => Kind::Octal,
+        "b" => Kind::Binary,
+        "e" => Kind::LowerExp,
+        "E" => Kind::UpperExp,
+        _ => return Err(SpecError::UnknownType { text: kind_text, pos: type_start }),
+    };
+    finish(cur, braced, spec)
+}
+
+fn finish(mut cur: Cursor, braced: bool, spec: Spec) -> Result<Spec, SpecError> {
+    if braced && !cur.eat('}') {
+        return Err(match cur.peek() {
+            Some(ch) => SpecError::UnexpectedChar { ch, pos: cur.pos },
+            None => SpecError::Unclosed,
+        });
+    }
+    match cur.peek() {
+        Some(ch) => Err(SpecError::UnexpectedChar { ch, pos: cur.pos }),
+        None => Ok(spec),
+    }
+}
+
+impl Spec {
+    fn display(argument: Argument) -> Spec {
+        Spec {
+            argument,
+            fill: None,
+            align: None,
+            sign: None,
+            alternate: false,
+            zero: false,
+            width: None,
+            precision: None,
+            kind: Kind::Display,
+        }
+    }
+
+    /// One `(component, explanation)` row per part of the spec.
+    pub fn explain(&self) -> Vec<(&'static str, String)> {
+        let mut rows = Vec::new();
+        rows.push((
+            "argument",
+            match &self.argument {
+                Argument::Next => "next positional argument (implicit)".to_string(),
+                Argument::Index(n) => format!("positional argument {}", n),
+                Argument::Name(name) => format!("named argument `{}`", name),
+            },
+        ));
+        let zero_note = if self.zero { " (ignored for numbers because of `0`)" } else { "" };
+        rows.push((
+            "fill",
+            match self.fill {
+                Some(c) => format!("{:?}{}", c, zero_note),
+                None => format!("' ' (default){}", zero_note),
+            },
+        ));
+        rows.push((
+            "align",
+            match self.align {
+                Some(Align::Left) => format!("< left{}", zero_note),
+                Some(Align::Center) => format!("^ center{}", zero_note),
+                Some(Align::Right) => format!("> right{}", zero_note),
+                None => "default (left for text, right for numbers)".to_string(),
+            },
+        ));
+        rows.push((
+            "sign",
+            match self.sign {
+                Some(Sign::Plus) => "+ always print the sign of numbers".to_string(),
+                Some(Sign::Minus) => "- accepted but currently has no effect".to_string(),
+                None => "default (only '-' for negative numbers)".to_string(),
+            },
+        ));
+        rows.push((
+            "alternate",
+            if self.alternate {
+                "# alternate form (0x/0o/0b prefix, pretty Debug)".to_string()
+            } else {
+                "off".to_string()
+            },
+        ));
+        rows.push((
+            "zero",
+            if self.zero {
+                "0 pad numbers with zeros after the sign and prefix".to_string()
+            } else {
+                "off".to_string()
+            },
+        ));
+        rows.push((
+            "width",
+            match self.width {
+                Some(w) => format!("{} (minimum number of chars)", w),
+                None => "none".to_string(),
+            },
+        ));
+        rows.push((
+            "precision",
+            match self.precision {
+                Some(p) => format!(".{} (digits after the point for floats, max chars for text)", p),
+                None => "none".to_string(),
+            },
+        ));
+        rows.push((
+            "type",
+            format!(
+                "'{}' -> {}",
+                self.kind.symbol(),
+                self.kind.trait_name()
+            ),
+        ));
+        rows
+    }
+}
+
+/// Write a spec back in canonical `{...}` form.
+impl fmt::Display for Spec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        match &self.argument {
+            Argument::Next => {}
+            Argument::Index(n) => write!(f, "{}", n)?,
+            Argument::Name(name) => write!(f, "{}", name)?,
+        }
+        let mut spec = String::new();
+        if let Some(align) = self.align {
+            if let Some(fill) = self.fill {
+                spec.push(fill);
+            }
+            spec.push(match align {
+                Align::Left => '<',
+                Align::Center => '^',
+                Align::Right => '>',
+            });
+        }
+        match self.sign {
+            Some(Sign::Plus) => spec.push('+'),
+            Some(Sign::Minus) => spec.push('-'),
+            None => {}
+        }
+        if self.alternate {
+            spec.push('#');
+        }
+        if self.zero {
+            spec.push('0');
+        }
+        if let Some(w) = self.width {
+            spec.push_str(&w.to_string());
+        }
+        if let Some(p) = self.precision {
+            spec.push_str(&format!(".{}", p));
+        }
+        spec.push_str(self.kind.symbol());
+        if !spec.is_empty() {
+            write!(f, ":{}", spec)?;
+        }
+        write!(f, "}}")
+    }
+}
+
+/// Whether `-magnitude` (or `magnitude`) fits in `ty`.
+fn fits(ty: IntType, negative: bool, magnitude: u128) -> bool {
+    let bits = ty.bits();
+    match (ty.signed(), negative) {
+        (false, true) => magnitude == 0,
+        (false, false) => bits == 128 || magnitude < 1u128 << bits,
+        (true, false) => magnitude < 1u128 << (bits - 1),
+        (true, true) => magnitude <= 1u128 << (bits - 1),
+    }
+}
+
+/// A value to format, parsed from Rust-like literal syntax.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Int { negative: bool, magnitude: u128, ty: IntType },
+    F32(f32),
+    F64(f64),
+    Char(char),
+    Bool(bool),
+    Str(String),
+}
+
+impl Value {
+    /// Parses `42`, `-1i8`, `3.14`, `2.5f32`, `NaN`, `-inf`, `'x'`, `true`,
+    /// `"quoted"` or any other text as a string. Unsuffixed integers become
+    /// `i32` when they fit (like Rust's default), floats become `f64`.
+    pub fn parse(text: &str) -> Result<Value, SpecError> {
+        let invalid = || SpecError::InvalidValue(text.to_string());
+        match text {
+            "true" => return Ok(Value::Bool(true)),
+            "false" => return Ok(Value::Bool(false)),
+            "NaN" | "nan" => return Ok(Value::F64(f64::NAN)),
+            "inf" | "+inf" => return Ok(Value::F64(f64::INFINITY)),
+            "-inf" => return Ok(Value::F64(f64::NEG_INFINITY)),
+            _ => {}
+        }
+        if text.len() >= 2 && text.starts_with('"') && text.ends_with('"') {
+            return Ok(Value::Str(text[1..text.len() - 1].to_string()));
+        }
+        if text.len() >= 3 && text.starts_with('\'') && text.ends_with('\'') {
+            let mut inner = text[1..text.len() - 1].chars();
+            return match (inner.next(), inner.next()) {
+                (Some(c), None) => Ok(Value::Char(c)),
+                _ => Err(invalid()),
+            };
+        }
+
+        let looks_numeric = text
+            .strip_prefix('-')
+            .unwrap_or(text)
+            .starts_with(|c: char| c.is_ascii_digit());
+        if !looks_numeric {
+            return Ok(Value::Str(text.to_string()));
+        }
+
+        let split = text
+            .find(['i', 'u', 'f'])
+            .unwrap_or(text.len());
+        let (number, suffix) = text.split_at(split);
+        let number = number.replace('_', "");
+        match suffix {
+            "f32" => return number.parse().map(Value::F32).map_err(|_| invalid()),
+            "f64" => return number.parse().map(Value::F64).map_err(|_| invalid()),
+            _ => {}
+        }
+        if suffix.is_empty() && number.contains(['.', 'e', 'E']) {
+            return number.parse().map(Value::F64).map_err(|_| invalid());
+        }
+
+        let negative = number.starts_with('-');
+        let magnitude: u128 = number.trim_start_matches('-').parse().map_err(|_| invalid())?;
+        let ty = if suffix.is_empty() {
+            [IntType::I32, IntType::I64, IntType::I128, IntType::U128]
+                .into_iter()
+                .find(|&t| fits(t, negative, magnitude))
+                .ok_or_else(invalid)?
+        } else {
+            IntType::from_suffix(suffix).ok_or_else(invalid)?
+        };
+        if !fits(ty, negative, magnitude) {
+            return Err(invalid());
+        }
+        Ok(Value::Int { negative: negative && magnitude != 0, magnitude, ty })
+    }
+
+    /// The Rust type name of the value.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::Int { ty, .. } => ty.name(),
+            Value::F32(_) => "f32",
+            Value::F64(_) => "f64",
+            Value::Char(_) => "char",
+            Value::Bool(_) => "bool",
+            Value::Str(_) => "&str",
+        }
+    }
+}
+
+macro_rules! value_from_int {
+    (signed: $($s:ty => $sv:ident),*; unsigned: $($u:ty => $uv:ident),*) => {
+        $(impl From<$s> for Value {
+            fn from(v: $s) -> Value {
+                Value::Int { negative: v < 0, magnitude: v.unsigned_abs() as u128, ty: IntType::$sv }
+            }
+        })*
+        $(impl From<$u> for Value {
+            fn from(v: $u) -> Value {
+                Value::Int { negative: false, magnitude: v as u128, ty: IntType::$uv }
+            }
+        })*
+    };
+}
+
+value_from_int!(
+    signed: i8 => I8, i16 => I16, i32 => I32, i64 => I64, i128 => I128, isize => Isize;
+    unsigned: u8 => U8, u16 => U16, u32 => U32, u64 => U64, u128 => U128, usize => Usize
+);
+
+impl From<f32> for Value {
+    fn from(v: f32) -> Value {
+        Value::F32(v)
+    }
+}
+
+impl From<f64> for Value {
+    fn from(v: f64) -> Value {
+        Value::F64(v)
+    }
+}
+
+impl From<char> for Value {
+    fn from(v: char) -> Value {
+        Value::Char(v)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(v: bool) -> Value {
+        Value::Bool(v)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(v: &str) -> Value {
+        Value::Str(v.to_string())
+    }
+}
+
+/// Pads `text` to the spec's width with its fill and alignment.
+fn pad(spec: &Spec, text: &str, default: Align) -> String {
+    let len = text.chars().count();
+    let width = spec.width.unwrap_or(0);
+    if len >= width {
+        return text.to_string();
+    }
+    let fill = spec.fill.unwrap_or(' ').to_string();
+    let missing = width - len;
+    let (before, after) = match spec.align.unwrap_or(default) {
+        Align::Left => (0, missing),
+        Align::Right => (missing, 0),
+        Align::Center => (missing / 2, missing - missing / 2),
+    };
+    format!("{}{}{}", fill.repeat(before), text, fill.repeat(after))
+}
+
+/// Numbers: sign and prefix go first; the `0` flag pads between them and the digits.
+fn pad_number(spec: &Spec, negative: bool, prefix: &str, digits: &str, show_sign: bool) -> String {
+    let sign = if negative {
+        "-"
+    } else if show_sign && spec.sign == Some(Sign::Plus) {
+        "+"
+    } else {
+        ""
+    };
+    let prefix = if spec.alternate { prefix } else { "" };
+    if spec.zero {
+        let len = sign.len() + prefix.len() + digits.chars().count();
+        let zeros = spec.width.unwrap_or(0).saturating_sub(len);
+        format!("{}{}{}{}", sign, prefix, "0".repeat(zeros), digits)
+    } else {
+        pad(spec, &format!("{}{}{}", sign, prefix, digits), Align::Right)
+    }
+}
+
+fn truncate(spec: &Spec, text: &str) -> String {
+    match spec.precision {
+        Some(p) => text.chars().take(p).collect(),
+        None => text.to_string(),
+    }
+}
+
+macro_rules! render_float {
+    ($spec:expr, $v:expr) => {{
+        let spec: &Spec = $spec;
+        let v = $v;
+        let abs = v.abs();
+        let digits = if v.is_nan() {
+            "NaN".to_string()
+        } else if v.is_infinite() {
+            "inf".to_string()
+        } else {
+            match (spec.kind, spec.precision) {
+                (Kind::LowerExp, Some(p)) => format!("{:.*e}", p, abs),
+                (Kind::LowerExp, None) => format!("{:e}", abs),
+                (Kind::UpperExp, Some(p)) => format!("{:.*E}", p, abs),
+                (Kind::UpperExp, None) => format!("{:E}", abs),
+                (_, Some(p)) => format!("{:.*}", p, abs),
+                (Kind::Display, None) => format!("{}", abs),
+                (_, None) => format!("{:?}", abs),
+            }
+        };
+        let negative = v.is_sign_negative() && !v.is_nan();
+        pad_number(spec, negative, "", &digits, !v.is_nan())
+    }};
+}
+
+/// Formats `value` according to `spec`, matching `format!` for the same spec.
+pub fn render(spec: &Spec, value: &Value) -> Result<String, SpecError> {
+    let unsupported = || SpecError::TraitNotImplemented { kind: spec.kind, value_type: value.type_name() };
+    let integer_only = matches!(spec.kind, Kind::LowerHex | Kind::UpperHex | Kind::Octal | Kind::Binary);
+    let exp = matches!(spec.kind, Kind::LowerExp | Kind::UpperExp);
+
+    match value {
+        Value::Int { negative, magnitude, ty } => {
+            // Radix formats print the two's complement bits of negative numbers
+            let bits = if *negative {
+                let mask = if ty.bits() == 128 { u128::MAX } else { (1u128 << ty.bits()) - 1 };
+                magnitude.wrapping_neg() & mask
+            } else {
+                *magnitude
+            };
+            let (negative, prefix, digits) = match spec.kind {
+                Kind::LowerHex | Kind::DebugLowerHex => (false, "0x", format!("{:x}", bits)),
+                Kind::UpperHex | Kind::DebugUpperHex => (false, "0x", format!("{:X}", bits)),
+                Kind::Octal => (false, "0o", format!("{:o}", bits)),
+                Kind::Binary => (false, "0b", format!("{:b}", bits)),
+                Kind::LowerExp => (*negative, "", match spec.precision {
+                    Some(p) => format!("{:.*e}", p, magnitude),
+                    None => format!("{:e}", magnitude),
+                }),
+                Kind::UpperExp => (*negative, "", match spec.precision {
+                    Some(p) => format!("{:.*E}", p, magnitude),
+                    None => format!("{:E}", magnitude),
+                }),
+                Kind::Display | Kind::Debug => (*negative, "", magnitude.to_string()),
+            };
+            Ok(pad_number(spec, negative, prefix, &digits, true))
+        }
+        Value::F32(_) | Value::F64(_) if integer_only => Err(unsupported()),
+        Value::F32(v) => Ok(render_float!(spec, *v)),
+        Value::F64(v) => Ok(render_float!(spec, *v)),
+        _ if integer_only || exp => Err(unsupported()),
+        // Debug for str and char writes the quoted text directly, ignoring width and precision
+        Value::Str(s) if spec.kind != Kind::Display => Ok(format!("{:?}", s)),
+        Value::Char(c) if spec.kind != Kind::Display => Ok(format!("{:?}", c)),
+        Value::Str(s) => Ok(pad(spec, &truncate(spec, s), Align::Left)),
+        Value::Char(c) => Ok(pad(spec, &truncate(spec, &c.to_string()), Align::Left)),
+        Value::Bool(b) => Ok(pad(spec, &truncate(spec, &b.to_string()), Align::Left)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Renders with our implementation and compares against `format!`.
+    macro_rules! check {
+        ($fmt:literal, $v:expr) => {{
+            let spec = parse($fmt).unwrap();
+            let ours = render(&spec, &Value::from($v)).unwrap();
+            assert_eq!(ours, format!($fmt, $v), "spec {} value {:?}", $fmt, $v);
+        }};
+    }
+
+    #[test]
+    fn matches_format_for_integers() {
+        check!("{:>5}", 42);
+        check!("{:^5}", 42);
+        check!("{:<5}", -42);
+        check!("{:*^9}", 7);
+        check!("{:+}", 5u8);
+        check!("{:05}", -42);
+        check!("{:+06}", 42);
+        check!("{:x}", 255);
+        check!("{:X}", 255);
+        check!("{:#x}", 255);
+        check!("{:#010x}", 255);
+        check!("{:#b}", 5);
+        check!("{:o}", 64);
+        check!("{:x}", -1i8);
+        check!("{:b}", -2i16);
+        check!("{:#X}", i128::MIN);
+        check!("{:x}", -1i64);
+        check!("{:+x}", 5);
+        check!("{:e}", 1234);
+        check!("{:.2e}", 1235);
+        check!("{:.2e}", 1225);
+        check!("{:E}", -1200);
+        check!("{:.3}", 7);
+        check!("{:?}", -3);
+        check!("{:x?}", 255);
+        check!("{:#X?}", 255);
+        check!("{}", u128::MAX);
+        check!("{}", i64::MIN);
+    }
+
+    #[test]
+    fn matches_format_for_floats() {
+        check!("{:>+#010.3e}", 12.34567);
+        check!("{:.2}", 12.34567);
+        check!("{:8.3}", -2.5);
+        check!("{:08.3}", -2.5);
+        check!("{:<8}", 1.5);
+        check!("{:e}", 12.34567);
+        check!("{:.0}", 0.5);
+        check!("{:.0}", 1.5);
+        check!("{}", 1e21);
+        check!("{}", 1e-7);
+        check!("{:?}", 1e16);
+        check!("{:?}", 1.0);
+        check!("{:.3?}", -1.5);
+        check!("{}", -0.0);
+        check!("{:05}", -0.0);
+        check!("{}", 0.1f32);
+        check!("{:?}", 0.1f32);
+        check!("{:+}", f64::NAN);
+        check!("{:05}", f64::NAN);
+        check!("{:+05}", f64::INFINITY);
+        check!("{:^9}", f64::NEG_INFINITY);
+        check!("{:E}", 0.00012);
+    }
+
+    #[test]
+    fn matches_format_for_text() {
+        check!("{:>8}", "abc");
+        check!("{:^7}", "abc");
+        check!("{:-<6}", "ab");
+        check!("{:05}", "ab");
+        check!("{:.2}", "hello");
+        check!("{:>6.2}", "hello");
+        check!("{:?}", "a\"b\n");
+        check!("{:10?}", "ab");
+        check!("{:.2?}", "hello");
+        check!("{:?}", 'a');
+        check!("{:5?}", 'a');
+        check!("{:>3}", 'x');
+        check!("{:05}", 'x');
+        check!("{:6}", true);
+        check!("{:.2}", false);
+        check!("{:?}", true);
+    }
+
+    #[test]
+    fn parses_every_component() {
+        let spec = parse("{0:_^+#010.3e}").unwrap();
+        assert_eq!(spec.argument, Argument::Index(0));
+        assert_eq!(spec.fill, Some('_'));
+        assert_eq!(spec.align, Some(Align::Center));
+        assert_eq!(spec.sign, Some(Sign::Plus));
+        assert!(spec.alternate && spec.zero);
+        assert_eq!((spec.width, spec.precision), (Some(10), Some(3)));
+        assert_eq!(spec.kind, Kind::LowerExp);
+        assert_eq!(spec.to_string(), "{0:_^+#010.3e}");
+
+        assert_eq!(parse("{name}").unwrap().argument, Argument::Name("name".into()));
+        assert_eq!(parse(">5").unwrap().to_string(), "{:>5}");
+        assert_eq!(parse(":x?").unwrap().kind, Kind::DebugLowerHex);
+        assert_eq!(parse("{}").unwrap().to_string(), "{}");
+        // A fill char that is itself an align char
+        assert_eq!(parse("{:<>4}").unwrap().fill, Some('<'));
+    }
+
+    #[test]
+    fn reports_errors() {
+        assert_eq!(parse("{:>5"), Err(SpecError::Unclosed));
+        assert_eq!(parse("{:w$}"), Err(SpecError::ParameterNotSupported { pos: 2 }));
+        assert_eq!(parse("{:.*}"), Err(SpecError::ParameterNotSupported { pos: 3 }));
+        assert_eq!(parse("{:q}"), Err(SpecError::UnknownType { text: "q".into(), pos: 2 }));
+        assert_eq!(parse("{:5x}x"), Err(SpecError::UnexpectedChar { ch: 'x', pos: 5 }));
+        assert!(render(&parse("{:x}").unwrap(), &Value::from(1.5)).is_err());
+        assert!(render(&parse("{:e}").unwrap(), &Value::from("s")).is_err());
+    }
+
+    #[test]
+    fn parses_values_like_rust_literals() {
+        assert_eq!(Value::parse("42"), Ok(Value::from(42)));
+        assert_eq!(Value::parse("-1i8"), Ok(Value::from(-1i8)));
+        assert_eq!(Value::parse("3000000000"), Ok(Value::from(3_000_000_000i64)));
+        assert_eq!(Value::parse("2.5f32"), Ok(Value::from(2.5f32)));
+        assert_eq!(Value::parse("1e3"), Ok(Value::from(1000.0)));
+        assert_eq!(Value::parse("'x'"), Ok(Value::from('x')));
+        assert_eq!(Value::parse("\"42\""), Ok(Value::from("42")));
+        assert_eq!(Value::parse("hello"), Ok(Value::from("hello")));
+        assert!(Value::parse("300u8").is_err());
+        assert!(Value::parse("-1u8").is_err());
+    }
+}
@@ -0,0 +1,560 @@
+// geometry.rs
+// 6.4 Generic structs: a worked example on top of `Point<T>`
+// Segments, polygons and bounding boxes, with the classic algorithms: shoelace
+// area, point-in-polygon, segment intersection and Andrew's monotone-chain
+// convex hull. `Svg` draws the results to a file a browser can open.
+//
+// Two levels of bounds are used:
+// - `Coord` (any number type with `+ - *` and an order) is enough for
+//   everything decided by the sign of a cross product: orientation, whether
+//   two segments intersect, the convex hull, bounding boxes. With integer
+//   coordinates these answers are exact.
+// - `Float` is needed where a result has to be divided or square-rooted: the
+//   area, lengths, the intersection point and the point-in-polygon test.
+
+use crate::float::Float;
+use crate::point::Point;
+use crate::sorting::merge_sort_by;
+use std::cmp::Ordering;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::ops::{Add, Mul, Sub};
+use std::path::Path;
+
+/// A coordinate type: copyable, ordered, with `+ - *`. `T::default()` is
+/// used as zero, which holds for every primitive number type.
+pub trait Coord: Copy + PartialOrd + Default + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> {}
+
+impl<T: Copy + PartialOrd + Default + Add<Output = T> + Sub<Output = T> + Mul<Output = T>> Coord for T {}
+
+/// Which way the path `a -> b -> c` turns.
+///
+/// `Greater` is a left (counter-clockwise) turn, `Less` a right turn and
+/// `Equal` means the three points are collinear.
+pub fn orientation<T: Coord>(a: Point<T>, b: Point<T>, c: Point<T>) -> Ordering {
+    (b - a).cross(c - a).partial_cmp(&T::default()).unwrap_or(Ordering::Equal)
+}
+
+/// `q` lies within the bounding box of `p` and `r`. Together with a
+/// collinearity check this means `q` is on the segment `p..r`.
+fn within<T: Coord>(p: Point<T>, q: Point<T>, r: Point<T>) -> bool {
+    let between = |a: T, b: T, x: T| if a <= b { a <= x && x <= b } else { b <= x && x <= a };
+    between(p.x, r.x, q.x) && between(p.y, r.y, q.y)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GeometryError {
+    /// A polygon needs at least three vertices.
+    TooFewVertices { found: usize },
+}
+
+impl fmt::Display for GeometryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GeometryError::TooFewVertices { found } => {
+                write!(f, "a polygon needs at least 3 vertices, got {}", found)
+            }
+        }
+    }
+}
+
+impl std::error::Error for GeometryError {}
+
+/// The straight line between two points, both ends included.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Segment<T> {
+    pub start: Point<T>,
+    pub end: Point<T>,
+}
+
+impl<T> Segment<T> {
+    pub fn new(start: Point<T>, end: Point<T>) -> Self {
+        Segment { start, end }
+    }
+}
+
+impl<T: Coord> Segment<T> {
+    /// Whether `point` lies on the segment, ends included.
+    pub fn contains(&self, point: Point<T>) -> bool {
+        orientation(self.start, self.end, point) == Ordering::Equal && within(self.start, point, self.end)
+    }
+
+    /// Whether the two segments share at least one point, including touching
+    /// ends and collinear overlaps. Exact for integer coordinates.
+    ///
+    /// ```
+    /// use rcrash_core::geometry::Segment;
+    /// use rcrash_core::point::Point;
+    ///
+    /// let s = |x1, y1, x2, y2| Segment::new(Point::new(x1, y1), Point::new(x2, y2));
+    /// assert!(s(0, 0, 4, 4).intersects(&s(0, 4, 4, 0)));  // cross at (2, 2)
+    /// assert!(s(0, 0, 2, 0).intersects(&s(2, 0, 3, 5)));  // touch at an end
+    /// assert!(s(0, 0, 4, 0).intersects(&s(3, 0, 6, 0)));  // collinear overlap
+    /// assert!(!s(0, 0, 1, 1).intersects(&s(2, 2, 3, 3))); // collinear, apart
+    /// ```
+    pub fn intersects(&self, other: &Segment<T>) -> bool {
+        let (p1, p2, q1, q2) = (self.start, self.end, other.start, other.end);
+        let o1 = orientation(p1, p2, q1);
+        let o2 = orientation(p1, p2, q2);
+        let o3 = orientation(q1, q2, p1);
+        let o4 = orientation(q1, q2, p2);
+        // Each segment's ends lie on different sides of (or on) the other's line
+        if o1 != o2 && o3 != o4 {
+            return true;
+        }
+        // Otherwise they can only meet if an end of one lies on the other
+        self.contains(q1) || self.contains(q2) || other.contains(p1) || other.contains(p2)
+    }
+}
+
+impl<T: Float> Segment<T> {
+    pub fn length(&self) -> T {
+        self.start.distance(self.end)
+    }
+
+    /// The single point where the segments cross, or `None` if they do not
+    /// meet. Parallel segments return `None` even when they overlap, because
+    /// they then share a whole stretch rather than one point.
+    ///
+    /// Writing the segments as `p + t * r` and `q + u * s`, they meet where
+    /// `t = (q - p) × s / (r × s)` and `u = (q - p) × r / (r × s)`, both in `0..=1`.
+    pub fn intersection(&self, other: &Segment<T>) -> Option<Point<T>> {
+        let r = self.end - self.start;
+        let s = other.end - other.start;
+        let denominator = r.cross(s);
+        if denominator == T::ZERO {
+            return None;
+        }
+        let offset = other.start - self.start;
+        let t = offset.cross(s) / denominator;
+        let u = offset.cross(r) / denominator;
+        let unit = |v: T| T::ZERO <= v && v <= T::ONE;
+        (unit(t) && unit(u)).then(|| self.start + r * t)
+    }
+}
+
+/// The smallest axis-aligned rectangle containing a set of points.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundingBox<T> {
+    pub min: Point<T>,
+    pub max: Point<T>,
+}
+
+impl<T: Coord> BoundingBox<T> {
+    /// The box around `points`, or `None` if there are none.
+    pub fn of(points: impl IntoIterator<Item = Point<T>>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        let mut bounds = BoundingBox { min: first, max: first };
+        for p in points {
+            bounds.include(p);
+        }
+        Some(bounds)
+    }
+
+    /// Grows the box to contain `p`.
+    pub fn include(&mut self, p: Point<T>) {
+        if p.x < self.min.x {
+            self.min.x = p.x;
+        }
+        if p.y < self.min.y {
+            self.min.y = p.y;
+        }
+        if p.x > self.max.x {
+            self.max.x = p.x;
+        }
+        if p.y > self.max.y {
+            self.max.y = p.y;
+        }
+    }
+
+    /// The smallest box containing both boxes.
+    pub fn union(mut self, other: BoundingBox<T>) -> Self {
+        self.include(other.min);
+        self.include(other.max);
+        self
+    }
+
+    pub fn width(&self) -> T {
+        self.max.x - self.min.x
+    }
+
+    pub fn height(&self) -> T {
+        self.max.y - self.min.y
+    }
+
+    /// Whether `p` is inside the box or on its edge.
+    pub fn contains(&self, p: Point<T>) -> bool {
+        within(self.min, p, self.max)
+    }
+}
+
+/// A simple polygon: at least three vertices, joined in order, with the last
+/// joined back to the first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polygon<T> {
+    vertices: Vec<Point<T>>,
+}
+
+impl<T> Polygon<T> {
+    pub fn new(vertices: Vec<Point<T>>) -> Result<Self, GeometryError> {
+        if vertices.len() < 3 {
+            return Err(GeometryError::TooFewVertices { found: vertices.len() });
+        }
+        Ok(Polygon { vertices })
+    }
+
+    pub fn vertices(&self) -> &[Point<T>] {
+        &self.vertices
+    }
+}
+
+impl<T: Coord> Polygon<T> {
+    /// The sides, starting with `vertices[0] -> vertices[1]` and ending with
+    /// the closing side back to `vertices[0]`.
+    pub fn edges(&self) -> impl Iterator<Item = Segment<T>> + '_ {
+        let next = self.vertices.iter().cycle().skip(1);
+        self.vertices.iter().zip(next).map(|(&a, &b)| Segment::new(a, b))
+    }
+
+    /// Twice the signed area, by the shoelace formula: the sum of `a × b`
+    /// over all sides. Positive when the vertices run counter-clockwise.
+    /// Exact for integer coordinates, since it needs no division.
+    pub fn signed_double_area(&self) -> T {
+        self.edges().fold(T::default(), |sum, e| sum + e.start.cross(e.end))
+    }
+
+    pub fn is_counter_clockwise(&self) -> bool {
+        self.signed_double_area() > T::default()
+    }
+
+    pub fn bounding_box(&self) -> BoundingBox<T> {
+        BoundingBox::of(self.vertices.iter().copied()).expect("a polygon has vertices")
+    }
+}
+
+impl<T: Float> Polygon<T> {
+    /// The enclosed area, whichever way the vertices run.
+    ///
+    /// ```
+    /// use rcrash_core::geometry::Polygon;
+    /// use rcrash_core::point::Point;
+    ///
+    /// let square = Polygon::new(vec![
+    ///     Point::new(0.0, 0.0), Point::new(2.0, 0.0), Point::new(2.0, 2.0), Point::new(0.0, 2.0),
+    /// ]).unwrap();
+    /// assert_eq!(square.area(), 4.0);
+    /// assert_eq!(square.perimeter(), 8.0);
+    /// ```
+    pub fn area(&self) -> T {
+        self.signed_double_area().abs() / (T::ONE + T::ONE)
+    }
+
+    pub fn perimeter(&self) -> T {
+        self.edges().fold(T::ZERO, |sum, e| sum + e.length())
+    }
+
+    /// Whether `p` is inside the polygon. Points on an edge count as inside.
+    ///
+    /// Casts a ray from `p` to the right and counts the sides it crosses: an
+    /// odd count means inside. A side is counted when it spans `p.y` with one
+    /// end strictly above, so a ray through a vertex is counted once.
+    pub fn contains(&self, p: Point<T>) -> bool {
+        let mut inside = false;
+        for edge in self.edges() {
+            if edge.contains(p) {
+                return true;
+            }
+            let (a, b) = (edge.start, edge.end);
+            if (a.y > p.y) != (b.y > p.y) {
+                let crossing_x = a.x + (p.y - a.y) * (b.x - a.x) / (b.y - a.y);
+                if p.x < crossing_x {
+                    inside = !inside;
+                }
+            }
+        }
+        inside
+    }
+}
+
+/// The convex hull of `points`, counter-clockwise from the leftmost point
+/// (lowest on ties), by Andrew's monotone chain. Duplicates and points in the middle of
+/// a hull side are left out. Fewer than three distinct points, or points all
+/// on one line, give the distinct extreme points only.
+///
+/// The points are sorted by x, then y, and the lower and upper halves of the
+/// hull are built in one pass each: a point that would make a right turn (or
+/// go straight) pops the previous point off the chain.
+///
+/// # Panics
+///
+/// If a coordinate is NaN, since the points cannot be sorted.
+///
+/// ```
+/// use rcrash_core::geometry::convex_hull;
+/// use rcrash_core::point::Point;
+///
+/// let points = [(0, 0), (2, 1), (4, 0), (3, 3), (1, 3), (2, 4), (0, 0)].map(|(x, y)| Point::new(x, y));
+/// let hull: Vec<_> = convex_hull(&points).into_iter().map(Point::into_tuple).collect();
+/// assert_eq!(hull, [(0, 0), (4, 0), (3, 3), (2, 4), (1, 3)]);
+/// ```
+pub fn convex_hull<T: Coord>(points: &[Point<T>]) -> Vec<Point<T>> {
+    let mut sorted = points.to_vec();
+    merge_sort_by(&mut sorted, |a, b| {
+        (a.x, a.y).partial_cmp(&(b.x, b.y)).expect("convex_hull: coordinates must not be NaN")
+    });
+    sorted.dedup();
+    if sorted.len() < 3 {
+        return sorted;
+    }
+
+    fn chain<T: Coord>(points: impl Iterator<Item = Point<T>>) -> Vec<Point<T>> {
+        let mut chain: Vec<Point<T>> = Vec::new();
+        for p in points {
+            while let [.., a, b] = chain[..]
+                && orientation(a, b, p) != Ordering::Greater
+            {
+                chain.pop();
+            }
+            chain.push(p);
+        }
+        // The last point starts the other chain
+        chain.pop();
+        chain
+    }
+    let mut hull = chain(sorted.iter().copied());
+    hull.extend(chain(sorted.iter().rev().copied()));
+    hull
+}
+
+/// A drawing, written out as an SVG document.
+///
+/// Shapes are added with `polygon`, `segment` and `point`, each with a CSS
+/// style such as `"fill:#cde;stroke:#246"`. The view box is fitted to the
+/// shapes when the document is written. The y axis is flipped so that y
+/// grows upwards, as in the maths, instead of downwards as in SVG.
+#[derive(Debug, Clone, Default)]
+pub struct Svg {
+    shapes: Vec<Shape>,
+    bounds: Option<BoundingBox<f64>>,
+}
+
+#[derive(Debug, Clone)]
+enum Shape {
+    Polygon { points: Vec<Point<f64>>, style: String },
+    Segment { start: Point<f64>, end: Point<f64>, style: String },
+    Point { at: Point<f64>, style: String },
+}
+
+/// Pixel width of the written image; the height follows the aspect ratio.
+const SVG_WIDTH: f64 = 600.0;
+
+impl Svg {
+    pub fn new() -> Self {
+        Svg::default()
+    }
+
+    /// Grows the view to contain `p`.
+    fn include(&mut self, p: Point<f64>) {
+        match &mut self.bounds {
+            Some(bounds) => bounds.include(p),
+            None => self.bounds = Some(BoundingBox { min: p, max: p }),
+        }
+    }
+
+    pub fn polygon<T: Copy + Into<f64>>(&mut self, polygon: &Polygon<T>, style: &str) -> &mut Self {
+        let points: Vec<_> = polygon.vertices().iter().map(|&p| to_f64(p)).collect();
+        points.iter().for_each(|&p| self.include(p));
+        self.shapes.push(Shape::Polygon { points, style: style.to_string() });
+        self
+    }
+
+    pub fn segment<T: Copy + Into<f64>>(&mut self, segment: &Segment<T>, style: &str) -> &mut Self {
+        let (start, end) = (to_f64(segment.start), to_f64(segment.end));
+        self.include(start);
+        self.include(end);
+        self.shapes.push(Shape::Segment { start, end, style: style.to_string() });
+        self
+    }
+
+    pub fn point<T: Copy + Into<f64>>(&mut self, point: Point<T>, style: &str) -> &mut Self {
+        let at = to_f64(point);
+        self.include(at);
+        self.shapes.push(Shape::Point { at, style: style.to_string() });
+        self
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+}
+
+fn to_f64<T: Copy + Into<f64>>(p: Point<T>) -> Point<f64> {
+    Point::new(p.x.into(), p.y.into())
+}
+
+impl fmt::Display for Svg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let b = self.bounds.unwrap_or(BoundingBox { min: Point::new(0.0, 0.0), max: Point::new(1.0, 1.0) });
+        // A margin of 5% of the larger side, and at least 1 unit for a single point
+        let size = b.width().max(b.height()).max(1.0);
+        let margin = size * 0.05;
+        let (width, height) = (b.width() + 2.0 * margin, b.height() + 2.0 * margin);
+        let stroke = size / 200.0;
+        // With the y axis flipped, the visible y range is -max.y ..= -min.y
+        let (left, top) = (b.min.x - margin, -b.max.y - margin);
+        writeln!(
+            f,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="{} {} {} {}">"#,
+            SVG_WIDTH,
+            (SVG_WIDTH * height / width).round(),
+            left,
+            top,
+            width,
+            height
+        )?;
+        writeln!(f, r#"<g transform="scale(1,-1)" stroke-width="{}" fill="none" stroke="black">"#, stroke)?;
+        for shape in &self.shapes {
+            match shape {
+                Shape::Polygon { points, style } => {
+                    let list: Vec<String> = points.iter().map(|p| format!("{},{}", p.x, p.y)).collect();
+                    writeln!(f, r#"  <polygon points="{}" style="{}"/>"#, list.join(" "), style)?;
+                }
+                Shape::Segment { start, end, style } => writeln!(
+                    f,
+                    r#"  <line x1="{}" y1="{}" x2="{}" y2="{}" style="{}"/>"#,
+                    start.x, start.y, end.x, end.y, style
+                )?,
+                Shape::Point { at, style } => {
+                    writeln!(f, r#"  <circle cx="{}" cy="{}" r="{}" style="{}"/>"#, at.x, at.y, stroke * 3.0, style)?
+                }
+            }
+        }
+        writeln!(f, "</g>")?;
+        writeln!(f, "</svg>")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn p<T>(x: T, y: T) -> Point<T> {
+        Point::new(x, y)
+    }
+
+    fn seg<T>(a: (T, T), b: (T, T)) -> Segment<T> {
+        Segment::new(p(a.0, a.1), p(b.0, b.1))
+    }
+
+    #[test]
+    fn polygon_area_and_perimeter() {
+        assert_eq!(Polygon::new(vec![p(0.0, 0.0), p(1.0, 1.0)]), Err(GeometryError::TooFewVertices { found: 2 }));
+
+        // An L shape, clockwise: area 3, perimeter 8
+        let l_shape = vec![p(0.0, 0.0), p(0.0, 2.0), p(1.0, 2.0), p(1.0, 1.0), p(2.0, 1.0), p(2.0, 0.0)];
+        let poly = Polygon::new(l_shape.clone()).unwrap();
+        assert_eq!(poly.area(), 3.0);
+        assert_eq!(poly.perimeter(), 8.0);
+        assert!(!poly.is_counter_clockwise());
+        let reversed = Polygon::new(l_shape.into_iter().rev().collect()).unwrap();
+        assert!(reversed.is_counter_clockwise());
+        assert_eq!(reversed.area(), 3.0);
+
+        // Integer polygons get the exact doubled area
+        let triangle = Polygon::new(vec![p(0, 0), p(5, 0), p(0, 3)]).unwrap();
+        assert_eq!(triangle.signed_double_area(), 15);
+        let bounds = triangle.bounding_box();
+        assert_eq!((bounds.min, bounds.max, bounds.width(), bounds.height()), (p(0, 0), p(5, 3), 5, 3));
+
+        let f32_triangle = Polygon::new(vec![p(0.0_f32, 0.0), p(3.0, 0.0), p(0.0, 4.0)]).unwrap();
+        assert_eq!((f32_triangle.area(), f32_triangle.perimeter()), (6.0, 12.0));
+    }
+
+    #[test]
+    fn point_in_polygon() {
+        // A "U": the notch between x = 1 and x = 2 above y = 1 is outside
+        let u = Polygon::new(vec![
+            p(0.0, 0.0), p(3.0, 0.0), p(3.0, 3.0), p(2.0, 3.0), p(2.0, 1.0), p(1.0, 1.0), p(1.0, 3.0), p(0.0, 3.0),
+        ])
+        .unwrap();
+        assert!(u.contains(p(0.5, 2.0)));
+        assert!(u.contains(p(2.5, 2.5)));
+        assert!(u.contains(p(1.5, 0.5)));
+        assert!(!u.contains(p(1.5, 2.0)));
+        assert!(!u.contains(p(4.0, 1.0)));
+        assert!(!u.contains(p(-1.0, 1.0)));
+        // On edges and vertices
+        assert!(u.contains(p(1.5, 1.0)));
+        assert!(u.contains(p(3.0, 3.0)));
+        // A ray through the vertices (2, 3) and (1, 3) at y = 3
+        assert!(!u.contains(p(-1.0, 3.0)));
+        assert!(!u.contains(p(1.5, 3.0)));
+    }
+
+    #[test]
+    fn segment_intersections() {
+        let x = seg((0.0, 0.0), (4.0, 4.0));
+        assert_eq!(x.intersection(&seg((0.0, 4.0), (4.0, 0.0))), Some(p(2.0, 2.0)));
+        assert_eq!(x.intersection(&seg((4.0, 4.0), (5.0, 0.0))), Some(p(4.0, 4.0)));
+        assert_eq!(x.intersection(&seg((3.0, 0.0), (5.0, 0.0))), None);
+        // Parallel, and collinear overlapping: no single point
+        assert_eq!(x.intersection(&seg((1.0, 0.0), (5.0, 4.0))), None);
+        assert_eq!(x.intersection(&seg((1.0, 1.0), (5.0, 5.0))), None);
+        assert!(x.intersects(&seg((1.0, 1.0), (5.0, 5.0))));
+
+        // intersects agrees with intersection whenever the segments are not parallel
+        let mut rng = StdRng::seed_from_u64(4);
+        for _ in 0..2000 {
+            let mut coord = || rng.random_range(-5..=5);
+            let (a, b) = (seg((coord(), coord()), (coord(), coord())), seg((coord(), coord()), (coord(), coord())));
+            let to_f64 = |s: Segment<i32>| seg((s.start.x as f64, s.start.y as f64), (s.end.x as f64, s.end.y as f64));
+            let (r, s) = (a.end - a.start, b.end - b.start);
+            if r.cross(s) != 0 {
+                assert_eq!(a.intersects(&b), to_f64(a).intersection(&to_f64(b)).is_some(), "{:?} {:?}", a, b);
+            }
+            assert_eq!(a.intersects(&b), b.intersects(&a));
+        }
+    }
+
+    #[test]
+    fn convex_hull_contains_every_point() {
+        assert_eq!(convex_hull::<i32>(&[]), []);
+        assert_eq!(convex_hull(&[p(1, 1), p(1, 1)]), [p(1, 1)]);
+        assert_eq!(convex_hull(&[p(0, 0), p(2, 2), p(1, 1), p(3, 3)]), [p(0, 0), p(3, 3)]);
+        // A square with points on its sides and inside: only the corners remain
+        let square = [p(0, 0), p(1, 0), p(2, 0), p(2, 1), p(2, 2), p(1, 2), p(0, 2), p(0, 1), p(1, 1)];
+        assert_eq!(convex_hull(&square), [p(0, 0), p(2, 0), p(2, 2), p(0, 2)]);
+
+        let mut rng = StdRng::seed_from_u64(5);
+        for n in 3..60 {
+            let points: Vec<Point<f64>> =
+                (0..n).map(|_| p(rng.random_range(-10.0..10.0), rng.random_range(-10.0..10.0))).collect();
+            let hull = Polygon::new(convex_hull(&points)).unwrap();
+            assert!(hull.is_counter_clockwise());
+            // Every turn is a strict left turn, and every input point is inside
+            let v = hull.vertices();
+            for i in 0..v.len() {
+                assert_eq!(orientation(v[i], v[(i + 1) % v.len()], v[(i + 2) % v.len()]), Ordering::Greater);
+            }
+            assert!(points.iter().all(|&q| hull.contains(q)));
+        }
+    }
+
+    #[test]
+    fn svg_fits_the_shapes() {
+        let mut svg = Svg::new();
+        svg.polygon(&Polygon::new(vec![p(0, 0), p(10, 0), p(0, 20)]).unwrap(), "fill:#cde")
+            .segment(&seg((0.0_f32, 0.0), (10.0, 20.0)), "stroke:red")
+            .point(p(5, 5), "fill:blue");
+        let text = svg.to_string();
+        assert!(text.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="600" height="1100" viewBox="-1 -21 12 22">"#));
+        assert!(text.contains(r#"<polygon points="0,0 10,0 0,20" style="fill:#cde"/>"#));
+        assert!(text.contains(r#"<line x1="0" y1="0" x2="10" y2="20" style="stroke:red"/>"#));
+        assert!(text.contains(r#"<circle cx="5" cy="5" r="0.30000000000000004" style="fill:blue"/>"#));
+        assert!(text.ends_with("</g>\n</svg>\n"));
+    }
+}
@@ -0,0 +1,356 @@
+// grading.rs
+// 4.3 / 4.7 Grading with if/else and match, made configurable
+// A grading scale is loaded from a text file instead of being hard-coded in
+// an if/else ladder or a match. The scale is checked for overlaps and gaps,
+// then used to grade a CSV of scores and summarise the results.
+
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// The scale used by ch04_7_match.rs: A 90..=100 down to F 0..60.
+pub const STANDARD_SCALE: &str = include_str!("../data/standard.txt");
+
+/// One grade and the scores it covers, both ends inclusive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Band {
+    pub grade: String,
+    pub min: u32,
+    pub max: u32,
+    /// The line of the scale file this band came from.
+    pub line: usize,
+}
+
+/// Why a scale file was rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScaleError {
+    Io(String),
+    Syntax { line: usize, message: String },
+    Empty,
+    DuplicateGrade { grade: String, line: usize },
+    Overlap { first: String, second: String, from: u32, to: u32 },
+    Gap { below: String, above: String, from: u32, to: u32 },
+}
+
+impl fmt::Display for ScaleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScaleError::Io(e) => write!(f, "cannot read scale: {}", e),
+            ScaleError::Syntax { line, message } => write!(f, "line {}: {}", line, message),
+            ScaleError::Empty => write!(f, "the scale has no grades"),
+            ScaleError::DuplicateGrade { grade, line } => {
+                write!(f, "line {}: grade '{}' is defined twice", line, grade)
+            }
+            ScaleError::Overlap { first, second, from, to } => {
+                write!(f, "grades '{}' and '{}' overlap on {}..={}", first, second, from, to)
+            }
+            ScaleError::Gap { below, above, from, to } => {
+                write!(f, "no grade covers {}..={} (between '{}' and '{}')", from, to, below, above)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ScaleError {}
+
+/// Parses `90..=100` or `0..60` into inclusive bounds.
+fn parse_range(text: &str) -> Result<(u32, u32), String> {
+    let (start, end, inclusive) = if let Some((s, e)) = text.split_once("..=") {
+        (s, e, true)
+    } else if let Some((s, e)) = text.split_once("..") {
+        (s, e, false)
+    } else {
+        return Err(format!("expected a range like 90..=100, found '{}'", text));
+    };
+    let number = |s: &str| s.parse::<u32>().map_err(|_| format!("'{}' is not a whole number", s));
+    let (start, end) = (number(start)?, number(end)?);
+    let max = if inclusive {
+        end
+    } else {
+        end.checked_sub(1).ok_or(format!("range '{}' is empty", text))?
+    };
+    if max < start {
+        return Err(format!("range '{}' is empty", text));
+    }
+    Ok((start, max))
+}
+
+/// A validated set of bands with no overlaps and no gaps, highest first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scale {
+    bands: Vec<Band>,
+}
+
+impl Scale {
+    /// Parses a scale definition: one `<grade> <range>` per line, `#` comments.
+    pub fn parse(text: &str) -> Result<Scale, ScaleError> {
+        let mut bands: Vec<Band> = Vec::new();
+        for (index, raw) in text.lines().enumerate() {
+            let line = index + 1;
+            let content = raw.split('#').next().unwrap_or("").trim();
+            if content.is_empty() {
+                continue;
+            }
+            let mut fields = content.split_whitespace();
+            let (Some(grade), Some(range), None) = (fields.next(), fields.next(), fields.next()) else {
+                let message = format!("expected '<grade> <range>', found '{}'", content);
+                return Err(ScaleError::Syntax { line, message });
+            };
+            let (min, max) = parse_range(range).map_err(|message| ScaleError::Syntax { line, message })?;
+            if bands.iter().any(|b| b.grade == grade) {
+                return Err(ScaleError::DuplicateGrade { grade: grade.to_string(), line });
+            }
+            bands.push(Band { grade: grade.to_string(), min, max, line });
+        }
+        if bands.is_empty() {
+            return Err(ScaleError::Empty);
+        }
+
+        // Sorted by lower bound, each band must start right after the previous one ends
+        bands.sort_by_key(|b| b.min);
+        for pair in bands.windows(2) {
+            let (low, high) = (&pair[0], &pair[1]);
+            if high.min <= low.max {
+                return Err(ScaleError::Overlap {
+                    first: low.grade.clone(),
+                    second: high.grade.clone(),
+                    from: high.min,
+                    to: low.max.min(high.max),
+                });
+            }
+            if high.min > low.max + 1 {
+                return Err(ScaleError::Gap {
+                    below: low.grade.clone(),
+                    above: high.grade.clone(),
+                    from: low.max + 1,
+                    to: high.min - 1,
+                });
+            }
+        }
+        bands.reverse();
+        Ok(Scale { bands })
+    }
+
+    /// Reads and parses a scale file. Like `Syntax` errors, `Io` errors do not
+    /// name the file; the caller adds the path it was given.
+    pub fn load(path: &Path) -> Result<Scale, ScaleError> {
+        let text = fs::read_to_string(path).map_err(|e| ScaleError::Io(e.to_string()))?;
+        Scale::parse(&text)
+    }
+
+    /// The A–F scale shipped in crates/rcrash-core/data/standard.txt.
+    pub fn standard() -> Scale {
+        Scale::parse(STANDARD_SCALE).expect("the bundled scale is valid")
+    }
+
+    /// Bands from the highest grade to the lowest.
+    pub fn bands(&self) -> &[Band] {
+        &self.bands
+    }
+
+    /// The lowest and highest score the scale covers.
+    pub fn domain(&self) -> (u32, u32) {
+        (self.bands[self.bands.len() - 1].min, self.bands[0].max)
+    }
+
+    /// The grade for `score`, or `None` outside the scale's domain.
+    pub fn grade(&self, score: u32) -> Option<&str> {
+        self.bands
+            .iter()
+            .find(|b| (b.min..=b.max).contains(&score))
+            .map(|b| b.grade.as_str())
+    }
+}
+
+/// A bad row in the scores CSV.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for CsvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for CsvError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GradedStudent {
+    pub name: String,
+    pub score: u32,
+    pub grade: String,
+}
+
+/// Mean, median, spread and extremes of the scores.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Summary {
+    pub count: usize,
+    pub mean: f64,
+    pub median: f64,
+    /// Population standard deviation.
+    pub std_dev: f64,
+    pub min: u32,
+    pub max: u32,
+}
+
+impl Summary {
+    /// `None` for an empty list of scores.
+    pub fn of(scores: &[u32]) -> Option<Summary> {
+        let (&min, &max) = (scores.iter().min()?, scores.iter().max()?);
+        let count = scores.len();
+        let mean = scores.iter().map(|&s| s as f64).sum::<f64>() / count as f64;
+        let variance = scores.iter().map(|&s| (s as f64 - mean).powi(2)).sum::<f64>() / count as f64;
+        let mut sorted = scores.to_vec();
+        sorted.sort_unstable();
+        let median = if count % 2 == 1 {
+            sorted[count / 2] as f64
+        } else {
+            (sorted[count / 2 - 1] as f64 + sorted[count / 2] as f64) / 2.0
+        };
+        Some(Summary { count, mean, median, std_dev: variance.sqrt(), min, max })
+    }
+}
+
+/// Every student's grade, the count per grade and summary statistics.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub students: Vec<GradedStudent>,
+    /// Every grade of the scale, highest first, including those nobody got.
+    pub histogram: Vec<(String, usize)>,
+    pub summary: Option<Summary>,
+}
+
+/// Grades `name,score` rows. A first row that is not a score is taken as a
+/// header; empty lines are skipped.
+pub fn grade_csv(scale: &Scale, csv: &str) -> Result<Report, CsvError> {
+    let mut students = Vec::new();
+    for (index, raw) in csv.lines().enumerate() {
+        let line = index + 1;
+        if raw.trim().is_empty() {
+            continue;
+        }
+        let Some((name, score)) = raw.rsplit_once(',') else {
+            return Err(CsvError { line, message: format!("expected 'name,score', found '{}'", raw) });
+        };
+        let (name, score) = (name.trim(), score.trim());
+        let score = match score.parse::<u32>() {
+            Ok(score) => score,
+            Err(_) if students.is_empty() && index == 0 => continue, // header
+            Err(_) => {
+                return Err(CsvError { line, message: format!("'{}' is not a whole-number score", score) });
+            }
+        };
+        let Some(grade) = scale.grade(score) else {
+            let (low, high) = scale.domain();
+            let message = format!("score {} for {} is outside the scale ({}..={})", score, name, low, high);
+            return Err(CsvError { line, message });
+        };
+        students.push(GradedStudent { name: name.to_string(), score, grade: grade.to_string() });
+    }
+
+    let histogram = scale
+        .bands()
+        .iter()
+        .map(|b| (b.grade.clone(), students.iter().filter(|s| s.grade == b.grade).count()))
+        .collect();
+    let scores: Vec<u32> = students.iter().map(|s| s.score).collect();
+    Ok(Report { summary: Summary::of(&scores), students, histogram })
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name_width = self.students.iter().map(|s| s.name.chars().count()).max().unwrap_or(4).max(4);
+        let grade_width = self.histogram.iter().map(|(g, _)| g.chars().count()).max().unwrap_or(5).max(5);
+        writeln!(f, "{:<nw$}  {:>5}  grade", "name", "score", nw = name_width)?;
+        for s in &self.students {
+            writeln!(f, "{:<nw$}  {:>5}  {}", s.name, s.score, s.grade, nw = name_width)?;
+        }
+        writeln!(f)?;
+        for (grade, count) in &self.histogram {
+            writeln!(f, "{:<gw$} {:>3} {}", grade, count, "#".repeat(*count), gw = grade_width)?;
+        }
+        match &self.summary {
+            Some(s) => write!(
+                f,
+                "\ncount {}  mean {:.1}  median {:.1}  std dev {:.1}  min {}  max {}",
+                s.count, s.mean, s.median, s.std_dev, s.min, s.max
+            ),
+            None => write!(f, "\nno scores"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn standard_scale_matches_ch04_7() {
+        let scale = Scale::standard();
+        let expected = |score| match score {
+            90..=100 => "A",
+            80..=89 => "B",
+            70..=79 => "C",
+            60..=69 => "D",
+            _ => "F",
+        };
+        for score in 0..=100 {
+            assert_eq!(scale.grade(score), Some(expected(score)), "score {}", score);
+        }
+        assert_eq!(scale.grade(101), None);
+        assert_eq!(scale.domain(), (0, 100));
+    }
+
+    #[test]
+    fn rejects_overlaps_gaps_and_bad_lines() {
+        assert_eq!(
+            Scale::parse("A 90..=100\nB 80..=90"),
+            Err(ScaleError::Overlap { first: "B".into(), second: "A".into(), from: 90, to: 90 })
+        );
+        assert_eq!(
+            Scale::parse("A 90..=100\nB 80..89"),
+            Err(ScaleError::Gap { below: "B".into(), above: "A".into(), from: 89, to: 89 })
+        );
+        assert_eq!(Scale::parse("# nothing\n\n"), Err(ScaleError::Empty));
+        assert_eq!(
+            Scale::parse("A 1..=2\nA 3..=4"),
+            Err(ScaleError::DuplicateGrade { grade: "A".into(), line: 2 })
+        );
+        assert!(matches!(Scale::parse("A 90-100"), Err(ScaleError::Syntax { line: 1, .. })));
+        assert!(matches!(Scale::parse("A\n"), Err(ScaleError::Syntax { line: 1, .. })));
+        assert!(matches!(Scale::parse("A 5..5"), Err(ScaleError::Syntax { line: 1, .. })));
+
+        // The caller prefixes the path, so the error must not repeat it
+        let missing = Scale::load(Path::new("no-such-scale.txt")).unwrap_err();
+        assert!(matches!(missing, ScaleError::Io(_)));
+        assert!(!missing.to_string().contains("no-such-scale"), "{}", missing);
+    }
+
+    #[test]
+    fn grades_csv_with_histogram_and_summary() {
+        let csv = "name,score\nAlice,93\nBob, 85\n\nCarol,78\nDave,85\n";
+        let report = grade_csv(&Scale::standard(), csv).unwrap();
+        let grades: Vec<&str> = report.students.iter().map(|s| s.grade.as_str()).collect();
+        assert_eq!(grades, ["A", "B", "C", "B"]);
+        let counts: Vec<usize> = report.histogram.iter().map(|(_, n)| *n).collect();
+        assert_eq!(counts, [1, 2, 1, 0, 0]);
+        let summary = report.summary.unwrap();
+        assert_eq!((summary.count, summary.min, summary.max), (4, 78, 93));
+        assert_eq!((summary.mean, summary.median), (85.25, 85.0));
+        assert!((summary.std_dev - 5.3092).abs() < 1e-3);
+    }
+
+    #[test]
+    fn csv_errors_name_the_line() {
+        let scale = Scale::standard();
+        assert_eq!(grade_csv(&scale, "Alice,93\nBob,eighty").unwrap_err().line, 2);
+        assert_eq!(
+            grade_csv(&scale, "name,score\nZed,120").unwrap_err().message,
+            "score 120 for Zed is outside the scale (0..=100)"
+        );
+        assert_eq!(grade_csv(&scale, "").unwrap().summary, None);
+    }
+}
@@ -0,0 +1,203 @@
+// index_parse.rs
+// 3.8 Arrays: indexes and ranges typed in by a user
+// Turns text such as `2`, `-1`, `1..3` or `..=2` into a checked position or
+// range for any slice, and slices `str` only on char boundaries.
+
+use std::fmt;
+use std::ops::Range;
+
+/// Why a piece of text could not be used as an index or range.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IndexError {
+    /// The text is not an integer or a `a..b` / `a..=b` range.
+    Parse { text: String },
+    /// The index (as typed) does not exist in a collection of `len` items.
+    OutOfBounds { index: isize, len: usize },
+    /// The range starts after it ends, such as `3..1`.
+    Inverted { start: usize, end: usize },
+    /// A `str` byte offset that falls inside a multi-byte character.
+    NotCharBoundary { index: usize },
+}
+
+impl fmt::Display for IndexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IndexError::Parse { text } => {
+                write!(f, "'{}' is not an index or a range like 1..3", text)
+            }
+            IndexError::OutOfBounds { index, len } => {
+                write!(f, "index {} is out of bounds for length {}", index, len)
+            }
+            IndexError::Inverted { start, end } => {
+                write!(f, "range starts at {} but ends at {}", start, end)
+            }
+            IndexError::NotCharBoundary { index } => {
+                write!(f, "byte {} is inside a character, not on a char boundary", index)
+            }
+        }
+    }
+}
+
+impl std::error::Error for IndexError {}
+
+/// What the user typed, before it is checked against a length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Selector {
+    /// `2`, or `-1` for the last item.
+    Index(isize),
+    /// `1..3`, `..=2`, `-2..`, `..`.
+    Range { start: Option<isize>, end: Option<isize>, inclusive: bool },
+}
+
+fn parse_number(text: &str, whole: &str) -> Result<isize, IndexError> {
+    text.trim().parse().map_err(|_| IndexError::Parse { text: whole.to_string() })
+}
+
+/// Parses an index or a range without knowing the length yet.
+pub fn parse_selector(text: &str) -> Result<Selector, IndexError> {
+    let trimmed = text.trim();
+    let Some((start, end)) = trimmed.split_once("..") else {
+        return parse_number(trimmed, text).map(Selector::Index);
+    };
+    let (end, inclusive) = match end.strip_prefix('=') {
+        Some(end) => (end, true),
+        None => (end, false),
+    };
+    let bound = |s: &str| -> Result<Option<isize>, IndexError> {
+        if s.trim().is_empty() { Ok(None) } else { parse_number(s, text).map(Some) }
+    };
+    let (start, end) = (bound(start)?, bound(end)?);
+    // `..=` needs an end, just like in Rust
+    if inclusive && end.is_none() {
+        return Err(IndexError::Parse { text: text.to_string() });
+    }
+    Ok(Selector::Range { start, end, inclusive })
+}
+
+/// Turns a possibly negative position into an offset from the front.
+/// `allow_end` admits `len` itself, which is valid as a range bound.
+fn resolve(index: isize, len: usize, allow_end: bool) -> Result<usize, IndexError> {
+    let out_of_bounds = IndexError::OutOfBounds { index, len };
+    let offset = if index < 0 {
+        len.checked_sub(index.unsigned_abs()).ok_or(out_of_bounds.clone())?
+    } else {
+        index as usize
+    };
+    if offset < len || (allow_end && offset == len) {
+        Ok(offset)
+    } else {
+        Err(out_of_bounds)
+    }
+}
+
+impl Selector {
+    /// Checks the selector against a collection of `len` items and returns
+    /// the non-negative range it covers. A plain index becomes a
+    /// one-item range.
+    pub fn resolve(&self, len: usize) -> Result<Range<usize>, IndexError> {
+        match *self {
+            Selector::Index(index) => {
+                let i = resolve(index, len, false)?;
+                Ok(i..i + 1)
+            }
+            Selector::Range { start, end, inclusive } => {
+                let start = match start {
+                    Some(s) => resolve(s, len, true)?,
+                    None => 0,
+                };
+                let end = match end {
+                    // `..=i` includes item i, so i itself must exist
+                    Some(e) if inclusive => resolve(e, len, false)? + 1,
+                    Some(e) => resolve(e, len, true)?,
+                    None => len,
+                };
+                if start > end {
+                    return Err(IndexError::Inverted { start, end });
+                }
+                Ok(start..end)
+            }
+        }
+    }
+}
+
+/// Parses a single index such as `2` or `-1` and checks it against `len`.
+pub fn parse_index(text: &str, len: usize) -> Result<usize, IndexError> {
+    match parse_selector(text)? {
+        Selector::Index(index) => resolve(index, len, false),
+        Selector::Range { .. } => Err(IndexError::Parse { text: text.to_string() }),
+    }
+}
+
+/// Parses an index or a range and checks it against `len`.
+pub fn parse_range(text: &str, len: usize) -> Result<Range<usize>, IndexError> {
+    parse_selector(text)?.resolve(len)
+}
+
+/// `get(&arr, "-1")` is the last item.
+pub fn get<'a, T>(items: &'a [T], text: &str) -> Result<&'a T, IndexError> {
+    parse_index(text, items.len()).map(|i| &items[i])
+}
+
+/// `get_range(&arr, "1..3")` is `&arr[1..3]`; a single index gives one item.
+pub fn get_range<'a, T>(items: &'a [T], text: &str) -> Result<&'a [T], IndexError> {
+    parse_range(text, items.len()).map(|range| &items[range])
+}
+
+/// Like `get_range`, but on the bytes of a `str`. Instead of panicking in the
+/// middle of a character the way `&s[0..1]` on `"é"` would, it returns
+/// `NotCharBoundary`.
+pub fn str_range<'a>(s: &'a str, text: &str) -> Result<&'a str, IndexError> {
+    let range = parse_range(text, s.len())?;
+    for index in [range.start, range.end] {
+        if !s.is_char_boundary(index) {
+            return Err(IndexError::NotCharBoundary { index });
+        }
+    }
+    Ok(&s[range])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn indexes_from_either_end() {
+        let arr = [10, 20, 30, 40, 50];
+        assert_eq!(get(&arr, "2"), Ok(&30));
+        assert_eq!(get(&arr, " -1 "), Ok(&50));
+        assert_eq!(get(&arr, "-5"), Ok(&10));
+        assert_eq!(get(&arr, "5"), Err(IndexError::OutOfBounds { index: 5, len: 5 }));
+        assert_eq!(get(&arr, "-6"), Err(IndexError::OutOfBounds { index: -6, len: 5 }));
+        assert_eq!(get(&arr, "two"), Err(IndexError::Parse { text: "two".into() }));
+        assert_eq!(get(&arr, "1..2"), Err(IndexError::Parse { text: "1..2".into() }));
+        assert!(get::<i32>(&[], "0").is_err());
+    }
+
+    #[test]
+    fn ranges_match_slice_syntax() {
+        let arr = [1, 2, 3, 4, 5];
+        assert_eq!(get_range(&arr, "1..3"), Ok(&arr[1..3]));
+        assert_eq!(get_range(&arr, "..=2"), Ok(&arr[..=2]));
+        assert_eq!(get_range(&arr, "3.."), Ok(&arr[3..]));
+        assert_eq!(get_range(&arr, ".."), Ok(&arr[..]));
+        assert_eq!(get_range(&arr, "5.."), Ok(&arr[5..]));
+        assert_eq!(get_range(&arr, "-2.."), Ok(&arr[3..]));
+        assert_eq!(get_range(&arr, "..-1"), Ok(&arr[..4]));
+        assert_eq!(get_range(&arr, "2"), Ok(&arr[2..3]));
+        assert_eq!(get_range(&arr, "3..1"), Err(IndexError::Inverted { start: 3, end: 1 }));
+        assert_eq!(get_range(&arr, "..=5"), Err(IndexError::OutOfBounds { index: 5, len: 5 }));
+        assert_eq!(get_range(&arr, "0..6"), Err(IndexError::OutOfBounds { index: 6, len: 5 }));
+        assert!(matches!(get_range(&arr, "1..="), Err(IndexError::Parse { .. })));
+        assert!(matches!(get_range(&arr, "a..b"), Err(IndexError::Parse { .. })));
+    }
+
+    #[test]
+    fn str_ranges_respect_char_boundaries() {
+        let s = "héllo";
+        assert_eq!(str_range(s, "0..1"), Ok("h"));
+        assert_eq!(str_range(s, "0..3"), Ok("hé"));
+        assert_eq!(str_range(s, "0..2"), Err(IndexError::NotCharBoundary { index: 2 }));
+        assert_eq!(str_range(s, "-3.."), Ok("llo"));
+        assert_eq!(str_range(s, "9.."), Err(IndexError::OutOfBounds { index: 9, len: 6 }));
+    }
+}
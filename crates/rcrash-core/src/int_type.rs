@@ -0,0 +1,111 @@
+// int_type.rs
+// The primitive integer types as a value, for code that picks a width at run
+// time: the format-spec explorer (3.13) and the overflow calculator (3.6).
+
+/// One of Rust's twelve primitive integer types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntType {
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    Isize,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    Usize,
+}
+
+impl IntType {
+    /// Every type: the signed ones first, then the unsigned ones.
+    pub const ALL: [IntType; 12] = [
+        IntType::I8,
+        IntType::I16,
+        IntType::I32,
+        IntType::I64,
+        IntType::I128,
+        IntType::Isize,
+        IntType::U8,
+        IntType::U16,
+        IntType::U32,
+        IntType::U64,
+        IntType::U128,
+        IntType::Usize,
+    ];
+
+    /// The type's name as written in Rust, such as `"u8"`.
+    pub fn name(self) -> &'static str {
+        match self {
+            IntType::I8 => "i8",
+            IntType::I16 => "i16",
+            IntType::I32 => "i32",
+            IntType::I64 => "i64",
+            IntType::I128 => "i128",
+            IntType::Isize => "isize",
+            IntType::U8 => "u8",
+            IntType::U16 => "u16",
+            IntType::U32 => "u32",
+            IntType::U64 => "u64",
+            IntType::U128 => "u128",
+            IntType::Usize => "usize",
+        }
+    }
+
+    /// Parses a type name such as `u8` or `i128`.
+    pub fn from_suffix(s: &str) -> Option<IntType> {
+        Some(match s {
+            "i8" => IntType::I8,
+            "i16" => IntType::I16,
+            "i32" => IntType::I32,
+            "i64" => IntType::I64,
+            "i128" => IntType::I128,
+            "isize" => IntType::Isize,
+            "u8" => IntType::U8,
+            "u16" => IntType::U16,
+            "u32" => IntType::U32,
+            "u64" => IntType::U64,
+            "u128" => IntType::U128,
+            "usize" => IntType::Usize,
+            _ => return None,
+        })
+    }
+
+    /// The width in bits. `isize` and `usize` follow the target.
+    pub fn bits(self) -> u32 {
+        match self {
+            IntType::I8 | IntType::U8 => 8,
+            IntType::I16 | IntType::U16 => 16,
+            IntType::I32 | IntType::U32 => 32,
+            IntType::I64 | IntType::U64 => 64,
+            IntType::I128 | IntType::U128 => 128,
+            IntType::Isize | IntType::Usize => usize::BITS,
+        }
+    }
+
+    pub fn signed(self) -> bool {
+        matches!(
+            self,
+            IntType::I8 | IntType::I16 | IntType::I32 | IntType::I64 | IntType::I128 | IntType::Isize
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_round_trip_and_match_the_real_types() {
+        for ty in IntType::ALL {
+            assert_eq!(IntType::from_suffix(ty.name()), Some(ty));
+            assert_eq!(ty.signed(), ty.name().starts_with('i'));
+        }
+        assert_eq!(IntType::U8.bits(), u8::BITS);
+        assert_eq!(IntType::I128.bits(), i128::BITS);
+        assert_eq!(IntType::Isize.bits(), isize::BITS);
+        assert_eq!(IntType::from_suffix("u7"), None);
+    }
+}
@@ -0,0 +1,30 @@
+// lib.rs
+// Shared library for the course examples.
+// The chapter binaries (src/bin/ of the root package) and the launcher in
+// crates/rcrash-cli pull reusable types from these modules.
+
+pub mod message;
+pub mod status;
+pub mod int_type;
+pub mod fmt_spec;
+pub mod cast_matrix;
+pub mod codec;
+pub mod overflow_calc;
+pub mod type_inspect;
+pub mod matrix;
+pub mod index_parse;
+pub mod slice_algos;
+pub mod expr_lang;
+pub mod grading;
+pub mod countdown;
+pub mod counter;
+pub mod combinators;
+pub mod my_iter;
+pub mod fatal;
+pub mod math;
+pub mod rectangle;
+pub mod sorting;
+pub mod float;
+pub mod point;
+pub mod geometry;
+pub mod registry;
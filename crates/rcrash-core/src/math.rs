@@ -0,0 +1,300 @@
+// math.rs
+// The `mod math` from ch05_02 grown into a number-theory library, laid out to
+// show the visibility levels:
+//
+//   math                      pub: re-exports the public API below
+//   ├── arith                 private module
+//   │   ├── gcd, lcm, mod_pow pub, re-exported as math::gcd, ...
+//   │   └── mul_mod           pub(super): usable anywhere in `math`, not outside
+//   └── primes                private module
+//       ├── sieve, is_prime,
+//       │   factorize         pub, re-exported
+//       └── decompose, is_witness,
+//           pollard_rho       private: only `primes` (and its tests) can call them
+//
+// Callers write `rcrash_core::math::gcd` and never see the inner modules, so they can
+// be rearranged without breaking anyone.
+
+pub use self::arith::{gcd, lcm, mod_pow};
+pub use self::primes::{factorize, is_prime, sieve};
+
+mod arith {
+    /// Greatest common divisor by Euclid's algorithm. `gcd(0, 0)` is 0.
+    pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+        while b != 0 {
+            (a, b) = (b, a % b);
+        }
+        a
+    }
+
+    /// Least common multiple, or `None` if it does not fit in a `u64`.
+    pub fn lcm(a: u64, b: u64) -> Option<u64> {
+        if a == 0 || b == 0 {
+            return Some(0);
+        }
+        // Divide first, so only a result that really is too big overflows
+        (a / gcd(a, b)).checked_mul(b)
+    }
+
+    /// `base^exp mod modulus` by square-and-multiply: O(log exp) multiplications.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `modulus` is zero.
+    pub fn mod_pow(base: u64, mut exp: u64, modulus: u64) -> u64 {
+        assert!(modulus != 0, "mod_pow with a zero modulus");
+        let mut result = 1 % modulus;
+        let mut base = base % modulus;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = mul_mod(result, base, modulus);
+            }
+            base = mul_mod(base, base, modulus);
+            exp >>= 1;
+        }
+        result
+    }
+
+    /// `a * b mod m` without overflow: the product of two u64 always fits in a u128.
+    /// `pub(super)` lets `primes` use it while keeping it out of the public API.
+    pub(super) fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+        (u128::from(a) * u128::from(b) % u128::from(m)) as u64
+    }
+}
+
+mod primes {
+    use super::arith::{gcd, mod_pow, mul_mod};
+
+    /// Bases that make Miller–Rabin exact for every `u64`: no composite below
+    /// 2^64 is a strong pseudoprime to all of them.
+    const WITNESSES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+    /// All primes `<= limit`, by the sieve of Eratosthenes.
+    pub fn sieve(limit: u64) -> Vec<u64> {
+        let limit = usize::try_from(limit).expect("sieve limit fits in memory");
+        let mut composite = vec![false; limit + 1];
+        let mut primes = Vec::new();
+        for n in 2..=limit {
+            if composite[n] {
+                continue;
+            }
+            primes.push(n as u64);
+            // Smaller multiples were already crossed off by smaller primes
+            for multiple in (n.saturating_mul(n)..=limit).step_by(n) {
+                composite[multiple] = true;
+            }
+        }
+        primes
+    }
+
+    /// Deterministic Miller–Rabin primality test for any `u64`.
+    pub fn is_prime(n: u64) -> bool {
+        if n < 2 {
+            return false;
+        }
+        // Also covers every witness, which the loop below cannot test against itself
+        for p in WITNESSES {
+            if n.is_multiple_of(p) {
+                return n == p;
+            }
+        }
+        let (d, s) = decompose(n - 1);
+        WITNESSES.iter().all(|&a| !is_witness(a, n, d, s))
+    }
+
+    /// Prime factorisation as `(prime, exponent)` pairs in increasing order.
+    /// 0 and 1 have no prime factors and give an empty list.
+    pub fn factorize(n: u64) -> Vec<(u64, u32)> {
+        let mut factors = Vec::new();
+        if n > 1 {
+            collect_factors(n, &mut factors);
+        }
+        factors.sort_unstable();
+        let mut grouped: Vec<(u64, u32)> = Vec::new();
+        for p in factors {
+            match grouped.last_mut() {
+                Some((last, count)) if *last == p => *count += 1,
+                _ => grouped.push((p, 1)),
+            }
+        }
+        grouped
+    }
+
+    /// Writes `n` as `d * 2^s` with `d` odd.
+    fn decompose(n: u64) -> (u64, u32) {
+        let s = n.trailing_zeros();
+        (n >> s, s)
+    }
+
+    /// True if `a` proves the odd number `n` composite, where `n - 1 = d * 2^s`.
+    fn is_witness(a: u64, n: u64, d: u64, s: u32) -> bool {
+        let mut x = mod_pow(a, d, n);
+        if x == 1 || x == n - 1 {
+            return false;
+        }
+        for _ in 1..s {
+            x = mul_mod(x, x, n);
+            if x == n - 1 {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Pushes every prime factor of `n > 1`, with repeats, in no particular order.
+    fn collect_factors(n: u64, out: &mut Vec<u64>) {
+        // Small factors first: cheap, and Pollard's rho is poor at finding 2
+        let mut n = n;
+        for p in WITNESSES {
+            while n.is_multiple_of(p) {
+                out.push(p);
+                n /= p;
+            }
+        }
+        let mut stack = vec![n];
+        while let Some(m) = stack.pop() {
+            if m == 1 {
+                continue;
+            }
+            if is_prime(m) {
+                out.push(m);
+                continue;
+            }
+            let d = pollard_rho(m);
+            stack.push(d);
+            stack.push(m / d);
+        }
+    }
+
+    /// A non-trivial divisor of the odd composite `n`, by Pollard's rho with
+    /// Floyd cycle detection. Retries with a new constant when a round fails.
+    fn pollard_rho(n: u64) -> u64 {
+        for c in 1u64.. {
+            // Adding in u128, since x*x mod n plus c can pass u64::MAX when n is close to it
+            let f = |x: u64| ((u128::from(mul_mod(x, x, n)) + u128::from(c)) % u128::from(n)) as u64;
+            let (mut x, mut y, mut d) = (2, 2, 1);
+            while d == 1 {
+                x = f(x);
+                y = f(f(y));
+                d = gcd(x.abs_diff(y), n);
+            }
+            if d != n {
+                return d;
+            }
+        }
+        unreachable!("some constant always splits a composite")
+    }
+
+    #[cfg(test)]
+    mod tests {
+        // `super::*` is `primes`, so its private helpers are in scope here
+        use super::*;
+
+        #[test]
+        fn decompose_splits_out_powers_of_two() {
+            assert_eq!(decompose(96), (3, 5));
+            assert_eq!(decompose(7), (7, 0));
+        }
+
+        #[test]
+        fn witnesses_expose_strong_pseudoprimes() {
+            // 2047 = 23 * 89 fools base 2 but not base 3
+            let (d, s) = decompose(2046);
+            assert!(!is_witness(2, 2047, d, s));
+            assert!(is_witness(3, 2047, d, s));
+        }
+
+        #[test]
+        fn pollard_rho_finds_a_proper_divisor() {
+            for n in [8051, 10403, 4_294_967_291 * 65_521, 600_851_475_143] {
+                let d = pollard_rho(n);
+                assert!(d > 1 && d < n && n % d == 0, "{} for {}", d, n);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // `super::*` is `math`: the re-exported API plus the private `arith` and
+    // `primes` modules themselves. `arith::mul_mod` is reachable because
+    // `pub(super)` means visible in `math` and everything inside it, but
+    // `primes::decompose` is private to `primes` and would not compile here.
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    const CASES: usize = 500;
+
+    #[test]
+    fn gcd_and_lcm() {
+        assert_eq!(gcd(0, 0), 0);
+        assert_eq!(gcd(0, 7), 7);
+        assert_eq!(gcd(48, 18), 6);
+        assert_eq!(lcm(4, 6), Some(12));
+        assert_eq!(lcm(0, 6), Some(0));
+        assert_eq!(lcm(u64::MAX, u64::MAX - 1), None);
+        let mut rng = StdRng::seed_from_u64(43);
+        for _ in 0..CASES {
+            let (a, b) = (rng.random_range(1..10_000u64), rng.random_range(1..10_000u64));
+            let g = gcd(a, b);
+            assert!(a % g == 0 && b % g == 0);
+            assert_eq!(gcd(a / g, b / g), 1);
+            assert_eq!(lcm(a, b), Some(a * b / g));
+        }
+    }
+
+    #[test]
+    fn mod_pow_and_mul_mod() {
+        assert_eq!(mod_pow(2, 10, 1000), 24);
+        assert_eq!(mod_pow(5, 0, 1), 0);
+        assert_eq!(arith::mul_mod(u64::MAX, u64::MAX, u64::MAX - 1), 1);
+        let mut rng = StdRng::seed_from_u64(44);
+        for _ in 0..CASES {
+            let (base, exp, m) = (rng.random(), rng.random_range(0..200u64), rng.random_range(1..u64::MAX));
+            let naive = (0..exp).fold(1 % m, |acc, _| arith::mul_mod(acc, base % m, m));
+            assert_eq!(mod_pow(base, exp, m), naive);
+        }
+    }
+
+    #[test]
+    fn is_prime_agrees_with_the_sieve() {
+        let primes = sieve(100_000);
+        assert_eq!(&primes[..10], [2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+        assert_eq!(primes.len(), 9592);
+        let mut expected = primes.iter().peekable();
+        for n in 0..=100_000 {
+            let listed = expected.next_if_eq(&&n).is_some();
+            assert_eq!(is_prime(n), listed, "{}", n);
+        }
+        assert_eq!(sieve(1), []);
+    }
+
+    #[test]
+    fn is_prime_on_hard_cases() {
+        // Carmichael numbers and strong pseudoprimes to several bases
+        for n in [561, 1105, 2047, 3_215_031_751, 3_825_123_056_546_413_051] {
+            assert!(!is_prime(n), "{}", n);
+        }
+        for n in [(1 << 61) - 1, 4_294_967_291, 18_446_744_073_709_551_557] {
+            assert!(is_prime(n), "{}", n);
+        }
+        assert!(!is_prime(u64::MAX));
+    }
+
+    #[test]
+    fn factorize_multiplies_back() {
+        assert_eq!(factorize(0), []);
+        assert_eq!(factorize(1), []);
+        assert_eq!(factorize(360), [(2, 3), (3, 2), (5, 1)]);
+        assert_eq!(factorize(u64::MAX), [(3, 1), (5, 1), (17, 1), (257, 1), (641, 1), (65_537, 1), (6_700_417, 1)]);
+        let mut rng = StdRng::seed_from_u64(45);
+        for _ in 0..CASES {
+            let n: u64 = rng.random_range(2..u64::MAX);
+            let factors = factorize(n);
+            assert!(factors.windows(2).all(|w| w[0].0 < w[1].0));
+            assert!(factors.iter().all(|&(p, _)| is_prime(p)));
+            assert_eq!(factors.iter().map(|&(p, k)| p.pow(k)).product::<u64>(), n);
+        }
+    }
+}
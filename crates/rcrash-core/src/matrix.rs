@@ -0,0 +1,239 @@
+// matrix.rs
+// 3.8 Arrays, two dimensions deep
+// A `Matrix<T, R, C>` stored as `[[T; C]; R]`. The dimensions are const
+// generics, so multiplying a 2×3 by a 2×3 is a type error, not a panic.
+
+use std::fmt;
+use std::ops::{Add, Index, IndexMut, Mul, Sub};
+
+/// Numbers with a zero and a one, enough to build `Matrix::identity`.
+pub trait Scalar: Copy + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+}
+
+macro_rules! scalar {
+    ($($t:ty: $zero:expr, $one:expr);*) => {
+        $(impl Scalar for $t {
+            const ZERO: Self = $zero;
+            const ONE: Self = $one;
+        })*
+    };
+}
+
+scalar!(i8: 0, 1; i16: 0, 1; i32: 0, 1; i64: 0, 1; i128: 0, 1; isize: 0, 1;
+        u8: 0, 1; u16: 0, 1; u32: 0, 1; u64: 0, 1; u128: 0, 1; usize: 0, 1;
+        f32: 0.0, 1.0; f64: 0.0, 1.0);
+
+/// An `R`×`C` matrix, row-major.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Matrix<T, const R: usize, const C: usize> {
+    rows: [[T; C]; R],
+}
+
+impl<T, const R: usize, const C: usize> Matrix<T, R, C> {
+    /// `Matrix::new([[1, 2, 3], [4, 5, 6]])` is a 2×3 matrix.
+    pub const fn new(rows: [[T; C]; R]) -> Self {
+        Matrix { rows }
+    }
+
+    /// Builds every element from its `(row, column)`.
+    pub fn from_fn(mut f: impl FnMut(usize, usize) -> T) -> Self {
+        Matrix { rows: std::array::from_fn(|r| std::array::from_fn(|c| f(r, c))) }
+    }
+
+    pub const fn row_count(&self) -> usize {
+        R
+    }
+
+    pub const fn column_count(&self) -> usize {
+        C
+    }
+
+    pub fn rows(&self) -> &[[T; C]; R] {
+        &self.rows
+    }
+
+    pub fn into_rows(self) -> [[T; C]; R] {
+        self.rows
+    }
+
+    /// Returns `None` instead of panicking when out of bounds.
+    pub fn get(&self, row: usize, column: usize) -> Option<&T> {
+        self.rows.get(row)?.get(column)
+    }
+
+    pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> Matrix<U, R, C> {
+        Matrix { rows: self.rows.map(|row| row.map(&mut f)) }
+    }
+}
+
+impl<T: Copy, const R: usize, const C: usize> Matrix<T, R, C> {
+    /// Rows become columns: an `R`×`C` matrix turns into a `C`×`R` one.
+    pub fn transpose(&self) -> Matrix<T, C, R> {
+        Matrix::from_fn(|r, c| self.rows[c][r])
+    }
+}
+
+impl<T: Scalar, const R: usize, const C: usize> Matrix<T, R, C> {
+    pub fn zero() -> Self {
+        Matrix { rows: [[T::ZERO; C]; R] }
+    }
+}
+
+impl<T: Scalar, const N: usize> Matrix<T, N, N> {
+    /// Ones on the diagonal; only square matrices have one.
+    ///
+    /// ```compile_fail
+    /// use rcrash_core::matrix::Matrix;
+    /// let _: Matrix<i32, 2, 3> = Matrix::identity();
+    /// ```
+    pub fn identity() -> Self {
+        Matrix::from_fn(|r, c| if r == c { T::ONE } else { T::ZERO })
+    }
+}
+
+impl<T, const R: usize, const C: usize> Index<(usize, usize)> for Matrix<T, R, C> {
+    type Output = T;
+
+    /// `m[(row, column)]`; panics when out of bounds, like array indexing.
+    fn index(&self, (row, column): (usize, usize)) -> &T {
+        &self.rows[row][column]
+    }
+}
+
+impl<T, const R: usize, const C: usize> IndexMut<(usize, usize)> for Matrix<T, R, C> {
+    fn index_mut(&mut self, (row, column): (usize, usize)) -> &mut T {
+        &mut self.rows[row][column]
+    }
+}
+
+/// Both sides must have the same shape:
+///
+/// ```compile_fail
+/// use rcrash_core::matrix::Matrix;
+/// let a = Matrix::new([[1, 2], [3, 4]]); // 2×2
+/// let b = Matrix::new([[1, 2, 3], [4, 5, 6]]); // 2×3
+/// let _ = a + b;
+/// ```
+impl<T: Scalar, const R: usize, const C: usize> Add for Matrix<T, R, C> {
+    type Output = Matrix<T, R, C>;
+
+    fn add(self, other: Self) -> Self {
+        Matrix::from_fn(|r, c| self.rows[r][c] + other.rows[r][c])
+    }
+}
+
+impl<T: Scalar, const R: usize, const C: usize> Sub for Matrix<T, R, C> {
+    type Output = Matrix<T, R, C>;
+
+    fn sub(self, other: Self) -> Self {
+        Matrix::from_fn(|r, c| self.rows[r][c] - other.rows[r][c])
+    }
+}
+
+/// (R×C) · (C×K) = R×K. The shared `C` is what makes mismatches a compile error:
+///
+/// ```compile_fail
+/// use rcrash_core::matrix::Matrix;
+/// let a = Matrix::new([[1, 2, 3], [4, 5, 6]]); // 2×3
+/// let b = Matrix::new([[1, 2, 3], [4, 5, 6]]); // 2×3, but 3×K is needed
+/// let _ = a * b;
+/// ```
+///
+/// ```
+/// use rcrash_core::matrix::Matrix;
+/// let a = Matrix::new([[1, 2, 3], [4, 5, 6]]); // 2×3
+/// let _: Matrix<i32, 2, 2> = a * a.transpose(); // 2×3 · 3×2
+/// ```
+impl<T: Scalar, const R: usize, const C: usize, const K: usize> Mul<Matrix<T, C, K>> for Matrix<T, R, C> {
+    type Output = Matrix<T, R, K>;
+
+    fn mul(self, other: Matrix<T, C, K>) -> Matrix<T, R, K> {
+        Matrix::from_fn(|r, k| {
+            (0..C).fold(T::ZERO, |sum, c| sum + self.rows[r][c] * other.rows[c][k])
+        })
+    }
+}
+
+impl<T: fmt::Display, const R: usize, const C: usize> fmt::Display for Matrix<T, R, C> {
+    /// One bracketed line per row, each column right-aligned to its widest
+    /// cell. A precision such as `{:.2}` is passed on to every element.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cells: Vec<Vec<String>> = self
+            .rows
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|x| match f.precision() {
+                        Some(p) => format!("{:.*}", p, x),
+                        None => x.to_string(),
+                    })
+                    .collect()
+            })
+            .collect();
+        let widths: Vec<usize> = (0..C)
+            .map(|c| cells.iter().map(|row| row[c].chars().count()).max().unwrap_or(0))
+            .collect();
+        for (r, row) in cells.iter().enumerate() {
+            if r > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "[")?;
+            for (c, cell) in row.iter().enumerate() {
+                let sep = if c == 0 { "" } else { "  " };
+                write!(f, "{}{:>w$}", sep, cell, w = widths[c])?;
+            }
+            write!(f, "]")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn indexing_and_transpose() {
+        let mut m = Matrix::new([[1, 2, 3], [4, 5, 6]]);
+        assert_eq!(m[(1, 2)], 6);
+        m[(0, 0)] = 10;
+        assert_eq!(m.get(0, 0), Some(&10));
+        assert_eq!(m.get(2, 0), None);
+        let t: Matrix<i32, 3, 2> = m.transpose();
+        assert_eq!(t, Matrix::new([[10, 4], [2, 5], [3, 6]]));
+        assert_eq!((t.row_count(), t.column_count()), (3, 2));
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a = Matrix::new([[1, 2], [3, 4]]);
+        let b = Matrix::new([[5, 6], [7, 8]]);
+        assert_eq!(a + b, Matrix::new([[6, 8], [10, 12]]));
+        assert_eq!(b - a, Matrix::new([[4, 4], [4, 4]]));
+        assert_eq!(a * b, Matrix::new([[19, 22], [43, 50]]));
+        assert_eq!(a * Matrix::identity(), a);
+        assert_eq!(a - a, Matrix::zero());
+
+        // 2×3 · 3×1 = 2×1
+        let v: Matrix<i64, 2, 1> = Matrix::new([[1, 2, 3], [4, 5, 6]]) * Matrix::new([[1], [0], [-1]]);
+        assert_eq!(v.into_rows(), [[-2], [-2]]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_out_of_bounds_panics() {
+        let m = Matrix::new([[1, 2]]);
+        let _ = m[(1, 0)];
+    }
+
+    #[test]
+    fn display_aligns_columns() {
+        let m = Matrix::new([[1, -20, 3], [100, 5, 6]]);
+        assert_eq!(m.to_string(), "[  1  -20  3]\n[100    5  6]");
+        let f = Matrix::new([[1.0, 0.5], [0.25, 2.0]]);
+        assert_eq!(format!("{:.2}", f), "[1.00  0.50]\n[0.25  2.00]");
+        assert_eq!(Matrix::<f64, 2, 2>::identity().map(|x| x * 2.0).to_string(), "[2  0]\n[0  2]");
+    }
+}
@@ -0,0 +1,442 @@
+// message.rs
+// 3.11 Enums in practice
+// A text command interpreter for the `Message` enum from ch03_11_enum.rs:
+// parses lines such as `move 10 20` into `Message` values and applies them
+// to a small canvas state machine.
+
+use std::fmt;
+use std::str::FromStr;
+
+/// The command set from ch03_11, now with a real dispatcher behind it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Message {
+    Quit,                       // No data
+    Move { x: i32, y: i32 },    // Struct-like variant
+    Write(String),              // Tuple-like variant
+    ChangeColor(i32, i32, i32), // Tuple-like variant
+}
+
+/// Prints a message back in the same syntax the parser accepts.
+impl fmt::Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Message::Quit => write!(f, "quit"),
+            Message::Move { x, y } => write!(f, "move {} {}", x, y),
+            Message::Write(s) => {
+                write!(f, "write \"")?;
+                for c in s.chars() {
+                    match c {
+                        '"' => write!(f, "\\\"")?,
+                        '\\' => write!(f, "\\\\")?,
+                        '\n' => write!(f, "\\n")?,
+                        '\t' => write!(f, "\\t")?,
+                        _ => write!(f, "{}", c)?,
+                    }
+                }
+                write!(f, "\"")
+            }
+            Message::ChangeColor(r, g, b) => write!(f, "color {} {} {}", r, g, b),
+        }
+    }
+}
+
+/// Everything that can go wrong while parsing a command line.
+/// Every variant carries the 1-based column where the problem starts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    Empty,
+    UnknownCommand { name: String, column: usize },
+    MissingArgument { command: &'static str, expected: &'static str, column: usize },
+    UnexpectedArgument { text: String, column: usize },
+    InvalidNumber { text: String, column: usize },
+    ColorOutOfRange { value: i32, column: usize },
+    UnterminatedString { column: usize },
+    InvalidEscape { escape: char, column: usize },
+}
+
+impl ParseError {
+    /// The 1-based column of the error, if it points at a position.
+    pub fn column(&self) -> Option<usize> {
+        match self {
+            ParseError::Empty => None,
+            ParseError::UnknownCommand { column, .. }
+            | ParseError::MissingArgument { column, .. }
+            | ParseError::UnexpectedArgument { column, .. }
+            | ParseError::InvalidNumber { column, .. }
+            | ParseError::ColorOutOfRange { column, .. }
+            | ParseError::UnterminatedString { column }
+            | ParseError::InvalidEscape { column, .. } => Some(*column),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Empty => write!(f, "empty command"),
+            ParseError::UnknownCommand { name, column } => write!(
+                f,
+                "column {}: unknown command '{}' (expected quit, move, write or color)",
+                column, name
+            ),
+            ParseError::MissingArgument { command, expected, column } => {
+                write!(f, "column {}: '{}' is missing {}", column, command, expected)
+            }
+            ParseError::UnexpectedArgument { text, column } => {
+                write!(f, "column {}: unexpected argument '{}'", column, text)
+            }
+            ParseError::InvalidNumber { text, column } => {
+                write!(f, "column {}: '{}' is not a valid i32", column, text)
+            }
+            ParseError::ColorOutOfRange { value, column } => {
+                write!(f, "column {}: color component {} is outside 0..=255", column, value)
+            }
+            ParseError::UnterminatedString { column } => {
+                write!(f, "column {}: string is missing its closing quote", column)
+            }
+            ParseError::InvalidEscape { escape, column } => {
+                write!(f, "column {}: unknown escape sequence '\\{}'", column, escape)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A whitespace-separated word or a double-quoted string, with its start column.
+#[derive(Debug)]
+struct Token {
+    text: String,
+    column: usize,
+    quoted: bool,
+}
+
+fn tokenize(line: &str) -> Result<Vec<Token>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut chars = line.chars().enumerate().peekable();
+
+    while let Some(&(i, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+        let column = i + 1;
+        if c == '"' {
+            chars.next();
+            let mut text = String::new();
+            let mut closed = false;
+            while let Some((j, c)) = chars.next() {
+                match c {
+                    '"' => {
+                        closed = true;
+                        break;
+                    }
+                    '\\' => match chars.next() {
+                        Some((_, '"')) => text.push('"'),
+                        Some((_, '\\')) => text.push('\\'),
+                        Some((_, 'n')) => text.push('\n'),
+                        Some((_, 't')) => text.push('\t'),
+                        Some((_, other)) => {
+                            return Err(ParseError::InvalidEscape { escape: other, column: j + 1 });
+                        }
+                        None => break,
+                    },
+                    _ => text.push(c),
+                }
+            }
+            if !closed {
+                return Err(ParseError::UnterminatedString { column });
+            }
+            tokens.push(Token { text, column, quoted: true });
+        } else {
+            let mut text = String::new();
+            while let Some(&(_, c)) = chars.peek() {
+                if c.is_whitespace() {
+                    break;
+                }
+                text.push(c);
+                chars.next();
+            }
+            tokens.push(Token { text, column, quoted: false });
+        }
+    }
+    Ok(tokens)
+}
+
+/// Walks the argument tokens of one command.
+struct Args<'a> {
+    command: &'static str,
+    tokens: std::slice::Iter<'a, Token>,
+    end_column: usize,
+}
+
+impl Args<'_> {
+    fn next(&mut self, expected: &'static str) -> Result<&Token, ParseError> {
+        self.tokens.next().ok_or(ParseError::MissingArgument {
+            command: self.command,
+            expected,
+            column: self.end_column,
+        })
+    }
+
+    fn int(&mut self, expected: &'static str) -> Result<(i32, usize), ParseError> {
+        let token = self.next(expected)?;
+        match token.text.parse() {
+            Ok(n) if !token.quoted => Ok((n, token.column)),
+            _ => Err(ParseError::InvalidNumber { text: token.text.clone(), column: token.column }),
+        }
+    }
+
+    fn color(&mut self, expected: &'static str) -> Result<i32, ParseError> {
+        let (value, column) = self.int(expected)?;
+        if (0..=255).contains(&value) {
+            Ok(value)
+        } else {
+            Err(ParseError::ColorOutOfRange { value, column })
+        }
+    }
+
+    fn finish(mut self) -> Result<(), ParseError> {
+        match self.tokens.next() {
+            Some(t) => Err(ParseError::UnexpectedArgument { text: t.text.clone(), column: t.column }),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Parses one command line into a `Message`.
+///
+/// Commands are case-insensitive: `quit`, `move <x> <y>`,
+/// `write "<text>"` (or a single bare word) and `color <r> <g> <b>`.
+pub fn parse(line: &str) -> Result<Message, ParseError> {
+    let tokens = tokenize(line)?;
+    let (head, rest) = tokens.split_first().ok_or(ParseError::Empty)?;
+    let end_column = line.trim_end().chars().count() + 1;
+
+    let command = match head.text.to_ascii_lowercase().as_str() {
+        "quit" | "exit" if !head.quoted => "quit",
+        "move" if !head.quoted => "move",
+        "write" if !head.quoted => "write",
+        "color" | "colour" if !head.quoted => "color",
+        _ => {
+            return Err(ParseError::UnknownCommand { name: head.text.clone(), column: head.column });
+        }
+    };
+    let mut args = Args { command, tokens: rest.iter(), end_column };
+
+    let message = match command {
+        "quit" => Message::Quit,
+        "move" => {
+            let (x, _) = args.int("an x coordinate")?;
+            let (y, _) = args.int("a y coordinate")?;
+            Message::Move { x, y }
+        }
+        "write" => Message::Write(args.next("the text to write")?.text.clone()),
+        _ => {
+            let r = args.color("a red component")?;
+            let g = args.color("a green component")?;
+            let b = args.color("a blue component")?;
+            Message::ChangeColor(r, g, b)
+        }
+    };
+    args.finish()?;
+    Ok(message)
+}
+
+impl FromStr for Message {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse(s)
+    }
+}
+
+/// A piece of text left on the canvas by a `Write` message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stroke {
+    pub at: (i32, i32),
+    pub color: (i32, i32, i32),
+    pub text: String,
+}
+
+/// What happened when a message was applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    Moved { from: (i32, i32), to: (i32, i32) },
+    Wrote(Stroke),
+    ColorChanged { from: (i32, i32, i32), to: (i32, i32, i32) },
+    Stopped,
+}
+
+/// Messages are rejected once the canvas has received `Quit`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoppedError;
+
+impl fmt::Display for StoppedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the canvas has already quit")
+    }
+}
+
+impl std::error::Error for StoppedError {}
+
+/// The state machine driven by `Message`s: a cursor, a pen color and the
+/// strokes written so far. It is either running or stopped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Canvas {
+    cursor: (i32, i32),
+    color: (i32, i32, i32),
+    strokes: Vec<Stroke>,
+    running: bool,
+}
+
+impl Default for Canvas {
+    fn default() -> Self {
+        Canvas::new()
+    }
+}
+
+impl Canvas {
+    /// A running canvas with the cursor at the origin and a black pen.
+    pub fn new() -> Self {
+        Canvas { cursor: (0, 0), color: (0, 0, 0), strokes: Vec::new(), running: true }
+    }
+
+    pub fn cursor(&self) -> (i32, i32) {
+        self.cursor
+    }
+
+    pub fn color(&self) -> (i32, i32, i32) {
+        self.color
+    }
+
+    pub fn strokes(&self) -> &[Stroke] {
+        &self.strokes
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+    /// Dispatches one message with a single `match` over every variant.
+    pub fn apply(&mut self, message: Message) -> Result<Event, StoppedError> {
+        if !self.running {
+            return Err(StoppedError);
+        }
+        let event = match message {
+            Message::Quit => {
+                self.running = false;
+                Event::Stopped
+            }
+            Message::Move { x, y } => {
+                let from = std::mem::replace(&mut self.cursor, (x, y));
+                Event::Moved { from, to: self.cursor }
+            }
+            Message::Write(text) => {
+                let stroke = Stroke { at: self.cursor, color: self.color, text };
+                self.strokes.push(stroke.clone());
+                Event::Wrote(stroke)
+            }
+            Message::ChangeColor(r, g, b) => {
+                let from = std::mem::replace(&mut self.color, (r, g, b));
+                Event::ColorChanged { from, to: self.color }
+            }
+        };
+        Ok(event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_every_command() {
+        assert_eq!(parse("quit"), Ok(Message::Quit));
+        assert_eq!(parse("  EXIT  "), Ok(Message::Quit));
+        assert_eq!(parse("move -3 7"), Ok(Message::Move { x: -3, y: 7 }));
+        assert_eq!(parse("write hello"), Ok(Message::Write("hello".to_string())));
+        assert_eq!(parse(r#"Write "two words""#), Ok(Message::Write("two words".to_string())));
+        assert_eq!(parse("colour 0 128 255"), Ok(Message::ChangeColor(0, 128, 255)));
+        assert_eq!("move 1 2".parse::<Message>(), Ok(Message::Move { x: 1, y: 2 }));
+    }
+
+    #[test]
+    fn errors_point_at_the_right_column() {
+        assert_eq!(parse("   "), Err(ParseError::Empty));
+        assert_eq!(parse("jump 1 2"), Err(ParseError::UnknownCommand { name: "jump".to_string(), column: 1 }));
+        assert_eq!(parse(r#""quit""#), Err(ParseError::UnknownCommand { name: "quit".to_string(), column: 1 }));
+        // A missing argument points just past the last non-blank character
+        assert_eq!(
+            parse("  move 10   "),
+            Err(ParseError::MissingArgument { command: "move", expected: "a y coordinate", column: 10 })
+        );
+        assert_eq!(
+            parse("write"),
+            Err(ParseError::MissingArgument { command: "write", expected: "the text to write", column: 6 })
+        );
+        assert_eq!(parse("quit now"), Err(ParseError::UnexpectedArgument { text: "now".to_string(), column: 6 }));
+        assert_eq!(
+            parse("write a b"),
+            Err(ParseError::UnexpectedArgument { text: "b".to_string(), column: 9 })
+        );
+        assert_eq!(parse("move 1 x2"), Err(ParseError::InvalidNumber { text: "x2".to_string(), column: 8 }));
+        assert_eq!(
+            parse("move 3000000000 0"),
+            Err(ParseError::InvalidNumber { text: "3000000000".to_string(), column: 6 })
+        );
+        assert_eq!(parse(r#"move "1" 2"#), Err(ParseError::InvalidNumber { text: "1".to_string(), column: 6 }));
+        assert_eq!(parse("color 0 256 0"), Err(ParseError::ColorOutOfRange { value: 256, column: 9 }));
+        assert_eq!(parse("color -1 0 0"), Err(ParseError::ColorOutOfRange { value: -1, column: 7 }));
+        assert_eq!(parse(r#"write "hello"#), Err(ParseError::UnterminatedString { column: 7 }));
+        assert_eq!(parse(r#"write "a\"#), Err(ParseError::UnterminatedString { column: 7 }));
+        assert_eq!(parse(r#"write "a\qb""#), Err(ParseError::InvalidEscape { escape: 'q', column: 9 }));
+
+        let e = parse("color 0 256 0").unwrap_err();
+        assert_eq!(e.column(), Some(9));
+        assert_eq!(e.to_string(), "column 9: color component 256 is outside 0..=255");
+        assert_eq!(ParseError::Empty.column(), None);
+    }
+
+    #[test]
+    fn display_round_trips_through_parse() {
+        let messages = [
+            Message::Quit,
+            Message::Move { x: i32::MIN, y: i32::MAX },
+            Message::Write("say \"hi\" \\ then\n\ttab".to_string()),
+            Message::Write(String::new()),
+            Message::ChangeColor(255, 0, 17),
+        ];
+        for m in messages {
+            let text = m.to_string();
+            assert_eq!(parse(&text), Ok(m), "{}", text);
+        }
+        assert_eq!(Message::Write("a\"b".to_string()).to_string(), r#"write "a\"b""#);
+    }
+
+    #[test]
+    fn canvas_applies_messages_until_quit() {
+        let mut canvas = Canvas::new();
+        assert!(canvas.is_running());
+        assert_eq!(
+            canvas.apply(Message::Move { x: 2, y: 3 }),
+            Ok(Event::Moved { from: (0, 0), to: (2, 3) })
+        );
+        assert_eq!(
+            canvas.apply(Message::ChangeColor(255, 0, 0)),
+            Ok(Event::ColorChanged { from: (0, 0, 0), to: (255, 0, 0) })
+        );
+        let stroke = Stroke { at: (2, 3), color: (255, 0, 0), text: "hi".to_string() };
+        assert_eq!(canvas.apply(Message::Write("hi".to_string())), Ok(Event::Wrote(stroke.clone())));
+        assert_eq!(canvas.strokes(), [stroke]);
+        assert_eq!((canvas.cursor(), canvas.color()), ((2, 3), (255, 0, 0)));
+
+        assert_eq!(canvas.apply(Message::Quit), Ok(Event::Stopped));
+        assert!(!canvas.is_running());
+        let before = canvas.clone();
+        for m in [Message::Quit, Message::Move { x: 9, y: 9 }, Message::Write("x".to_string()), Message::ChangeColor(1, 2, 3)] {
+            assert_eq!(canvas.apply(m), Err(StoppedError));
+        }
+        assert_eq!(canvas, before);
+    }
+}
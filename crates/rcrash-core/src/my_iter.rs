@@ -0,0 +1,450 @@
+// my_iter.rs
+// Hand-written versions of the std iterator adapters used in ch04_13.
+//
+// An adapter is just a struct holding the iterator it wraps (and maybe a closure
+// or some state). Building one does no work. Each `next` pulls as many items from
+// the inner iterator as it needs to produce one item of its own, so
+//   v.iter().my_map(f).my_filter(p).next()
+// runs f and p on the first element before looking at the second.
+//
+// The adapters are reached through the `MyIterExt` extension trait, implemented
+// for every `Iterator`. The methods carry a `my_` prefix because calls like
+// `.map()` would be ambiguous with `Iterator::map` in scope.
+//
+// Each adapter also reports a `size_hint` that matches std exactly, which the
+// tests check after every step.
+
+/// Extension methods that build the adapters in this module.
+pub trait MyIterExt: Iterator + Sized {
+    fn my_map<B, F>(self, f: F) -> Map<Self, F>
+    where
+        F: FnMut(Self::Item) -> B,
+    {
+        Map { iter: self, f }
+    }
+
+    fn my_filter<P>(self, predicate: P) -> Filter<Self, P>
+    where
+        P: FnMut(&Self::Item) -> bool,
+    {
+        Filter { iter: self, predicate }
+    }
+
+    fn my_take_while<P>(self, predicate: P) -> TakeWhile<Self, P>
+    where
+        P: FnMut(&Self::Item) -> bool,
+    {
+        TakeWhile { iter: self, predicate, done: false }
+    }
+
+    fn my_zip<U: IntoIterator>(self, other: U) -> Zip<Self, U::IntoIter> {
+        Zip { a: self, b: other.into_iter() }
+    }
+
+    fn my_chain<U>(self, other: U) -> Chain<Self, U::IntoIter>
+    where
+        U: IntoIterator<Item = Self::Item>,
+    {
+        Chain { a: Some(self), b: Some(other.into_iter()) }
+    }
+
+    fn my_flatten(self) -> Flatten<Self>
+    where
+        Self::Item: IntoIterator,
+    {
+        Flatten { outer: Some(self), inner: None }
+    }
+
+    fn my_peekable(self) -> Peekable<Self> {
+        Peekable { iter: self, peeked: None }
+    }
+
+    fn my_scan<St, B, F>(self, initial: St, f: F) -> Scan<Self, St, F>
+    where
+        F: FnMut(&mut St, Self::Item) -> Option<B>,
+    {
+        Scan { iter: self, state: initial, f }
+    }
+}
+
+impl<I: Iterator> MyIterExt for I {}
+
+/// Calls `f` on each item.
+pub struct Map<I, F> {
+    iter: I,
+    f: F,
+}
+
+impl<B, I: Iterator, F: FnMut(I::Item) -> B> Iterator for Map<I, F> {
+    type Item = B;
+
+    fn next(&mut self) -> Option<B> {
+        self.iter.next().map(&mut self.f)
+    }
+
+    // One output per input, so the inner hint carries over unchanged
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+/// Skips items the predicate rejects.
+pub struct Filter<I, P> {
+    iter: I,
+    predicate: P,
+}
+
+impl<I: Iterator, P: FnMut(&I::Item) -> bool> Iterator for Filter<I, P> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        // Keep pulling until an item passes, or the inner iterator runs out
+        loop {
+            let item = self.iter.next()?;
+            if (self.predicate)(&item) {
+                return Some(item);
+            }
+        }
+    }
+
+    // Every remaining item might be rejected, or none might be
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+/// Yields items until the predicate first fails, then nothing more.
+pub struct TakeWhile<I, P> {
+    iter: I,
+    predicate: P,
+    done: bool,
+}
+
+impl<I: Iterator, P: FnMut(&I::Item) -> bool> Iterator for TakeWhile<I, P> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        if self.done {
+            return None;
+        }
+        let item = self.iter.next()?;
+        if (self.predicate)(&item) {
+            Some(item)
+        } else {
+            // The failing item is consumed and dropped
+            self.done = true;
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done { (0, Some(0)) } else { (0, self.iter.size_hint().1) }
+    }
+}
+
+/// Pairs items from two iterators, stopping when either runs out.
+pub struct Zip<A, B> {
+    a: A,
+    b: B,
+}
+
+impl<A: Iterator, B: Iterator> Iterator for Zip<A, B> {
+    type Item = (A::Item, B::Item);
+
+    fn next(&mut self) -> Option<(A::Item, B::Item)> {
+        // If `b` is empty, the item already taken from `a` is lost, as in std
+        let x = self.a.next()?;
+        let y = self.b.next()?;
+        Some((x, y))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (a_lower, a_upper) = self.a.size_hint();
+        let (b_lower, b_upper) = self.b.size_hint();
+        let upper = match (a_upper, b_upper) {
+            (Some(x), Some(y)) => Some(x.min(y)),
+            (x, None) => x,
+            (None, y) => y,
+        };
+        (a_lower.min(b_lower), upper)
+    }
+}
+
+/// All of `a`, then all of `b`. Each side becomes `None` once it is used up,
+/// so it is never polled again.
+pub struct Chain<A, B> {
+    a: Option<A>,
+    b: Option<B>,
+}
+
+impl<A: Iterator, B: Iterator<Item = A::Item>> Iterator for Chain<A, B> {
+    type Item = A::Item;
+
+    fn next(&mut self) -> Option<A::Item> {
+        if let Some(a) = &mut self.a {
+            match a.next() {
+                Some(item) => return Some(item),
+                None => self.a = None,
+            }
+        }
+        self.b.as_mut()?.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match (&self.a, &self.b) {
+            (Some(a), Some(b)) => {
+                let (a_lower, a_upper) = a.size_hint();
+                let (b_lower, b_upper) = b.size_hint();
+                let upper = match (a_upper, b_upper) {
+                    (Some(x), Some(y)) => x.checked_add(y),
+                    _ => None,
+                };
+                (a_lower.saturating_add(b_lower), upper)
+            }
+            (Some(a), None) => a.size_hint(),
+            (None, Some(b)) => b.size_hint(),
+            (None, None) => (0, Some(0)),
+        }
+    }
+}
+
+/// Yields the items of each inner iterable in turn.
+pub struct Flatten<I: Iterator>
+where
+    I::Item: IntoIterator,
+{
+    /// `None` once the outer iterator is used up.
+    outer: Option<I>,
+    /// The inner iterator currently being drained.
+    inner: Option<<I::Item as IntoIterator>::IntoIter>,
+}
+
+impl<I: Iterator> Iterator for Flatten<I>
+where
+    I::Item: IntoIterator,
+{
+    type Item = <I::Item as IntoIterator>::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(inner) = &mut self.inner {
+                match inner.next() {
+                    Some(item) => return Some(item),
+                    None => self.inner = None,
+                }
+            }
+            let next_inner = self.outer.as_mut()?.next();
+            match next_inner {
+                Some(iterable) => self.inner = Some(iterable.into_iter()),
+                None => {
+                    self.outer = None;
+                    return None;
+                }
+            }
+        }
+    }
+
+    // The current inner iterator is known, but the ones still in `outer` are not,
+    // so there is an upper bound only when `outer` is certainly empty
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.inner.as_ref().map_or((0, Some(0)), |inner| inner.size_hint());
+        let outer_empty = self.outer.as_ref().is_none_or(|outer| outer.size_hint() == (0, Some(0)));
+        (lower, if outer_empty { upper } else { None })
+    }
+}
+
+/// Lets the caller look at the next item without consuming it.
+pub struct Peekable<I: Iterator> {
+    iter: I,
+    /// `Some(None)` means the inner iterator was already found to be empty.
+    peeked: Option<Option<I::Item>>,
+}
+
+impl<I: Iterator> Peekable<I> {
+    pub fn peek(&mut self) -> Option<&I::Item> {
+        let iter = &mut self.iter;
+        self.peeked.get_or_insert_with(|| iter.next()).as_ref()
+    }
+
+    /// Consumes the next item only if it satisfies `predicate`.
+    pub fn next_if(&mut self, predicate: impl FnOnce(&I::Item) -> bool) -> Option<I::Item> {
+        match self.next() {
+            Some(item) if predicate(&item) => Some(item),
+            other => {
+                // Put it back, so the next call sees it again
+                self.peeked = Some(other);
+                None
+            }
+        }
+    }
+}
+
+impl<I: Iterator> Iterator for Peekable<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        match self.peeked.take() {
+            Some(peeked) => peeked,
+            None => self.iter.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let extra = match &self.peeked {
+            Some(None) => return (0, Some(0)),
+            Some(Some(_)) => 1,
+            None => 0,
+        };
+        let (lower, upper) = self.iter.size_hint();
+        (lower.saturating_add(extra), upper.and_then(|u| u.checked_add(extra)))
+    }
+}
+
+/// Like `fold`, but yields every intermediate result. It stops when `f`
+/// returns `None`.
+pub struct Scan<I, St, F> {
+    iter: I,
+    state: St,
+    f: F,
+}
+
+impl<B, I: Iterator, St, F> Iterator for Scan<I, St, F>
+where
+    F: FnMut(&mut St, I::Item) -> Option<B>,
+{
+    type Item = B;
+
+    fn next(&mut self) -> Option<B> {
+        let item = self.iter.next()?;
+        (self.f)(&mut self.state, item)
+    }
+
+    // `f` may stop early by returning `None`
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use std::cell::Cell;
+    use std::fmt::Debug;
+
+    const CASES: usize = 300;
+
+    fn random_vec(rng: &mut StdRng) -> Vec<i32> {
+        let len = rng.random_range(0..20);
+        (0..len).map(|_| rng.random_range(-10..10)).collect()
+    }
+
+    /// Steps both iterators to the end, comparing each item and the size hint
+    /// before every step and once more after the end.
+    fn assert_same<T: PartialEq + Debug>(
+        mut ours: impl Iterator<Item = T>,
+        mut theirs: impl Iterator<Item = T>,
+    ) {
+        loop {
+            assert_eq!(ours.size_hint(), theirs.size_hint());
+            let (a, b) = (ours.next(), theirs.next());
+            assert_eq!(a, b);
+            if a.is_none() {
+                assert_eq!(ours.size_hint(), theirs.size_hint());
+                return;
+            }
+        }
+    }
+
+    #[test]
+    fn single_iterator_adapters_match_std() {
+        let mut rng = StdRng::seed_from_u64(41);
+        for _ in 0..CASES {
+            let v = random_vec(&mut rng);
+            let k = rng.random_range(-10..10);
+            assert_same(v.iter().my_map(|x| x * k), v.iter().map(|x| x * k));
+            assert_same(v.iter().my_filter(|&&x| x > k), v.iter().filter(|&&x| x > k));
+            assert_same(v.iter().my_take_while(|&&x| x < k), v.iter().take_while(|&&x| x < k));
+            assert_same(
+                v.iter().my_scan(0, |acc, &x| {
+                    *acc += x;
+                    (*acc < k * 5).then_some(*acc)
+                }),
+                v.iter().scan(0, |acc, &x| {
+                    *acc += x;
+                    (*acc < k * 5).then_some(*acc)
+                }),
+            );
+            // Unknown upper bounds pass through too
+            let unbounded = || v.iter().copied().cycle().take_while(|_| true);
+            assert_eq!(unbounded().my_map(|x| x + k).size_hint(), unbounded().map(|x| x + k).size_hint());
+        }
+    }
+
+    #[test]
+    fn two_iterator_adapters_match_std() {
+        let mut rng = StdRng::seed_from_u64(42);
+        for _ in 0..CASES {
+            let (a, b) = (random_vec(&mut rng), random_vec(&mut rng));
+            assert_same(a.iter().my_zip(&b), a.iter().zip(&b));
+            assert_same(a.iter().my_chain(&b), a.iter().chain(&b));
+            // Filtering one side makes the bounds inexact
+            let odd = |x: &&i32| *x % 2 != 0;
+            assert_same(a.iter().filter(odd).my_zip(&b), a.iter().filter(odd).zip(&b));
+            assert_same(a.iter().filter(odd).my_chain(&b), a.iter().filter(odd).chain(&b));
+        }
+    }
+
+    #[test]
+    fn flatten_matches_std() {
+        let mut rng = StdRng::seed_from_u64(43);
+        for _ in 0..CASES {
+            let groups: Vec<Vec<i32>> = (0..rng.random_range(0..6)).map(|_| random_vec(&mut rng)).collect();
+            assert_same(groups.iter().my_flatten(), groups.iter().flatten());
+            assert_same(groups.clone().into_iter().my_flatten(), groups.clone().into_iter().flatten());
+        }
+    }
+
+    #[test]
+    fn peekable_matches_std() {
+        let mut rng = StdRng::seed_from_u64(44);
+        for _ in 0..CASES {
+            let v = random_vec(&mut rng);
+            let (mut ours, mut theirs) = (v.iter().my_peekable(), v.iter().peekable());
+            loop {
+                assert_eq!(ours.size_hint(), theirs.size_hint());
+                let k = rng.random_range(-10..10);
+                let step = match rng.random_range(0..3) {
+                    0 => (ours.peek().copied(), theirs.peek().copied()),
+                    1 => (ours.next_if(|&&x| x > k), theirs.next_if(|&&x| x > k)),
+                    _ => (ours.next(), theirs.next()),
+                };
+                assert_eq!(step.0, step.1);
+                if ours.peek().is_none() && theirs.peek().is_none() {
+                    assert_eq!(ours.size_hint(), theirs.size_hint());
+                    break;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn adapters_are_lazy() {
+        let calls = Cell::new(0);
+        let mut doubled = (1..=5).my_map(|x| {
+            calls.set(calls.get() + 1);
+            x * 2
+        });
+        // Building the adapter ran nothing
+        assert_eq!(calls.get(), 0);
+        assert_eq!(doubled.next(), Some(2));
+        assert_eq!(calls.get(), 1);
+
+        // A filter pulls only as far as the first item that passes
+        let pulled = Cell::new(0);
+        let mut big = (1..100).inspect(|_| pulled.set(pulled.get() + 1)).my_filter(|&x| x > 3);
+        assert_eq!(big.next(), Some(4));
+        assert_eq!(pulled.get(), 4);
+    }
+}
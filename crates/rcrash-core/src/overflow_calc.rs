@@ -0,0 +1,573 @@
+// overflow_calc.rs
+// 3.6 Integer overflow, every way at once
+// Evaluates integer expressions in a chosen type (i8..i128, u8..u128) under a
+// chosen overflow policy: panic, wrapping, checked, saturating or overflowing.
+// Wrapping and saturating arithmetic go through `std::num::Wrapping` and
+// `std::num::Saturating`.
+
+use crate::int_type::IntType;
+use std::fmt;
+use std::num::{Saturating, Wrapping};
+use std::str::FromStr;
+
+/// What happens when an operation overflows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Policy {
+    /// Like `a + b` in a debug build: overflow panics.
+    Panic,
+    /// `Wrapping(a) + Wrapping(b)`: keep the low bits.
+    Wrapping,
+    /// `a.checked_add(b)`: `None` on overflow.
+    Checked,
+    /// `Saturating(a) + Saturating(b)`: clamp to MIN/MAX.
+    Saturating,
+    /// `a.overflowing_add(b)`: the wrapped value plus a flag.
+    Overflowing,
+}
+
+impl Policy {
+    pub const ALL: [Policy; 5] =
+        [Policy::Panic, Policy::Wrapping, Policy::Checked, Policy::Saturating, Policy::Overflowing];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Policy::Panic => "panic",
+            Policy::Wrapping => "wrapping",
+            Policy::Checked => "checked",
+            Policy::Saturating => "saturating",
+            Policy::Overflowing => "overflowing",
+        }
+    }
+}
+
+impl FromStr for Policy {
+    type Err = CalcError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Policy::ALL
+            .into_iter()
+            .find(|p| p.name() == s)
+            .ok_or_else(|| CalcError::UnknownPolicy(s.to_string()))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Shl,
+    Shr,
+    Pow,
+}
+
+impl BinOp {
+    fn symbol(self) -> &'static str {
+        match self {
+            BinOp::Add => "+",
+            BinOp::Sub => "-",
+            BinOp::Mul => "*",
+            BinOp::Div => "/",
+            BinOp::Rem => "%",
+            BinOp::Shl => "<<",
+            BinOp::Shr => ">>",
+            BinOp::Pow => "**",
+        }
+    }
+
+    /// The message Rust panics with, e.g. "attempt to add with overflow".
+    fn panic_message(self) -> &'static str {
+        match self {
+            BinOp::Add => "attempt to add with overflow",
+            BinOp::Sub => "attempt to subtract with overflow",
+            BinOp::Mul => "attempt to multiply with overflow",
+            BinOp::Div => "attempt to divide with overflow",
+            BinOp::Rem => "attempt to calculate the remainder with overflow",
+            BinOp::Shl => "attempt to shift left with overflow",
+            BinOp::Shr => "attempt to shift right with overflow",
+            BinOp::Pow => "attempt to multiply with overflow",
+        }
+    }
+}
+
+/// Errors from parsing or evaluating an expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CalcError {
+    UnexpectedChar { ch: char, pos: usize },
+    UnexpectedEnd,
+    LiteralOutOfRange { text: String, ty: &'static str, pos: usize },
+    InvalidShiftOrExponent { text: String },
+    Panicked(String),
+    UnknownType(String),
+    UnknownPolicy(String),
+}
+
+impl fmt::Display for CalcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CalcError::UnexpectedChar { ch, pos } => {
+                write!(f, "unexpected '{}' at position {}", ch, pos)
+            }
+            CalcError::UnexpectedEnd => write!(f, "expression ends too early"),
+            CalcError::LiteralOutOfRange { text, ty, pos } => {
+                write!(f, "literal {} at position {} is out of range for {}", text, pos, ty)
+            }
+            CalcError::InvalidShiftOrExponent { text } => {
+                write!(f, "shift amounts and exponents must fit in u32, got {}", text)
+            }
+            CalcError::Panicked(msg) => write!(f, "thread 'main' panicked: {}", msg),
+            CalcError::UnknownType(s) => write!(f, "unknown integer type '{}'", s),
+            CalcError::UnknownPolicy(s) => write!(
+                f,
+                "unknown policy '{}' (expected panic, wrapping, checked, saturating or overflowing)",
+                s
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CalcError {}
+
+/// The parsed expression. Literals stay as text until the type is known.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr {
+    Literal { text: String, pos: usize },
+    Neg(Box<Expr>),
+    Binary(BinOp, Box<Expr>, Box<Expr>),
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expr::Literal { text, .. } => write!(f, "{}", text),
+            Expr::Neg(e) => write!(f, "-{}", e),
+            Expr::Binary(op, l, r) => write!(f, "({} {} {})", l, op.symbol(), r),
+        }
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn skip_spaces(&mut self) {
+        while self.chars.get(self.pos).is_some_and(|c| c.is_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    /// Consumes `token` if it comes next (after spaces).
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_spaces();
+        let end = self.pos + token.chars().count();
+        if end <= self.chars.len() && self.chars[self.pos..end].iter().copied().eq(token.chars()) {
+            self.pos = end;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn unexpected(&self) -> CalcError {
+        match self.chars.get(self.pos) {
+            Some(&ch) => CalcError::UnexpectedChar { ch, pos: self.pos },
+            None => CalcError::UnexpectedEnd,
+        }
+    }
+
+    // shift := additive (('<<' | '>>') additive)*
+    fn shift(&mut self) -> Result<Expr, CalcError> {
+        let mut left = self.additive()?;
+        loop {
+            let op = if self.eat("<<") {
+                BinOp::Shl
+            } else if self.eat(">>") {
+                BinOp::Shr
+            } else {
+                return Ok(left);
+            };
+            left = Expr::Binary(op, Box::new(left), Box::new(self.additive()?));
+        }
+    }
+
+    // additive := term (('+' | '-') term)*
+    fn additive(&mut self) -> Result<Expr, CalcError> {
+        let mut left = self.term()?;
+        loop {
+            let op = if self.eat("+") {
+                BinOp::Add
+            } else if self.eat("-") {
+                BinOp::Sub
+            } else {
+                return Ok(left);
+            };
+            left = Expr::Binary(op, Box::new(left), Box::new(self.term()?));
+        }
+    }
+
+    // term := power (('*' | '/' | '%') power)*
+    fn term(&mut self) -> Result<Expr, CalcError> {
+        let mut left = self.power()?;
+        loop {
+            self.skip_spaces();
+            // `**` is pow, not two multiplications
+            let op = if self.chars[self.pos..].starts_with(&['*', '*']) {
+                return Ok(left);
+            } else if self.eat("*") {
+                BinOp::Mul
+            } else if self.eat("/") {
+                BinOp::Div
+            } else if self.eat("%") {
+                BinOp::Rem
+            } else {
+                return Ok(left);
+            };
+            left = Expr::Binary(op, Box::new(left), Box::new(self.power()?));
+        }
+    }
+
+    // power := unary ('**' power)?      (right-associative)
+    fn power(&mut self) -> Result<Expr, CalcError> {
+        let base = self.unary()?;
+        if self.eat("**") {
+            let exponent = self.power()?;
+            return Ok(Expr::Binary(BinOp::Pow, Box::new(base), Box::new(exponent)));
+        }
+        Ok(base)
+    }
+
+    // unary := '-' unary | atom      (`-128` is one literal, as in Rust)
+    fn unary(&mut self) -> Result<Expr, CalcError> {
+        if self.eat("-") {
+            if !self.chars.get(self.pos).is_some_and(|c| c.is_ascii_digit()) {
+                return Ok(Expr::Neg(Box::new(self.unary()?)));
+            }
+            return match self.atom()? {
+                Expr::Literal { text, pos } => {
+                    Ok(Expr::Literal { text: format!("-{}", text), pos: pos - 1 })
+                }
+                other => Ok(Expr::Neg(Box::new(other))),
+            };
+        }
+        self.atom()
+    }
+
+    // atom := digits | '(' shift ')'
+    fn atom(&mut self) -> Result<Expr, CalcError> {
+        if self.eat("(") {
+            let inner = self.shift()?;
+            if !self.eat(")") {
+                return Err(self.unexpected());
+            }
+            return Ok(inner);
+        }
+        self.skip_spaces();
+        let start = self.pos;
+        while self.chars.get(self.pos).is_some_and(|c| c.is_ascii_digit() || *c == '_') {
+            self.pos += 1;
+        }
+        if start == self.pos {
+            return Err(self.unexpected());
+        }
+        let text: String = self.chars[start..self.pos].iter().collect();
+        Ok(Expr::Literal { text, pos: start })
+    }
+}
+
+/// Parses `200 * 2 + 1`, `-128 - 1`, `1 << 9`, `3 ** 5`, with parentheses.
+pub fn parse(input: &str) -> Result<Expr, CalcError> {
+    let mut parser = Parser { chars: input.chars().collect(), pos: 0 };
+    let expr = parser.shift()?;
+    parser.skip_spaces();
+    if parser.pos < parser.chars.len() {
+        return Err(parser.unexpected());
+    }
+    Ok(expr)
+}
+
+/// The value of an expression under a policy, plus whether any step overflowed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome<T> {
+    /// `None` only under the checked policy.
+    pub value: Option<T>,
+    pub overflowed: bool,
+    /// The first operation that overflowed, e.g. `200 * 2`.
+    pub first_overflow: Option<String>,
+}
+
+/// The per-type arithmetic: one method per operator, each obeying a policy.
+pub trait CalcInt: Copy + fmt::Display + FromStr {
+    const NAME: &'static str;
+
+    fn binary(policy: Policy, op: BinOp, a: Self, b: Self) -> Result<(Option<Self>, bool), CalcError>;
+
+    fn negate(policy: Policy, a: Self) -> Result<(Option<Self>, bool), CalcError>;
+}
+
+/// Shift amounts and exponents are `u32` in std.
+fn rhs_u32<T: CalcInt>(b: T) -> Result<u32, CalcError> {
+    b.to_string()
+        .parse()
+        .map_err(|_| CalcError::InvalidShiftOrExponent { text: b.to_string() })
+}
+
+macro_rules! calc_int {
+    ($($t:ident),*) => {
+        $(impl CalcInt for $t {
+            const NAME: &'static str = stringify!($t);
+
+            fn binary(policy: Policy, op: BinOp, a: $t, b: $t) -> Result<(Option<$t>, bool), CalcError> {
+                let divides = matches!(op, BinOp::Div | BinOp::Rem);
+                if divides && b == 0 && policy != Policy::Checked {
+                    let what = if op == BinOp::Div { "divide" } else { "calculate the remainder" };
+                    return Err(CalcError::Panicked(format!("attempt to {} by zero", what)));
+                }
+                let (wrapped, overflowed) = match op {
+                    BinOp::Add => a.overflowing_add(b),
+                    BinOp::Sub => a.overflowing_sub(b),
+                    BinOp::Mul => a.overflowing_mul(b),
+                    BinOp::Div if b == 0 => (0, false),
+                    BinOp::Div => a.overflowing_div(b),
+                    BinOp::Rem if b == 0 => (0, false),
+                    BinOp::Rem => a.overflowing_rem(b),
+                    BinOp::Shl => a.overflowing_shl(rhs_u32(b)?),
+                    BinOp::Shr => a.overflowing_shr(rhs_u32(b)?),
+                    BinOp::Pow => a.overflowing_pow(rhs_u32(b)?),
+                };
+                let value = match policy {
+                    Policy::Panic if overflowed => {
+                        return Err(CalcError::Panicked(op.panic_message().to_string()));
+                    }
+                    Policy::Panic | Policy::Overflowing => Some(wrapped),
+                    Policy::Checked => match op {
+                        BinOp::Add => a.checked_add(b),
+                        BinOp::Sub => a.checked_sub(b),
+                        BinOp::Mul => a.checked_mul(b),
+                        BinOp::Div => a.checked_div(b),
+                        BinOp::Rem => a.checked_rem(b),
+                        BinOp::Shl => a.checked_shl(rhs_u32(b)?),
+                        BinOp::Shr => a.checked_shr(rhs_u32(b)?),
+                        BinOp::Pow => a.checked_pow(rhs_u32(b)?),
+                    },
+                    Policy::Wrapping => Some(match op {
+                        BinOp::Add => (Wrapping(a) + Wrapping(b)).0,
+                        BinOp::Sub => (Wrapping(a) - Wrapping(b)).0,
+                        BinOp::Mul => (Wrapping(a) * Wrapping(b)).0,
+                        BinOp::Div => (Wrapping(a) / Wrapping(b)).0,
+                        BinOp::Rem => (Wrapping(a) % Wrapping(b)).0,
+                        BinOp::Shl => (Wrapping(a) << rhs_u32(b)? as usize).0,
+                        BinOp::Shr => (Wrapping(a) >> rhs_u32(b)? as usize).0,
+                        BinOp::Pow => a.wrapping_pow(rhs_u32(b)?),
+                    }),
+                    Policy::Saturating => Some(match op {
+                        BinOp::Add => (Saturating(a) + Saturating(b)).0,
+                        BinOp::Sub => (Saturating(a) - Saturating(b)).0,
+                        BinOp::Mul => (Saturating(a) * Saturating(b)).0,
+                        BinOp::Div => (Saturating(a) / Saturating(b)).0,
+                        // `%` cannot overflow except MIN % -1, whose true result 0 is in range
+                        BinOp::Rem => a.wrapping_rem(b),
+                        // std has no saturating shift: shifting every bit out leaves
+                        // 0, or -1 for a negative number shifted right
+                        BinOp::Shl => a.checked_shl(rhs_u32(b)?).unwrap_or(0),
+                        BinOp::Shr => a.checked_shr(rhs_u32(b)?).unwrap_or(a >> ($t::BITS - 1) >> 1),
+                        BinOp::Pow => a.saturating_pow(rhs_u32(b)?),
+                    }),
+                };
+                Ok((value, overflowed))
+            }
+
+            fn negate(policy: Policy, a: $t) -> Result<(Option<$t>, bool), CalcError> {
+                let (wrapped, overflowed) = a.overflowing_neg();
+                let value = match policy {
+                    Policy::Panic if overflowed => {
+                        return Err(CalcError::Panicked("attempt to negate with overflow".to_string()));
+                    }
+                    Policy::Panic | Policy::Overflowing => Some(wrapped),
+                    Policy::Checked => a.checked_neg(),
+                    Policy::Wrapping => Some((-Wrapping(a)).0),
+                    // 0 - a also works for unsigned types, which have no `Neg`
+                    Policy::Saturating => Some((Saturating(0) - Saturating(a)).0),
+                };
+                Ok((value, overflowed))
+            }
+        })*
+    };
+}
+
+calc_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// Evaluates a parsed expression in type `T`.
+pub fn eval<T: CalcInt>(expr: &Expr, policy: Policy) -> Result<Outcome<T>, CalcError> {
+    match expr {
+        Expr::Literal { text, pos } => {
+            let value = text.replace('_', "").parse::<T>().map_err(|_| {
+                CalcError::LiteralOutOfRange { text: text.clone(), ty: T::NAME, pos: *pos }
+            })?;
+            Ok(Outcome { value: Some(value), overflowed: false, first_overflow: None })
+        }
+        Expr::Neg(inner) => {
+            let inner = eval::<T>(inner, policy)?;
+            let Some(a) = inner.value else { return Ok(inner) };
+            let (value, overflowed) = T::negate(policy, a)?;
+            let here = overflowed.then(|| format!("-({})", a));
+            Ok(Outcome {
+                value,
+                overflowed: inner.overflowed || overflowed,
+                first_overflow: inner.first_overflow.or(here),
+            })
+        }
+        Expr::Binary(op, left, right) => {
+            let left = eval::<T>(left, policy)?;
+            let right = eval::<T>(right, policy)?;
+            let overflowed = left.overflowed || right.overflowed;
+            let first_overflow = left.first_overflow.or(right.first_overflow);
+            // A checked `None` propagates like `?` on an Option
+            let (Some(a), Some(b)) = (left.value, right.value) else {
+                return Ok(Outcome { value: None, overflowed, first_overflow });
+            };
+            let (value, this_overflowed) = T::binary(policy, *op, a, b)?;
+            let here = this_overflowed.then(|| format!("{} {} {}", a, op.symbol(), b));
+            Ok(Outcome {
+                value,
+                overflowed: overflowed || this_overflowed,
+                first_overflow: first_overflow.or(here),
+            })
+        }
+    }
+}
+
+/// An outcome with the value already rendered the way the policy's API returns it:
+/// `44`, `Some(44)`, `None` or `(44, true)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub ty: IntType,
+    pub policy: Policy,
+    pub result: String,
+    pub overflowed: bool,
+    pub first_overflow: Option<String>,
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:<11} {:<24} overflow: {}", self.policy.name(), self.result, self.overflowed)?;
+        if let Some(op) = &self.first_overflow {
+            write!(f, " (first at `{}`)", op)?;
+        }
+        Ok(())
+    }
+}
+
+fn report<T: CalcInt>(ty: IntType, policy: Policy, expr: &Expr) -> Result<Report, CalcError> {
+    let outcome = eval::<T>(expr, policy)?;
+    let result = match (policy, outcome.value) {
+        (Policy::Checked, Some(v)) => format!("Some({})", v),
+        (_, None) => "None".to_string(),
+        (Policy::Overflowing, Some(v)) => format!("({}, {})", v, outcome.overflowed),
+        (_, Some(v)) => v.to_string(),
+    };
+    Ok(Report {
+        ty,
+        policy,
+        result,
+        overflowed: outcome.overflowed,
+        first_overflow: outcome.first_overflow,
+    })
+}
+
+/// Evaluates `expr` as type `ty` under `policy`.
+pub fn evaluate(ty: IntType, policy: Policy, expr: &Expr) -> Result<Report, CalcError> {
+    match ty {
+        IntType::I8 => report::<i8>(ty, policy, expr),
+        IntType::I16 => report::<i16>(ty, policy, expr),
+        IntType::I32 => report::<i32>(ty, policy, expr),
+        IntType::I64 => report::<i64>(ty, policy, expr),
+        IntType::I128 => report::<i128>(ty, policy, expr),
+        IntType::Isize => report::<isize>(ty, policy, expr),
+        IntType::U8 => report::<u8>(ty, policy, expr),
+        IntType::U16 => report::<u16>(ty, policy, expr),
+        IntType::U32 => report::<u32>(ty, policy, expr),
+        IntType::U64 => report::<u64>(ty, policy, expr),
+        IntType::U128 => report::<u128>(ty, policy, expr),
+        IntType::Usize => report::<usize>(ty, policy, expr),
+    }
+}
+
+/// Parses an integer type name such as `u8`.
+pub fn parse_type(s: &str) -> Result<IntType, CalcError> {
+    IntType::from_suffix(s).ok_or_else(|| CalcError::UnknownType(s.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(ty: &str, policy: Policy, input: &str) -> Result<String, CalcError> {
+        let expr = parse(input)?;
+        evaluate(parse_type(ty)?, policy, &expr).map(|r| r.result)
+    }
+
+    #[test]
+    fn each_policy_on_u8_overflow() {
+        let cases = [
+            (Policy::Wrapping, "144"),
+            (Policy::Checked, "None"),
+            (Policy::Saturating, "255"),
+            (Policy::Overflowing, "(144, true)"),
+        ];
+        for (policy, expected) in cases {
+            assert_eq!(run("u8", policy, "200 * 2").unwrap(), expected, "{:?}", policy);
+        }
+        assert_eq!(
+            run("u8", Policy::Panic, "200 * 2"),
+            Err(CalcError::Panicked("attempt to multiply with overflow".into()))
+        );
+        assert_eq!(run("u8", Policy::Checked, "200 + 55").unwrap(), "Some(255)");
+    }
+
+    #[test]
+    fn precedence_and_negative_literals() {
+        assert_eq!(run("i32", Policy::Panic, "1 + 2 * 3 ** 2").unwrap(), "19");
+        assert_eq!(run("i32", Policy::Panic, "2 ** 3 ** 2").unwrap(), "512");
+        assert_eq!(run("i32", Policy::Panic, "1 << 2 + 1").unwrap(), "8");
+        assert_eq!(run("i8", Policy::Panic, "-128").unwrap(), "-128");
+        assert_eq!(run("i8", Policy::Wrapping, "-(-128)").unwrap(), "-128");
+        assert_eq!(run("i8", Policy::Saturating, "-(-128)").unwrap(), "127");
+        assert_eq!(run("i8", Policy::Saturating, "-128 / -1").unwrap(), "127");
+        assert_eq!(run("i8", Policy::Wrapping, "-128 / -1").unwrap(), "-128");
+        assert!(matches!(run("u8", Policy::Panic, "256"), Err(CalcError::LiteralOutOfRange { .. })));
+    }
+
+    #[test]
+    fn shifts_pow_and_division_by_zero() {
+        assert_eq!(run("u8", Policy::Overflowing, "1 << 9").unwrap(), "(2, true)");
+        assert_eq!(run("u8", Policy::Checked, "1 << 9").unwrap(), "None");
+        assert_eq!(run("u8", Policy::Saturating, "1 << 9").unwrap(), "0");
+        assert_eq!(run("i16", Policy::Saturating, "2 ** 20").unwrap(), "32767");
+        assert_eq!(run("u128", Policy::Wrapping, "2 ** 128").unwrap(), "0");
+        assert_eq!(run("u8", Policy::Checked, "1 / 0").unwrap(), "None");
+        assert_eq!(
+            run("u8", Policy::Wrapping, "1 / 0"),
+            Err(CalcError::Panicked("attempt to divide by zero".into()))
+        );
+    }
+
+    #[test]
+    fn overflow_flag_and_location_survive_later_steps() {
+        let expr = parse("255 + 1 - 1").unwrap();
+        let r = evaluate(IntType::U8, Policy::Wrapping, &expr).unwrap();
+        assert_eq!(r.result, "255");
+        assert!(r.overflowed);
+        assert_eq!(r.first_overflow.as_deref(), Some("255 + 1"));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(parse("1 +"), Err(CalcError::UnexpectedEnd));
+        assert_eq!(parse("1 $ 2"), Err(CalcError::UnexpectedChar { ch: '$', pos: 2 }));
+        assert_eq!(parse("(1"), Err(CalcError::UnexpectedEnd));
+    }
+}
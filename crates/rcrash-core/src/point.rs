@@ -0,0 +1,203 @@
+// point.rs
+// 6.3 / 6.4 Generic structs, grown into a 2D vector type
+// `Point<T>` from ch06_04_generic_struct_impl.rs with the std operator traits
+// implemented. Each impl asks only for the operations it uses, so integer
+// points get `+ - *`, `dot` and `cross`, and only float points (through
+// `crate::float::Float`) get `distance`, `length` and `lerp`.
+
+use crate::float::Float;
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub};
+
+/// A point, or a vector from the origin, in 2D.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point<T> {
+    pub fn new(x: T, y: T) -> Self {
+        Point { x, y }
+    }
+
+    /// Consumes the point and returns a tuple.
+    pub fn into_tuple(self) -> (T, T) {
+        (self.x, self.y)
+    }
+}
+
+impl<T: Copy + Add<Output = T> + Mul<Output = T>> Point<T> {
+    /// The dot product `x1 * x2 + y1 * y2`.
+    ///
+    /// ```
+    /// use rcrash_core::point::Point;
+    ///
+    /// assert_eq!(Point::new(1, 2).dot(Point::new(3, 4)), 11);
+    /// assert_eq!(Point::new(1, 0).dot(Point::new(0, 5)), 0); // perpendicular
+    /// ```
+    pub fn dot(self, other: Self) -> T {
+        self.x * other.x + self.y * other.y
+    }
+}
+
+impl<T: Copy + Sub<Output = T> + Mul<Output = T>> Point<T> {
+    /// The z component of the 3D cross product, `x1 * y2 - y1 * x2`.
+    /// Positive when `other` is counter-clockwise from `self`, negative when
+    /// clockwise, zero when they are parallel.
+    ///
+    /// ```
+    /// use rcrash_core::point::Point;
+    ///
+    /// assert_eq!(Point::new(1, 0).cross(Point::new(0, 1)), 1);
+    /// assert_eq!(Point::new(0, 1).cross(Point::new(1, 0)), -1);
+    /// assert_eq!(Point::new(2, 4).cross(Point::new(1, 2)), 0);
+    /// ```
+    pub fn cross(self, other: Self) -> T {
+        self.x * other.y - self.y * other.x
+    }
+}
+
+impl<T: Float> Point<T> {
+    /// The distance from the origin.
+    pub fn length(self) -> T {
+        self.dot(self).sqrt()
+    }
+
+    /// The distance between two points.
+    ///
+    /// ```
+    /// use rcrash_core::point::Point;
+    ///
+    /// assert_eq!(Point::new(0.0_f32, 0.0).distance(Point::new(3.0, 4.0)), 5.0);
+    /// assert_eq!(Point::new(1.0_f64, 1.0).distance(Point::new(4.0, 5.0)), 5.0);
+    /// ```
+    ///
+    /// Integer points have no `distance`, because `i32` does not implement `Float`:
+    ///
+    /// ```compile_fail
+    /// use rcrash_core::point::Point;
+    ///
+    /// let d = Point::new(0, 0).distance(Point::new(3, 4)); // the trait `Float` is not implemented for `{integer}`
+    /// ```
+    pub fn distance(self, other: Self) -> T {
+        (other - self).length()
+    }
+
+    /// The point a fraction `t` of the way from `self` to `other`: `self` at
+    /// `t = 0`, `other` at `t = 1`. Values outside `0..=1` extrapolate.
+    ///
+    /// ```
+    /// use rcrash_core::point::Point;
+    ///
+    /// let (a, b) = (Point::new(0.0, 10.0), Point::new(4.0, 20.0));
+    /// assert_eq!(a.lerp(b, 0.25), Point::new(1.0, 12.5));
+    /// ```
+    pub fn lerp(self, other: Self, t: T) -> Self {
+        self + (other - self) * t
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Point<T>;
+
+    fn add(self, other: Self) -> Self::Output {
+        Point { x: self.x + other.x, y: self.y + other.y }
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Point<T>;
+
+    fn sub(self, other: Self) -> Self::Output {
+        Point { x: self.x - other.x, y: self.y - other.y }
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point<T> {
+    type Output = Point<T>;
+
+    fn neg(self) -> Self::Output {
+        Point { x: -self.x, y: -self.y }
+    }
+}
+
+/// Scalar multiplication: `point * k`. The scalar has the point's own type,
+/// so `Point<i32> * 2` works and `Point<i32> * 2.5` does not compile.
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point<T> {
+    type Output = Point<T>;
+
+    fn mul(self, k: T) -> Self::Output {
+        Point { x: self.x * k, y: self.y * k }
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point<T> {
+    fn add_assign(&mut self, other: Self) {
+        self.x += other.x;
+        self.y += other.y;
+    }
+}
+
+/// `(x, y)`. Format options apply to each coordinate, so `{:.2}` rounds both.
+impl<T: fmt::Display> fmt::Display for Point<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "(")?;
+        self.x.fmt(f)?;
+        write!(f, ", ")?;
+        self.y.fmt(f)?;
+        write!(f, ")")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integer_points() {
+        let (a, b) = (Point::new(3, -4), Point::new(1, 2));
+        assert_eq!(a + b, Point::new(4, -2));
+        assert_eq!(a - b, Point::new(2, -6));
+        assert_eq!(-a, Point::new(-3, 4));
+        assert_eq!(a * 3, Point::new(9, -12));
+        assert_eq!(a.dot(b), -5);
+        assert_eq!(a.cross(b), 10);
+        assert_eq!(b.cross(a), -10);
+
+        let mut c = Point::default();
+        for p in [a, b, b] {
+            c += p;
+        }
+        assert_eq!(c, Point::new(5, 0));
+        assert_eq!(c.into_tuple(), (5, 0));
+
+        // Unsigned points have no `Neg`, but everything else still applies
+        let u = Point::new(2u8, 3) * 4;
+        assert_eq!(u + Point::new(1, 1), Point::new(9, 13));
+    }
+
+    #[test]
+    fn float_points() {
+        let a = Point::new(1.0_f64, 2.0);
+        let b = Point::new(4.0_f64, 6.0);
+        assert_eq!(a.distance(b), 5.0);
+        assert_eq!(b.distance(a), 5.0);
+        assert_eq!(a.lerp(b, 0.0), a);
+        assert_eq!(a.lerp(b, 1.0), b);
+        assert_eq!(a.lerp(b, 0.5), Point::new(2.5, 4.0));
+        assert_eq!(a.lerp(b, 2.0), Point::new(7.0, 10.0));
+
+        let c = Point::new(0.5_f32, -1.5);
+        assert_eq!(c * 2.0, Point::new(1.0, -3.0));
+        assert_eq!((c - c).length(), 0.0);
+        assert!((Point::new(1.0_f32, 1.0).length() - std::f32::consts::SQRT_2).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn display_applies_format_options_to_each_coordinate() {
+        assert_eq!(Point::new(3, -4).to_string(), "(3, -4)");
+        assert_eq!(format!("{:.2}", Point::new(1.0 / 3.0, 2.5)), "(0.33, 2.50)");
+        assert_eq!(format!("{:>3}", Point::new(1, 22)), "(  1,  22)");
+    }
+}
@@ -0,0 +1,234 @@
+// rectangle.rs
+// 5.4 Struct visibility: private fields that actually protect something
+// `Rectangle` keeps its sides private, so the only ways to get one are the
+// builder and the checked setters, and both reject sides that are not
+// positive and finite. The builder is a typestate: its type records which
+// required sides have been given, and `build` only exists once both are set.
+
+use std::fmt;
+
+/// A side that breaks the rectangle's invariant.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RectError {
+    /// Zero or negative.
+    NotPositive { side: &'static str, value: f64 },
+    /// NaN or infinite.
+    NotFinite { side: &'static str, value: f64 },
+}
+
+impl fmt::Display for RectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RectError::NotPositive { side, value } => write!(f, "{} must be positive, got {}", side, value),
+            RectError::NotFinite { side, value } => write!(f, "{} must be a finite number, got {}", side, value),
+        }
+    }
+}
+
+impl std::error::Error for RectError {}
+
+fn check(side: &'static str, value: f64) -> Result<f64, RectError> {
+    if !value.is_finite() {
+        Err(RectError::NotFinite { side, value })
+    } else if value <= 0.0 {
+        Err(RectError::NotPositive { side, value })
+    } else {
+        Ok(value)
+    }
+}
+
+/// A rectangle whose width and height are always positive and finite.
+///
+/// The fields are private, so a struct literal does not compile outside this module:
+///
+/// ```compile_fail
+/// use rcrash_core::rectangle::Rectangle;
+///
+/// let r = Rectangle { width: -1.0, height: 2.0, origin: (0.0, 0.0) }; // fields are private
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rectangle {
+    width: f64,
+    height: f64,
+    origin: (f64, f64),
+}
+
+/// Builder state: a required side has not been given yet.
+#[derive(Debug, Clone, Copy)]
+pub struct Missing;
+
+/// Builder state: the width has been given and checked.
+#[derive(Debug, Clone, Copy)]
+pub struct Width(f64);
+
+/// Builder state: the height has been given and checked.
+#[derive(Debug, Clone, Copy)]
+pub struct Height(f64);
+
+/// Builds a [`Rectangle`]. `W` and `H` are [`Missing`] until the side is set.
+///
+/// Forgetting a side is a compile error, because `build` is only defined for
+/// `RectangleBuilder<Width, Height>`:
+///
+/// ```compile_fail
+/// use rcrash_core::rectangle::Rectangle;
+///
+/// let r = Rectangle::builder().width(3.0).unwrap().build(); // no `build` on RectangleBuilder<Width, Missing>
+/// ```
+///
+/// So is setting a side twice, because `width` is only defined while `W` is `Missing`:
+///
+/// ```compile_fail
+/// use rcrash_core::rectangle::Rectangle;
+///
+/// let b = Rectangle::builder().width(3.0).unwrap().width(4.0); // no `width` on RectangleBuilder<Width, Missing>
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct RectangleBuilder<W, H> {
+    width: W,
+    height: H,
+    origin: (f64, f64),
+}
+
+impl Rectangle {
+    /// Starts a builder with no sides set and the origin at `(0, 0)`.
+    pub fn builder() -> RectangleBuilder<Missing, Missing> {
+        RectangleBuilder { width: Missing, height: Missing, origin: (0.0, 0.0) }
+    }
+
+    pub fn width(&self) -> f64 {
+        self.width
+    }
+
+    pub fn height(&self) -> f64 {
+        self.height
+    }
+
+    /// The bottom-left corner.
+    pub fn origin(&self) -> (f64, f64) {
+        self.origin
+    }
+
+    pub fn area(&self) -> f64 {
+        self.width * self.height
+    }
+
+    pub fn perimeter(&self) -> f64 {
+        2.0 * (self.width + self.height)
+    }
+
+    pub fn is_square(&self) -> bool {
+        self.width == self.height
+    }
+
+    /// Changes the width. On error the rectangle is left unchanged.
+    pub fn set_width(&mut self, width: f64) -> Result<(), RectError> {
+        self.width = check("width", width)?;
+        Ok(())
+    }
+
+    /// Changes the height. On error the rectangle is left unchanged.
+    pub fn set_height(&mut self, height: f64) -> Result<(), RectError> {
+        self.height = check("height", height)?;
+        Ok(())
+    }
+
+    /// Moves the rectangle. Any origin is valid, so this cannot fail.
+    pub fn move_to(&mut self, x: f64, y: f64) {
+        self.origin = (x, y);
+    }
+
+    /// Multiplies both sides by `factor`. Both new sides are checked before
+    /// either is stored, so a failed scale changes nothing.
+    pub fn scale(&mut self, factor: f64) -> Result<(), RectError> {
+        let width = check("width", self.width * factor)?;
+        let height = check("height", self.height * factor)?;
+        self.width = width;
+        self.height = height;
+        Ok(())
+    }
+}
+
+impl fmt::Display for Rectangle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} x {} at ({}, {})", self.width, self.height, self.origin.0, self.origin.1)
+    }
+}
+
+impl<H> RectangleBuilder<Missing, H> {
+    /// Sets the width, moving the builder to the `Width` state.
+    pub fn width(self, width: f64) -> Result<RectangleBuilder<Width, H>, RectError> {
+        let width = Width(check("width", width)?);
+        Ok(RectangleBuilder { width, height: self.height, origin: self.origin })
+    }
+}
+
+impl<W> RectangleBuilder<W, Missing> {
+    /// Sets the height, moving the builder to the `Height` state.
+    pub fn height(self, height: f64) -> Result<RectangleBuilder<W, Height>, RectError> {
+        let height = Height(check("height", height)?);
+        Ok(RectangleBuilder { width: self.width, height, origin: self.origin })
+    }
+}
+
+impl<W, H> RectangleBuilder<W, H> {
+    /// Sets the origin. Optional, and allowed in any state.
+    pub fn origin(mut self, x: f64, y: f64) -> Self {
+        self.origin = (x, y);
+        self
+    }
+}
+
+impl RectangleBuilder<Width, Height> {
+    /// Both sides were checked when they were set, so this cannot fail.
+    pub fn build(self) -> Rectangle {
+        Rectangle { width: self.width.0, height: self.height.0, origin: self.origin }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sides_can_be_set_in_either_order() {
+        let a = Rectangle::builder().width(3.0).unwrap().height(4.0).unwrap().build();
+        let b = Rectangle::builder().origin(1.0, 2.0).height(4.0).unwrap().width(3.0).unwrap().build();
+        assert_eq!((a.width(), a.height(), a.origin()), (3.0, 4.0, (0.0, 0.0)));
+        assert_eq!((b.width(), b.height(), b.origin()), (3.0, 4.0, (1.0, 2.0)));
+        assert_eq!(a.area(), 12.0);
+        assert_eq!(a.perimeter(), 14.0);
+        assert_eq!(b.to_string(), "3 x 4 at (1, 2)");
+    }
+
+    #[test]
+    fn builder_rejects_bad_sides() {
+        for bad in [0.0, -1.0, -0.0] {
+            assert_eq!(Rectangle::builder().width(bad).unwrap_err(), RectError::NotPositive { side: "width", value: bad });
+        }
+        assert!(matches!(Rectangle::builder().height(f64::NAN), Err(RectError::NotFinite { side: "height", .. })));
+        assert!(matches!(Rectangle::builder().height(f64::INFINITY), Err(RectError::NotFinite { side: "height", .. })));
+        assert_eq!(
+            Rectangle::builder().width(-2.0).unwrap_err().to_string(),
+            "width must be positive, got -2"
+        );
+    }
+
+    #[test]
+    fn failed_setters_leave_the_rectangle_unchanged() {
+        let mut r = Rectangle::builder().width(2.0).unwrap().height(5.0).unwrap().build();
+        let before = r;
+        assert!(r.set_width(-3.0).is_err());
+        assert!(r.set_height(f64::NAN).is_err());
+        assert!(r.scale(0.0).is_err());
+        // The width would still be fine; the height overflows, and neither changes.
+        assert!(r.scale(f64::MAX / 4.0).is_err());
+        assert_eq!(r, before);
+
+        r.set_width(5.0).unwrap();
+        assert!(r.is_square());
+        r.scale(0.5).unwrap();
+        r.move_to(-1.0, -1.0);
+        assert_eq!(r.to_string(), "2.5 x 2.5 at (-1, -1)");
+    }
+}
@@ -0,0 +1,245 @@
+// registry.rs
+// 6.5 Traits: from generics to trait objects
+// `Describable` from ch06_05_traits.rs, extended with default methods and
+// supertraits, and a `Registry` that holds values of different types in one
+// `Vec<Box<dyn Describable>>`. A concrete type can be recovered from a trait
+// object through `Any`.
+
+use std::any::{type_name, Any};
+use std::fmt::Debug;
+
+/// A type that can describe itself.
+///
+/// `Debug` is a supertrait, so every trait object can be printed with `{:?}`.
+/// `Any` is one too, so a `&dyn Describable` can be turned back into the
+/// concrete type with `downcast_ref`, defined on `dyn Describable` below.
+/// `Any` requires `'static`, so implementors cannot hold borrowed data.
+pub trait Describable: Debug + Any {
+    /// A one-line description. The only method without a default.
+    fn describe(&self) -> String;
+
+    /// A short label. Defaults to the type's name without its module path.
+    fn short_name(&self) -> String {
+        let full = type_name::<Self>();
+        full.rsplit("::").next().unwrap_or(full).to_string()
+    }
+
+    /// A group used by [`Registry::by_category`]. Defaults to `"other"`.
+    fn category(&self) -> &'static str {
+        "other"
+    }
+
+    /// How many people this entry stands for. Defaults to 1.
+    fn headcount(&self) -> usize {
+        1
+    }
+}
+
+impl dyn Describable {
+    /// The concrete value, if this trait object holds a `T`.
+    ///
+    /// `&dyn Describable` coerces to `&dyn Any` because `Any` is a supertrait
+    /// (trait upcasting), and `Any::downcast_ref` compares type ids.
+    ///
+    /// ```
+    /// use rcrash_core::registry::{Company, Describable, Person};
+    ///
+    /// let item: Box<dyn Describable> = Box::new(Person::new("Alice", 30));
+    /// assert_eq!(item.downcast_ref::<Person>().map(|p| p.age), Some(30));
+    /// assert!(item.downcast_ref::<Company>().is_none());
+    /// ```
+    pub fn downcast_ref<T: Describable>(&self) -> Option<&T> {
+        (self as &dyn Any).downcast_ref::<T>()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Person {
+    pub name: String,
+    pub age: u32,
+}
+
+impl Person {
+    pub fn new(name: &str, age: u32) -> Self {
+        Person { name: name.to_string(), age }
+    }
+}
+
+impl Describable for Person {
+    fn describe(&self) -> String {
+        format!("Person: {} ({} years old)", self.name, self.age)
+    }
+
+    fn short_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn category(&self) -> &'static str {
+        "people"
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Company {
+    pub name: String,
+    pub employee_count: usize,
+}
+
+impl Company {
+    pub fn new(name: &str, employee_count: usize) -> Self {
+        Company { name: name.to_string(), employee_count }
+    }
+}
+
+impl Describable for Company {
+    fn describe(&self) -> String {
+        format!("Company: {} ({} employees)", self.name, self.employee_count)
+    }
+
+    fn category(&self) -> &'static str {
+        "organisations"
+    }
+
+    fn headcount(&self) -> usize {
+        self.employee_count
+    }
+}
+
+/// Implements only `describe`; everything else comes from the defaults.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Book {
+    pub title: String,
+    pub author: String,
+}
+
+impl Book {
+    pub fn new(title: &str, author: &str) -> Self {
+        Book { title: title.to_string(), author: author.to_string() }
+    }
+}
+
+impl Describable for Book {
+    fn describe(&self) -> String {
+        format!("Book: {} by {}", self.title, self.author)
+    }
+}
+
+/// Values of any `Describable` type, kept in the order they were added.
+///
+/// ```
+/// use rcrash_core::registry::{Book, Company, Person, Registry};
+///
+/// let mut registry = Registry::new();
+/// registry.register(Person::new("Alice", 30));
+/// registry.register(Company::new("Acme", 100));
+/// registry.register(Book::new("Dune", "Frank Herbert"));
+///
+/// assert_eq!(registry.categories(), ["organisations", "other", "people"]);
+/// assert_eq!(registry.by_category("people").map(|d| d.short_name()).collect::<Vec<_>>(), ["Alice"]);
+/// assert_eq!(registry.of_type::<Company>().next().unwrap().employee_count, 100);
+/// ```
+#[derive(Debug, Default)]
+pub struct Registry {
+    items: Vec<Box<dyn Describable>>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Registry::default()
+    }
+
+    /// Boxes `item` and adds it. Generic, so the caller passes a plain value.
+    pub fn register(&mut self, item: impl Describable) {
+        self.items.push(Box::new(item));
+    }
+
+    /// Adds an item that is already a trait object.
+    pub fn register_boxed(&mut self, item: Box<dyn Describable>) {
+        self.items.push(item);
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &dyn Describable> {
+        self.items.iter().map(|item| item.as_ref())
+    }
+
+    /// The items whose `category()` is `category`.
+    pub fn by_category<'a>(&'a self, category: &'a str) -> impl Iterator<Item = &'a dyn Describable> {
+        self.iter().filter(move |item| item.category() == category)
+    }
+
+    /// Every category in use, sorted, each once.
+    pub fn categories(&self) -> Vec<&'static str> {
+        let mut categories: Vec<_> = self.iter().map(|item| item.category()).collect();
+        categories.sort_unstable();
+        categories.dedup();
+        categories
+    }
+
+    /// The items that are a `T`, as `&T`.
+    pub fn of_type<T: Describable>(&self) -> impl Iterator<Item = &T> {
+        self.iter().filter_map(|item| item.downcast_ref::<T>())
+    }
+
+    /// The sum of every item's `headcount()`.
+    pub fn headcount(&self) -> usize {
+        self.iter().map(|item| item.headcount()).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Registry {
+        let mut registry = Registry::new();
+        registry.register(Person::new("Alice", 30));
+        registry.register(Company::new("Acme Corp", 100));
+        registry.register(Book::new("Dune", "Frank Herbert"));
+        registry.register_boxed(Box::new(Person::new("Bob", 25)));
+        registry
+    }
+
+    #[test]
+    fn default_and_overridden_methods() {
+        let registry = sample();
+        let rows: Vec<_> = registry.iter().map(|d| (d.short_name(), d.category(), d.headcount())).collect();
+        assert_eq!(
+            rows,
+            [
+                ("Alice".to_string(), "people", 1),
+                ("Company".to_string(), "organisations", 100),
+                ("Book".to_string(), "other", 1),
+                ("Bob".to_string(), "people", 1),
+            ]
+        );
+        assert_eq!(registry.headcount(), 103);
+        // Debug comes through the supertrait, even behind `dyn`
+        let first = registry.iter().next().unwrap();
+        assert_eq!(format!("{:?}", first), r#"Person { name: "Alice", age: 30 }"#);
+    }
+
+    #[test]
+    fn filters_by_category_and_type() {
+        let registry = sample();
+        assert_eq!(registry.len(), 4);
+        assert!(!registry.is_empty());
+        let people: Vec<_> = registry.by_category("people").map(|d| d.describe()).collect();
+        assert_eq!(people, ["Person: Alice (30 years old)", "Person: Bob (25 years old)"]);
+        assert_eq!(registry.by_category("nothing").count(), 0);
+        assert_eq!(registry.categories(), ["organisations", "other", "people"]);
+
+        let ages: Vec<u32> = registry.of_type::<Person>().map(|p| p.age).collect();
+        assert_eq!(ages, [30, 25]);
+        let books: Vec<&Book> = registry.of_type::<Book>().collect();
+        assert_eq!(books, [&Book::new("Dune", "Frank Herbert")]);
+        assert!(Registry::new().of_type::<Company>().next().is_none());
+    }
+}
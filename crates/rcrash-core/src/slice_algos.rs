@@ -0,0 +1,247 @@
+// slice_algos.rs
+// 3.9 Slices: classic algorithms written by hand
+// Each function works on `&[T]` or `&mut [T]` and has a std equivalent that
+// the tests compare it against.
+// `reverse`, `rotate_left` / `rotate_right` and `partition` write to two parts
+// of one slice at once, so they split it with `split_at_mut`, which hands out
+// two non-overlapping `&mut` borrows the compiler can check.
+// `binary_search`, `sliding_window_max` and the word functions only read, and
+// any number of shared borrows may overlap, so they have no use for
+// `split_at_mut`. `binary_search` and `split_first_word` use `split_at`, its
+// shared counterpart; `sliding_window_max` indexes the slice directly.
+
+use std::collections::VecDeque;
+
+/// Swaps `items[a]` and `items[b]` (with `a < b`) through two `&mut` halves.
+///
+/// `let x = &mut items[a]; let y = &mut items[b];` is rejected because both
+/// borrow all of `items`; `split_at_mut(b)` proves they do not overlap.
+fn swap_split<T>(items: &mut [T], a: usize, b: usize) {
+    debug_assert!(a < b);
+    let (left, right) = items.split_at_mut(b);
+    std::mem::swap(&mut left[a], &mut right[0]);
+}
+
+/// Like `slice::binary_search`: `Ok(index)` of a matching element, or
+/// `Err(index)` where the target could be inserted to keep the slice sorted.
+pub fn binary_search<T: Ord>(items: &[T], target: &T) -> Result<usize, usize> {
+    let mut base = 0;
+    let mut rest = items;
+    while !rest.is_empty() {
+        // Look at the middle element; keep only the half that can contain target
+        let (left, right) = rest.split_at(rest.len() / 2);
+        let (middle, after) = right.split_first().expect("right half is never empty");
+        match middle.cmp(target) {
+            std::cmp::Ordering::Equal => return Ok(base + left.len()),
+            std::cmp::Ordering::Less => {
+                base += left.len() + 1;
+                rest = after;
+            }
+            std::cmp::Ordering::Greater => rest = left,
+        }
+    }
+    Err(base)
+}
+
+/// Like `slice::reverse`: swaps the two ends and walks inwards.
+pub fn reverse<T>(items: &mut [T]) {
+    let half = items.len() / 2;
+    // The front half and the back half are separate borrows
+    let (front, back) = items.split_at_mut(items.len() - half);
+    let back_len = back.len();
+    for i in 0..half {
+        std::mem::swap(&mut front[i], &mut back[back_len - 1 - i]);
+    }
+}
+
+/// Like `slice::rotate_left`: `[1, 2, 3, 4, 5]` by 2 becomes `[3, 4, 5, 1, 2]`.
+///
+/// Reversing both halves and then the whole slice moves each half to the
+/// other end in place.
+pub fn rotate_left<T>(items: &mut [T], mid: usize) {
+    assert!(mid <= items.len(), "mid {} is past the end of a slice of length {}", mid, items.len());
+    let (left, right) = items.split_at_mut(mid);
+    reverse(left);
+    reverse(right);
+    reverse(items);
+}
+
+/// Like `slice::rotate_right`.
+pub fn rotate_right<T>(items: &mut [T], k: usize) {
+    assert!(k <= items.len(), "k {} is past the end of a slice of length {}", k, items.len());
+    let mid = items.len() - k;
+    rotate_left(items, mid);
+}
+
+/// Moves every element for which `pred` is true to the front and returns how
+/// many there are. The order within each group is not kept.
+///
+/// The std equivalent is `Iterator::partition`, which copies into two `Vec`s.
+pub fn partition<T, F: FnMut(&T) -> bool>(items: &mut [T], mut pred: F) -> usize {
+    // items[..split] matches pred; items[split..i] does not
+    let mut split = 0;
+    for i in 0..items.len() {
+        if pred(&items[i]) {
+            if split < i {
+                swap_split(items, split, i);
+            }
+            split += 1;
+        }
+    }
+    split
+}
+
+/// The maximum of every window of `k` consecutive elements, in O(n).
+/// Matches `items.windows(k).map(|w| w.iter().max())`.
+///
+/// The deque holds indexes of the current window whose values decrease from
+/// front to back, so the front is always the window's maximum.
+pub fn sliding_window_max<T: Ord + Clone>(items: &[T], k: usize) -> Vec<T> {
+    assert!(k > 0, "window size must be positive");
+    if items.len() < k {
+        return Vec::new();
+    }
+    let mut result = Vec::with_capacity(items.len() - k + 1);
+    let mut candidates: VecDeque<usize> = VecDeque::new();
+    for (i, item) in items.iter().enumerate() {
+        // Anything smaller than the new item can never be a maximum again
+        while candidates.back().is_some_and(|&j| items[j] <= *item) {
+            candidates.pop_back();
+        }
+        candidates.push_back(i);
+        // Drop the front once it slides out of the window
+        if candidates.front().is_some_and(|&j| j + k <= i) {
+            candidates.pop_front();
+        }
+        if i + 1 >= k {
+            result.push(items[candidates[0]].clone());
+        }
+    }
+    result
+}
+
+/// Splits off the first whitespace-separated word: `("Hello,", "Rustaceans!")`.
+/// Leading whitespace is skipped; the rest keeps everything after the word,
+/// minus the whitespace right after it.
+pub fn split_first_word(s: &str) -> Option<(&str, &str)> {
+    let s = s.trim_start();
+    if s.is_empty() {
+        return None;
+    }
+    let end = s.char_indices().find(|(_, c)| c.is_whitespace()).map_or(s.len(), |(i, _)| i);
+    let (word, rest) = s.split_at(end);
+    Some((word, rest.trim_start()))
+}
+
+/// The first word of `s`, or `""` if there is none. Matches
+/// `s.split_whitespace().next().unwrap_or("")`.
+pub fn first_word(s: &str) -> &str {
+    split_first_word(s).map_or("", |(word, _)| word)
+}
+
+/// Splits `s` into all of its words by repeatedly taking the first one.
+pub fn words(mut s: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    while let Some((word, rest)) = split_first_word(s) {
+        words.push(word);
+        s = rest;
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    const CASES: usize = 500;
+
+    /// Random small vectors with plenty of duplicates.
+    fn random_vec(rng: &mut StdRng) -> Vec<i32> {
+        let len = rng.random_range(0..40);
+        (0..len).map(|_| rng.random_range(-10..10)).collect()
+    }
+
+    #[test]
+    fn binary_search_matches_std() {
+        let mut rng = StdRng::seed_from_u64(35);
+        for _ in 0..CASES {
+            let mut items = random_vec(&mut rng);
+            items.sort();
+            let target = rng.random_range(-12..12);
+            match (binary_search(&items, &target), items.binary_search(&target)) {
+                // With duplicates, any matching index is correct
+                (Ok(i), Ok(_)) => assert_eq!(items[i], target),
+                (mine, std) => assert_eq!(mine, std, "{:?} in {:?}", target, items),
+            }
+        }
+    }
+
+    #[test]
+    fn reverse_and_rotate_match_std() {
+        let mut rng = StdRng::seed_from_u64(36);
+        for _ in 0..CASES {
+            let items = random_vec(&mut rng);
+            let mid = rng.random_range(0..=items.len());
+
+            let (mut mine, mut std) = (items.clone(), items.clone());
+            reverse(&mut mine);
+            std.reverse();
+            assert_eq!(mine, std);
+
+            let (mut mine, mut std) = (items.clone(), items.clone());
+            rotate_left(&mut mine, mid);
+            std.rotate_left(mid);
+            assert_eq!(mine, std, "rotate_left({:?}, {})", items, mid);
+
+            let (mut mine, mut std) = (items.clone(), items.clone());
+            rotate_right(&mut mine, mid);
+            std.rotate_right(mid);
+            assert_eq!(mine, std, "rotate_right({:?}, {})", items, mid);
+        }
+    }
+
+    #[test]
+    fn partition_matches_std() {
+        let mut rng = StdRng::seed_from_u64(37);
+        for _ in 0..CASES {
+            let items = random_vec(&mut rng);
+            let is_even = |x: &i32| x % 2 == 0;
+            let (mut evens, mut odds): (Vec<i32>, Vec<i32>) = items.iter().partition(|x| is_even(x));
+
+            let mut mine = items.clone();
+            let split = partition(&mut mine, is_even);
+            assert_eq!(split, evens.len());
+            let (mut front, mut back) = (mine[..split].to_vec(), mine[split..].to_vec());
+            for v in [&mut evens, &mut odds, &mut front, &mut back] {
+                v.sort();
+            }
+            assert_eq!((front, back), (evens, odds));
+        }
+    }
+
+    #[test]
+    fn sliding_window_max_matches_windows() {
+        let mut rng = StdRng::seed_from_u64(38);
+        for _ in 0..CASES {
+            let items = random_vec(&mut rng);
+            let k = rng.random_range(1..8);
+            let expected: Vec<i32> = items.windows(k).map(|w| *w.iter().max().unwrap()).collect();
+            assert_eq!(sliding_window_max(&items, k), expected, "k = {} on {:?}", k, items);
+        }
+    }
+
+    #[test]
+    fn words_match_split_whitespace() {
+        let mut rng = StdRng::seed_from_u64(39);
+        let pieces = ["a", "héllo", "Rust", " ", "  ", "\t", "\n", "\u{3000}", "世界", ","];
+        for _ in 0..CASES {
+            let len = rng.random_range(0..10);
+            let s: String = (0..len).map(|_| pieces[rng.random_range(0..pieces.len())]).collect();
+            assert_eq!(first_word(&s), s.split_whitespace().next().unwrap_or(""), "{:?}", s);
+            assert_eq!(words(&s), s.split_whitespace().collect::<Vec<_>>(), "{:?}", s);
+        }
+        assert_eq!(split_first_word("  Hello, Rustaceans!"), Some(("Hello,", "Rustaceans!")));
+    }
+}
@@ -0,0 +1,381 @@
+// sorting.rs
+// 6.2 Generics as function parameters: sorting and searching
+// `max<T: PartialOrd + Copy>` in ch06_02_generic_fn_param.rs compares two
+// values. These functions grow that into whole slices. Each one is generic
+// over `T: Ord`, or takes a comparator `F: FnMut(&T, &T) -> Ordering`, and
+// none needs `Copy` or `Clone`: elements are only compared, swapped and rotated.
+
+use std::cmp::Ordering;
+
+/// The smallest and largest element, or `None` for an empty slice.
+///
+/// Elements are compared in pairs, which takes about `3n/2` comparisons instead
+/// of `2n`. Ties are broken like `Iterator::min` / `Iterator::max`: the first
+/// smallest and the last largest.
+///
+/// ```
+/// use rcrash_core::sorting::min_max;
+///
+/// assert_eq!(min_max(&[3, 1, 4, 1, 5]), Some((&1, &5)));
+/// assert_eq!(min_max::<i32>(&[]), None);
+/// ```
+pub fn min_max<T: Ord>(items: &[T]) -> Option<(&T, &T)> {
+    let (first, rest) = items.split_first()?;
+    let (mut min, mut max) = (first, first);
+    let mut pairs = rest.chunks_exact(2);
+    for pair in &mut pairs {
+        let (small, large) = if pair[1] < pair[0] { (&pair[1], &pair[0]) } else { (&pair[0], &pair[1]) };
+        if small < min {
+            min = small;
+        }
+        if large >= max {
+            max = large;
+        }
+    }
+    if let [last] = pairs.remainder() {
+        if last < min {
+            min = last;
+        }
+        if last >= max {
+            max = last;
+        }
+    }
+    Some((min, max))
+}
+
+/// The index of the largest element, or `None` for an empty slice.
+/// If the largest value occurs more than once, the first index is returned.
+///
+/// ```
+/// use rcrash_core::sorting::argmax;
+///
+/// assert_eq!(argmax(&["pear", "apple", "quince", "quince"]), Some(2));
+/// ```
+pub fn argmax<T: Ord>(items: &[T]) -> Option<usize> {
+    let mut best = 0;
+    for (i, item) in items.iter().enumerate().skip(1) {
+        if *item > items[best] {
+            best = i;
+        }
+    }
+    (!items.is_empty()).then_some(best)
+}
+
+/// `value` limited to `lo..=hi`, like `Ord::clamp`.
+///
+/// # Panics
+///
+/// If `lo > hi`.
+///
+/// ```
+/// use rcrash_core::sorting::clamp;
+///
+/// assert_eq!(clamp(120, 0, 100), 100);
+/// assert_eq!(clamp("m", "a", "k"), "k");
+/// ```
+pub fn clamp<T: Ord>(value: T, lo: T, hi: T) -> T {
+    assert!(lo <= hi, "clamp: lo must not be greater than hi");
+    if value < lo {
+        lo
+    } else if value > hi {
+        hi
+    } else {
+        value
+    }
+}
+
+/// Insertion sort: stable, `O(n²)`, but fast on short or nearly sorted slices.
+pub fn insertion_sort<T: Ord>(items: &mut [T]) {
+    for i in 1..items.len() {
+        let mut j = i;
+        while j > 0 && items[j - 1] > items[j] {
+            items.swap(j - 1, j);
+            j -= 1;
+        }
+    }
+}
+
+/// Merge sort: stable and `O(n log² n)` without extra memory. See [`merge_sort_by`].
+pub fn merge_sort<T: Ord>(items: &mut [T]) {
+    merge_sort_by(items, T::cmp);
+}
+
+/// Stable merge sort with a comparator, like `slice::sort_by`.
+///
+/// The halves are merged in place by rotating blocks, so no buffer (and no
+/// `Clone`) is needed. The price is an extra `log n` factor over a merge with a
+/// buffer.
+///
+/// ```
+/// use rcrash_core::sorting::merge_sort_by;
+///
+/// let mut words = ["bb", "a", "cc", "d"];
+/// merge_sort_by(&mut words, |a, b| a.len().cmp(&b.len()));
+/// assert_eq!(words, ["a", "d", "bb", "cc"]); // equal lengths keep their order
+/// ```
+pub fn merge_sort_by<T, F: FnMut(&T, &T) -> Ordering>(items: &mut [T], mut compare: F) {
+    sort_runs(items, &mut compare);
+}
+
+fn sort_runs<T, F: FnMut(&T, &T) -> Ordering>(items: &mut [T], compare: &mut F) {
+    if items.len() < 2 {
+        return;
+    }
+    let mid = items.len() / 2;
+    sort_runs(&mut items[..mid], compare);
+    sort_runs(&mut items[mid..], compare);
+    // If the last of the left run is not after the first of the right, the runs are already in order
+    if compare(&items[mid - 1], &items[mid]) == Ordering::Greater {
+        merge_in_place(items, mid, compare);
+    }
+}
+
+/// Merges the sorted runs `items[..mid]` and `items[mid..]`.
+///
+/// The longer run is cut in half, and the other run is split where
+/// that element would go. Rotating the middle two blocks leaves two smaller,
+/// independent merges. Elements from the right run only move in front of
+/// strictly greater left elements, which keeps the merge stable.
+fn merge_in_place<T, F: FnMut(&T, &T) -> Ordering>(items: &mut [T], mid: usize, compare: &mut F) {
+    let len = items.len();
+    if mid == 0 || mid == len {
+        return;
+    }
+    if len == 2 {
+        if compare(&items[1], &items[0]) == Ordering::Less {
+            items.swap(0, 1);
+        }
+        return;
+    }
+    let (left_cut, right_cut) = if mid >= len - mid {
+        let left_cut = mid / 2;
+        let pivot = &items[left_cut];
+        let below = items[mid..].partition_point(|x| compare(x, pivot) == Ordering::Less);
+        (left_cut, mid + below)
+    } else {
+        let right_cut = mid + (len - mid) / 2;
+        let pivot = &items[right_cut];
+        (items[..mid].partition_point(|x| compare(pivot, x) != Ordering::Less), right_cut)
+    };
+    items[left_cut..right_cut].rotate_left(mid - left_cut);
+    let new_mid = left_cut + (right_cut - mid);
+    let (left, right) = items.split_at_mut(new_mid);
+    merge_in_place(left, left_cut, compare);
+    merge_in_place(right, right_cut - new_mid, compare);
+}
+
+/// Quicksort: unstable, `O(n log n)` on average.
+///
+/// The pivot is the median of the elements at 1/4, 1/2 and 3/4 of the slice,
+/// so sorted and reversed input split evenly. (Sampling the first and last
+/// elements instead goes quadratic on reversed input: after one partition the
+/// left side is sorted except for a large element at the front.) Only the smaller side is sorted
+/// recursively, which keeps the stack depth at `O(log n)`. Short slices are
+/// finished with [`insertion_sort`].
+pub fn quick_sort<T: Ord>(mut items: &mut [T]) {
+    const SMALL: usize = 16;
+    while items.len() > SMALL {
+        let pivot = median_of_three(items);
+        items.swap(0, pivot);
+        let split = partition(items);
+        let (left, rest) = items.split_at_mut(split);
+        let right = &mut rest[1..];
+        if left.len() < right.len() {
+            quick_sort(left);
+            items = right;
+        } else {
+            quick_sort(right);
+            items = left;
+        }
+    }
+    insertion_sort(items);
+}
+
+fn median_of_three<T: Ord>(items: &[T]) -> usize {
+    let (a, b, c) = (items.len() / 4, items.len() / 2, items.len() * 3 / 4);
+    match (items[a] < items[b], items[b] < items[c], items[a] < items[c]) {
+        (true, true, _) | (false, false, _) => b,
+        (true, false, true) | (false, true, false) => c,
+        _ => a,
+    }
+}
+
+/// Hoare-style partition around `items[0]`. Returns the pivot's final index
+/// `p`, with `items[..p] <= pivot` and `items[p + 1..] >= pivot`.
+///
+/// Both scans stop at elements equal to the pivot and swap them across, so a
+/// slice with many duplicates still splits near the middle. The pivot stays at
+/// index 0 until the end, so it is compared in place and never copied.
+fn partition<T: Ord>(items: &mut [T]) -> usize {
+    let (mut i, mut j) = (1, items.len() - 1);
+    loop {
+        while i <= j && items[i] < items[0] {
+            i += 1;
+        }
+        while i <= j && items[j] > items[0] {
+            j -= 1;
+        }
+        if i >= j {
+            break;
+        }
+        items.swap(i, j);
+        i += 1;
+        j -= 1;
+    }
+    items.swap(0, j);
+    j
+}
+
+/// Heapsort: unstable, `O(n log n)` in every case, no extra memory.
+///
+/// The slice is turned into a max-heap, then the largest element is swapped
+/// to the end and the heap shrinks by one, until the heap is empty.
+pub fn heap_sort<T: Ord>(items: &mut [T]) {
+    for start in (0..items.len() / 2).rev() {
+        sift_down(items, start);
+    }
+    for end in (1..items.len()).rev() {
+        items.swap(0, end);
+        sift_down(&mut items[..end], 0);
+    }
+}
+
+/// Moves `heap[node]` down until both children are no larger.
+fn sift_down<T: Ord>(heap: &mut [T], mut node: usize) {
+    loop {
+        let mut child = 2 * node + 1;
+        if child >= heap.len() {
+            return;
+        }
+        if child + 1 < heap.len() && heap[child + 1] > heap[child] {
+            child += 1;
+        }
+        if heap[node] >= heap[child] {
+            return;
+        }
+        heap.swap(node, child);
+        node = child;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    type Sort = fn(&mut [i32]);
+    const SORTS: [(&str, Sort); 4] = [
+        ("insertion", insertion_sort),
+        ("merge", merge_sort),
+        ("quick", quick_sort),
+        ("heap", heap_sort),
+    ];
+
+    /// Random vectors of every length up to 300, with few or many distinct values.
+    fn random_inputs(seed: u64) -> Vec<Vec<i32>> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        (0..300)
+            .map(|len| {
+                let range = if len % 2 == 0 { 5 } else { 1_000_000 };
+                (0..len).map(|_| rng.random_range(-range..range)).collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn sorts_agree_with_slice_sort() {
+        let mut inputs = random_inputs(1);
+        inputs.push((0..100).collect());
+        inputs.push((0..100).rev().collect());
+        inputs.push(vec![7; 50]);
+        inputs.push([(0..50).collect::<Vec<_>>(), (0..50).collect()].concat());
+        for input in inputs {
+            let mut expected = input.clone();
+            expected.sort();
+            for (name, sort) in SORTS {
+                let mut actual = input.clone();
+                sort(&mut actual);
+                assert_eq!(actual, expected, "{} sort of {:?}", name, input);
+            }
+        }
+    }
+
+    #[test]
+    fn merge_sort_by_is_stable() {
+        let mut rng = StdRng::seed_from_u64(2);
+        for len in 0..200 {
+            // (key, original position): a stable sort keeps positions increasing within a key
+            let input: Vec<(u8, usize)> = (0..len).map(|i| (rng.random_range(0..4), i)).collect();
+            let mut expected = input.clone();
+            expected.sort_by_key(|&(key, _)| key);
+            let mut actual = input.clone();
+            merge_sort_by(&mut actual, |a, b| a.0.cmp(&b.0));
+            assert_eq!(actual, expected);
+
+            // A reversed comparator sorts descending, still stably
+            let mut descending = input.clone();
+            merge_sort_by(&mut descending, |a, b| b.0.cmp(&a.0));
+            expected.sort_by_key(|&(key, _)| std::cmp::Reverse(key));
+            assert_eq!(descending, expected);
+        }
+    }
+
+    #[test]
+    fn sorts_move_non_copy_values() {
+        let words = ["pear", "fig", "apple", "kiwi", "fig", "banana"].map(String::from);
+        let mut expected = words.to_vec();
+        expected.sort();
+        let sorts: [fn(&mut [String]); 4] = [insertion_sort, merge_sort, quick_sort, heap_sort];
+        for sort in sorts {
+            let mut actual = words.to_vec();
+            sort(&mut actual);
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn min_max_and_argmax_agree_with_iterators() {
+        for input in random_inputs(3) {
+            let expected = input.iter().min().zip(input.iter().max());
+            assert_eq!(min_max(&input), expected);
+            assert_eq!(argmax(&input), input.iter().position(|x| Some(x) == input.iter().max()));
+        }
+        // Ties: first smallest, last largest, like Iterator::min / max
+        let tagged = [(1, 'a'), (0, 'b'), (1, 'c'), (0, 'd')];
+        let keys: Vec<_> = tagged.iter().map(|&(k, tag)| Key(k, tag)).collect();
+        let (min, max) = min_max(&keys).unwrap();
+        assert_eq!((min.1, max.1), ('b', 'c'));
+        assert_eq!(argmax(&keys), Some(0));
+    }
+
+    /// Compares by the number only, so elements with equal numbers tie.
+    #[derive(Debug, PartialEq, Eq)]
+    struct Key(i32, char);
+
+    impl PartialOrd for Key {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Key {
+        fn cmp(&self, other: &Self) -> Ordering {
+            self.0.cmp(&other.0)
+        }
+    }
+
+    #[test]
+    fn clamp_matches_ord_clamp() {
+        for value in -5..=5 {
+            assert_eq!(clamp(value, -2, 3), value.clamp(-2, 3));
+        }
+        assert_eq!(clamp(String::from("zebra"), String::from("a"), String::from("m")), "m");
+    }
+
+    #[test]
+    #[should_panic(expected = "lo must not be greater than hi")]
+    fn clamp_rejects_an_empty_range() {
+        clamp(1, 3, 2);
+    }
+}
@@ -0,0 +1,377 @@
+// status.rs
+// 3.11 Enums with explicit discriminants
+// Grows the `Status` enum from ch03_11_enum.rs (Ok = 200, NotFound = 404, ...)
+// into a complete HTTP status-code type that converts both ways.
+
+use std::fmt;
+use std::str::FromStr;
+
+/// Common HTTP status codes. The discriminant is the numeric code,
+/// so `status as u16` still works like the `as i32` cast in ch03_11.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[repr(u16)]
+pub enum Status {
+    // 1xx Informational
+    Continue = 100,
+    SwitchingProtocols = 101,
+    Processing = 102,
+    EarlyHints = 103,
+    // 2xx Success
+    Ok = 200,
+    Created = 201,
+    Accepted = 202,
+    NonAuthoritativeInformation = 203,
+    NoContent = 204,
+    ResetContent = 205,
+    PartialContent = 206,
+    // 3xx Redirection
+    MultipleChoices = 300,
+    MovedPermanently = 301,
+    Found = 302,
+    SeeOther = 303,
+    NotModified = 304,
+    TemporaryRedirect = 307,
+    PermanentRedirect = 308,
+    // 4xx Client Error
+    BadRequest = 400,
+    Unauthorized = 401,
+    PaymentRequired = 402,
+    Forbidden = 403,
+    NotFound = 404,
+    MethodNotAllowed = 405,
+    NotAcceptable = 406,
+    RequestTimeout = 408,
+    Conflict = 409,
+    Gone = 410,
+    LengthRequired = 411,
+    PreconditionFailed = 412,
+    PayloadTooLarge = 413,
+    UriTooLong = 414,
+    UnsupportedMediaType = 415,
+    RangeNotSatisfiable = 416,
+    ExpectationFailed = 417,
+    ImATeapot = 418,
+    UnprocessableEntity = 422,
+    TooEarly = 425,
+    UpgradeRequired = 426,
+    PreconditionRequired = 428,
+    TooManyRequests = 429,
+    RequestHeaderFieldsTooLarge = 431,
+    UnavailableForLegalReasons = 451,
+    // 5xx Server Error
+    InternalServerError = 500,
+    NotImplemented = 501,
+    BadGateway = 502,
+    ServiceUnavailable = 503,
+    GatewayTimeout = 504,
+    HttpVersionNotSupported = 505,
+    NetworkAuthenticationRequired = 511,
+}
+
+/// The five classes given by the first digit of a status code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StatusClass {
+    Informational,
+    Success,
+    Redirection,
+    ClientError,
+    ServerError,
+}
+
+impl Status {
+    /// Every status code, in ascending numeric order.
+    pub const ALL: [Status; 50] = [
+        Status::Continue,
+        Status::SwitchingProtocols,
+        Status::Processing,
+        Status::EarlyHints,
+        Status::Ok,
+        Status::Created,
+        Status::Accepted,
+        Status::NonAuthoritativeInformation,
+        Status::NoContent,
+        Status::ResetContent,
+        Status::PartialContent,
+        Status::MultipleChoices,
+        Status::MovedPermanently,
+        Status::Found,
+        Status::SeeOther,
+        Status::NotModified,
+        Status::TemporaryRedirect,
+        Status::PermanentRedirect,
+        Status::BadRequest,
+        Status::Unauthorized,
+        Status::PaymentRequired,
+        Status::Forbidden,
+        Status::NotFound,
+        Status::MethodNotAllowed,
+        Status::NotAcceptable,
+        Status::RequestTimeout,
+        Status::Conflict,
+        Status::Gone,
+        Status::LengthRequired,
+        Status::PreconditionFailed,
+        Status::PayloadTooLarge,
+        Status::UriTooLong,
+        Status::UnsupportedMediaType,
+        Status::RangeNotSatisfiable,
+        Status::ExpectationFailed,
+        Status::ImATeapot,
+        Status::UnprocessableEntity,
+        Status::TooEarly,
+        Status::UpgradeRequired,
+        Status::PreconditionRequired,
+        Status::TooManyRequests,
+        Status::RequestHeaderFieldsTooLarge,
+        Status::UnavailableForLegalReasons,
+        Status::InternalServerError,
+        Status::NotImplemented,
+        Status::BadGateway,
+        Status::ServiceUnavailable,
+        Status::GatewayTimeout,
+        Status::HttpVersionNotSupported,
+        Status::NetworkAuthenticationRequired,
+    ];
+
+    /// The numeric code (the enum discriminant).
+    pub fn code(self) -> u16 {
+        self as u16
+    }
+
+    /// The standard reason phrase, e.g. "Not Found".
+    pub fn reason(self) -> &'static str {
+        match self {
+            Status::Continue => "Continue",
+            Status::SwitchingProtocols => "Switching Protocols",
+            Status::Processing => "Processing",
+            Status::EarlyHints => "Early Hints",
+            Status::Ok => "OK",
+            Status::Created => "Created",
+            Status::Accepted => "Accepted",
+            Status::NonAuthoritativeInformation => "Non-Authoritative Information",
+            Status::NoContent => "No Content",
+            Status::ResetContent => "Reset Content",
+            Status::PartialContent => "Partial Content",
+            Status::MultipleChoices => "Multiple Choices",
+            Status::MovedPermanently => "Moved Permanently",
+            Status::Found => "Found",
+            Status::SeeOther => "See Other",
+            Status::NotModified => "Not Modified",
+            Status::TemporaryRedirect => "Temporary Redirect",
+            Status::PermanentRedirect => "Permanent Redirect",
+            Status::BadRequest => "Bad Request",
+            Status::Unauthorized => "Unauthorized",
+            Status::PaymentRequired => "Payment Required",
+            Status::Forbidden => "Forbidden",
+            Status::NotFound => "Not Found",
+            Status::MethodNotAllowed => "Method Not Allowed",
+            Status::NotAcceptable => "Not Acceptable",
+            Status::RequestTimeout => "Request Timeout",
+            Status::Conflict => "Conflict",
+            Status::Gone => "Gone",
+            Status::LengthRequired => "Length Required",
+            Status::PreconditionFailed => "Precondition Failed",
+            Status::PayloadTooLarge => "Payload Too Large",
+            Status::UriTooLong => "URI Too Long",
+            Status::UnsupportedMediaType => "Unsupported Media Type",
+            Status::RangeNotSatisfiable => "Range Not Satisfiable",
+            Status::ExpectationFailed => "Expectation Failed",
+            Status::ImATeapot => "I'm a teapot",
+            Status::UnprocessableEntity => "Unprocessable Entity",
+            Status::TooEarly => "Too Early",
+            Status::UpgradeRequired => "Upgrade Required",
+            Status::PreconditionRequired => "Precondition Required",
+            Status::TooManyRequests => "Too Many Requests",
+            Status::RequestHeaderFieldsTooLarge => "Request Header Fields Too Large",
+            Status::UnavailableForLegalReasons => "Unavailable For Legal Reasons",
+            Status::InternalServerError => "Internal Server Error",
+            Status::NotImplemented => "Not Implemented",
+            Status::BadGateway => "Bad Gateway",
+            Status::ServiceUnavailable => "Service Unavailable",
+            Status::GatewayTimeout => "Gateway Timeout",
+            Status::HttpVersionNotSupported => "HTTP Version Not Supported",
+            Status::NetworkAuthenticationRequired => "Network Authentication Required",
+        }
+    }
+
+    /// The class is decided by the hundreds digit alone.
+    pub fn class(self) -> StatusClass {
+        match self.code() {
+            100..=199 => StatusClass::Informational,
+            200..=299 => StatusClass::Success,
+            300..=399 => StatusClass::Redirection,
+            400..=499 => StatusClass::ClientError,
+            _ => StatusClass::ServerError,
+        }
+    }
+
+    pub fn is_informational(self) -> bool {
+        self.class() == StatusClass::Informational
+    }
+
+    pub fn is_success(self) -> bool {
+        self.class() == StatusClass::Success
+    }
+
+    pub fn is_redirection(self) -> bool {
+        self.class() == StatusClass::Redirection
+    }
+
+    pub fn is_client_error(self) -> bool {
+        self.class() == StatusClass::ClientError
+    }
+
+    pub fn is_server_error(self) -> bool {
+        self.class() == StatusClass::ServerError
+    }
+
+    /// Client or server error.
+    pub fn is_error(self) -> bool {
+        self.is_client_error() || self.is_server_error()
+    }
+}
+
+/// Prints the status line form, e.g. `404 Not Found`.
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.code(), self.reason())
+    }
+}
+
+/// Errors from converting a number or a string into a `Status`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StatusError {
+    Empty,
+    InvalidCode(String),
+    UnknownCode(u16),
+    ReasonMismatch { status: Status, found: String },
+}
+
+impl fmt::Display for StatusError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StatusError::Empty => write!(f, "empty status"),
+            StatusError::InvalidCode(s) => write!(f, "'{}' is not a three-digit status code", s),
+            StatusError::UnknownCode(code) => write!(f, "unknown status code {}", code),
+            StatusError::ReasonMismatch { status, found } => write!(
+                f,
+                "reason '{}' does not match code {} (expected '{}')",
+                found,
+                status.code(),
+                status.reason()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for StatusError {}
+
+impl TryFrom<u16> for Status {
+    type Error = StatusError;
+
+    fn try_from(code: u16) -> Result<Self, Self::Error> {
+        Status::ALL
+            .iter()
+            .copied()
+            .find(|s| s.code() == code)
+            .ok_or(StatusError::UnknownCode(code))
+    }
+}
+
+impl From<Status> for u16 {
+    fn from(status: Status) -> u16 {
+        status.code()
+    }
+}
+
+/// Accepts `"404"` or a full status line such as `"404 Not Found"`.
+/// The reason phrase is compared case-insensitively.
+impl FromStr for Status {
+    type Err = StatusError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(StatusError::Empty);
+        }
+        let (code_text, reason) = match s.split_once(char::is_whitespace) {
+            Some((code, reason)) => (code, Some(reason.trim())),
+            None => (s, None),
+        };
+        if code_text.len() != 3 || !code_text.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(StatusError::InvalidCode(code_text.to_string()));
+        }
+        let code: u16 = code_text
+            .parse()
+            .map_err(|_| StatusError::InvalidCode(code_text.to_string()))?;
+        let status = Status::try_from(code)?;
+        match reason {
+            Some(r) if !r.eq_ignore_ascii_case(status.reason()) => {
+                Err(StatusError::ReasonMismatch { status, found: r.to_string() })
+            }
+            _ => Ok(status),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_is_sorted_and_unique() {
+        for pair in Status::ALL.windows(2) {
+            assert!(pair[0].code() < pair[1].code(), "{} before {}", pair[0], pair[1]);
+        }
+    }
+
+    #[test]
+    fn round_trip_through_u16() {
+        for status in Status::ALL {
+            assert_eq!(Status::try_from(status.code()), Ok(status));
+            assert_eq!(u16::from(status), status as u16);
+        }
+    }
+
+    #[test]
+    fn round_trip_through_display_and_from_str() {
+        for status in Status::ALL {
+            let line = status.to_string();
+            assert_eq!(line.parse::<Status>(), Ok(status));
+            assert_eq!(line.to_uppercase().parse::<Status>(), Ok(status));
+            assert_eq!(status.code().to_string().parse::<Status>(), Ok(status));
+        }
+    }
+
+    #[test]
+    fn every_other_code_is_rejected() {
+        for code in 0..=u16::MAX {
+            let known = Status::ALL.iter().any(|s| s.code() == code);
+            assert_eq!(Status::try_from(code).is_ok(), known, "code {}", code);
+        }
+    }
+
+    #[test]
+    fn class_predicates_match_the_hundreds_digit() {
+        for status in Status::ALL {
+            let digit = status.code() / 100;
+            assert_eq!(status.is_informational(), digit == 1);
+            assert_eq!(status.is_success(), digit == 2);
+            assert_eq!(status.is_redirection(), digit == 3);
+            assert_eq!(status.is_client_error(), digit == 4);
+            assert_eq!(status.is_server_error(), digit == 5);
+        }
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!("".parse::<Status>(), Err(StatusError::Empty));
+        assert_eq!("4O4".parse::<Status>(), Err(StatusError::InvalidCode("4O4".into())));
+        assert_eq!("+404".parse::<Status>(), Err(StatusError::InvalidCode("+404".into())));
+        assert_eq!("299 Whatever".parse::<Status>(), Err(StatusError::UnknownCode(299)));
+        assert_eq!(
+            "404 Gone".parse::<Status>(),
+            Err(StatusError::ReasonMismatch { status: Status::NotFound, found: "Gone".into() })
+        );
+    }
+}
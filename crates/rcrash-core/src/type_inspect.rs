@@ -0,0 +1,315 @@
+// type_inspect.rs
+// 3.4 Basic data types, looked at from the inside
+// Sizes, alignments and ranges of the primitive types, and the IEEE-754 bit
+// layout (sign, exponent, mantissa) of f32 and f64 values.
+
+use std::fmt;
+use std::mem::{align_of, size_of};
+use std::num::FpCategory;
+
+/// One row of the primitive type table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeInfo {
+    pub name: &'static str,
+    pub size: usize,
+    pub align: usize,
+    pub min: String,
+    pub max: String,
+}
+
+macro_rules! type_info {
+    ($($t:ty => $min:expr, $max:expr);* $(;)?) => {
+        vec![$(TypeInfo {
+            name: stringify!($t),
+            size: size_of::<$t>(),
+            align: align_of::<$t>(),
+            min: $min.to_string(),
+            max: $max.to_string(),
+        }),*]
+    };
+}
+
+/// Every scalar primitive, in the order the book introduces them.
+pub fn primitive_types() -> Vec<TypeInfo> {
+    type_info! {
+        i8 => i8::MIN, i8::MAX;
+        i16 => i16::MIN, i16::MAX;
+        i32 => i32::MIN, i32::MAX;
+        i64 => i64::MIN, i64::MAX;
+        i128 => i128::MIN, i128::MAX;
+        isize => isize::MIN, isize::MAX;
+        u8 => u8::MIN, u8::MAX;
+        u16 => u16::MIN, u16::MAX;
+        u32 => u32::MIN, u32::MAX;
+        u64 => u64::MIN, u64::MAX;
+        u128 => u128::MIN, u128::MAX;
+        usize => usize::MIN, usize::MAX;
+        f32 => format!("{:e}", f32::MIN), format!("{:e}", f32::MAX);
+        f64 => format!("{:e}", f64::MIN), format!("{:e}", f64::MAX);
+        bool => false, true;
+        char => "'\\u{0}'", "'\\u{10ffff}'";
+        () => "()", "()";
+    }
+}
+
+/// A binary floating-point type whose bits can be taken apart.
+pub trait Ieee754: Copy + PartialOrd + fmt::Display + fmt::Debug {
+    const NAME: &'static str;
+    const EXPONENT_BITS: u32;
+    const MANTISSA_BITS: u32;
+
+    /// The raw bits, zero-extended to 64.
+    fn to_raw(self) -> u64;
+
+    fn from_raw(raw: u64) -> Self;
+
+    fn classify(self) -> FpCategory;
+
+    fn is_sign_negative(self) -> bool;
+
+    fn neg(self) -> Self;
+}
+
+macro_rules! ieee754 {
+    ($($t:ident: $bits:ident, $exp:expr, $mant:expr);*) => {
+        $(impl Ieee754 for $t {
+            const NAME: &'static str = stringify!($t);
+            const EXPONENT_BITS: u32 = $exp;
+            const MANTISSA_BITS: u32 = $mant;
+
+            fn to_raw(self) -> u64 {
+                self.to_bits() as u64
+            }
+
+            fn from_raw(raw: u64) -> Self {
+                $t::from_bits(raw as $bits)
+            }
+
+            fn classify(self) -> FpCategory {
+                $t::classify(self)
+            }
+
+            fn is_sign_negative(self) -> bool {
+                $t::is_sign_negative(self)
+            }
+
+            fn neg(self) -> Self {
+                -self
+            }
+        })*
+    };
+}
+
+ieee754!(f32: u32, 8, 23; f64: u64, 11, 52);
+
+/// The smallest value greater than `x`.
+///
+/// Positive floats are ordered like their bit patterns, so stepping up is
+/// adding one to the bits; negative floats are stored as sign + magnitude,
+/// so stepping up means making the magnitude smaller.
+pub fn next_up<F: Ieee754>(x: F) -> F {
+    match x.classify() {
+        FpCategory::Nan => x,
+        FpCategory::Infinite if !x.is_sign_negative() => x,
+        // Both +0.0 and -0.0 step up to the smallest positive subnormal
+        FpCategory::Zero => F::from_raw(1),
+        _ if x.is_sign_negative() => F::from_raw(x.to_raw() - 1),
+        _ => F::from_raw(x.to_raw() + 1),
+    }
+}
+
+/// The largest value less than `x`.
+pub fn next_down<F: Ieee754>(x: F) -> F {
+    next_up(x.neg()).neg()
+}
+
+/// How a category is usually named.
+pub fn category_name(category: FpCategory) -> &'static str {
+    match category {
+        FpCategory::Zero => "zero",
+        FpCategory::Subnormal => "subnormal",
+        FpCategory::Normal => "normal",
+        FpCategory::Infinite => "infinite",
+        FpCategory::Nan => "NaN",
+    }
+}
+
+/// The three fields of a float, as stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FloatLayout {
+    pub ty: &'static str,
+    pub sign: u64,
+    /// The biased exponent field.
+    pub exponent: u64,
+    /// The fraction field, without the implicit leading 1.
+    pub mantissa: u64,
+    pub exponent_bits: u32,
+    pub mantissa_bits: u32,
+    pub category: FpCategory,
+}
+
+impl FloatLayout {
+    pub fn of<F: Ieee754>(x: F) -> FloatLayout {
+        let raw = x.to_raw();
+        let mantissa_mask = (1u64 << F::MANTISSA_BITS) - 1;
+        let exponent_mask = (1u64 << F::EXPONENT_BITS) - 1;
+        FloatLayout {
+            ty: F::NAME,
+            sign: raw >> (F::EXPONENT_BITS + F::MANTISSA_BITS),
+            exponent: (raw >> F::MANTISSA_BITS) & exponent_mask,
+            mantissa: raw & mantissa_mask,
+            exponent_bits: F::EXPONENT_BITS,
+            mantissa_bits: F::MANTISSA_BITS,
+            category: x.classify(),
+        }
+    }
+
+    /// 127 for f32, 1023 for f64.
+    pub fn bias(&self) -> i32 {
+        (1 << (self.exponent_bits - 1)) - 1
+    }
+
+    /// The power of two the significand is multiplied by, for finite values.
+    /// Subnormals use the smallest normal exponent, with no implicit 1.
+    pub fn unbiased_exponent(&self) -> Option<i32> {
+        match self.category {
+            FpCategory::Normal => Some(self.exponent as i32 - self.bias()),
+            FpCategory::Subnormal | FpCategory::Zero => Some(1 - self.bias()),
+            FpCategory::Infinite | FpCategory::Nan => None,
+        }
+    }
+
+    /// The bits split into fields: `0 01111111 00111100000011001010010`.
+    pub fn bit_string(&self) -> String {
+        format!(
+            "{} {:0ew$b} {:0mw$b}",
+            self.sign,
+            self.exponent,
+            self.mantissa,
+            ew = self.exponent_bits as usize,
+            mw = self.mantissa_bits as usize
+        )
+    }
+
+    /// `1.mantissa` for normals, `0.mantissa` for subnormals and zero, as a decimal.
+    pub fn significand(&self) -> f64 {
+        let fraction = self.mantissa as f64 / (1u64 << self.mantissa_bits) as f64;
+        match self.category {
+            FpCategory::Normal => 1.0 + fraction,
+            _ => fraction,
+        }
+    }
+
+    /// How many decimal places the exact value of this float needs.
+    /// A finite float is an integer times 2^-n, and 2^-n has n decimal places.
+    pub fn exact_decimal_places(&self) -> Option<usize> {
+        let exponent = self.unbiased_exponent()?;
+        if self.mantissa == 0 && self.category != FpCategory::Normal {
+            return Some(0);
+        }
+        let trailing_zeros = if self.mantissa == 0 { self.mantissa_bits } else { self.mantissa.trailing_zeros() };
+        let fraction_bits = self.mantissa_bits as i32 - trailing_zeros as i32;
+        Some((fraction_bits - exponent).max(0) as usize)
+    }
+}
+
+impl fmt::Display for FloatLayout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.sign == 1 { "-" } else { "+" };
+        match (self.category, self.unbiased_exponent()) {
+            (FpCategory::Nan, _) => write!(f, "NaN (exponent all ones, mantissa {:#x})", self.mantissa),
+            (FpCategory::Infinite, _) => write!(f, "{}inf (exponent all ones, mantissa 0)", sign),
+            (_, Some(exponent)) => write!(f, "{}{} × 2^{}", sign, self.significand(), exponent),
+            (_, None) => unreachable!("finite floats have an exponent"),
+        }
+    }
+}
+
+/// Everything the inspector prints about one float.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FloatReport {
+    pub layout: FloatLayout,
+    pub value: String,
+    /// The exact decimal value, when it fits in a reasonable number of digits.
+    pub exact: Option<String>,
+    pub next_up: String,
+    pub next_down: String,
+}
+
+/// Beyond this many decimal places the exact value is not worth printing.
+const MAX_EXACT_PLACES: usize = 80;
+
+pub fn inspect_float<F: Ieee754>(x: F) -> FloatReport {
+    let layout = FloatLayout::of(x);
+    let exact = layout
+        .exact_decimal_places()
+        .filter(|&places| places <= MAX_EXACT_PLACES)
+        .map(|places| format!("{:.*}", places, x));
+    FloatReport {
+        layout,
+        value: format!("{:?}", x),
+        exact,
+        next_up: format!("{:?}", next_up(x)),
+        next_down: format!("{:?}", next_down(x)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table_matches_mem_functions() {
+        let types = primitive_types();
+        let char_info = types.iter().find(|t| t.name == "char").unwrap();
+        assert_eq!((char_info.size, char_info.align), (4, 4));
+        let unit = types.iter().find(|t| t.name == "()").unwrap();
+        assert_eq!(unit.size, 0);
+        let i128_info = types.iter().find(|t| t.name == "i128").unwrap();
+        assert_eq!(i128_info.max, i128::MAX.to_string());
+    }
+
+    #[test]
+    fn layout_of_known_values() {
+        let one = FloatLayout::of(1.0f32);
+        assert_eq!((one.sign, one.exponent, one.mantissa), (0, 127, 0));
+        assert_eq!(one.unbiased_exponent(), Some(0));
+
+        let minus_two = FloatLayout::of(-2.0f64);
+        assert_eq!((minus_two.sign, minus_two.exponent), (1, 1024));
+        assert_eq!(minus_two.to_string(), "-1 × 2^1");
+
+        let tiny = FloatLayout::of(f32::from_bits(1));
+        assert_eq!(tiny.category, FpCategory::Subnormal);
+        assert_eq!(tiny.unbiased_exponent(), Some(-126));
+
+        assert_eq!(FloatLayout::of(0.1f32).bit_string(), "0 01111011 10011001100110011001101");
+    }
+
+    #[test]
+    fn neighbours_match_std() {
+        let samples = [0.0, -0.0, 1.0, -1.0, 0.1, f64::MAX, f64::MIN_POSITIVE, 5e-324, -5e-324, 1e300];
+        for x in samples {
+            assert_eq!(next_up(x).to_bits(), x.next_up().to_bits(), "next_up({:e})", x);
+            assert_eq!(next_down(x).to_bits(), x.next_down().to_bits(), "next_down({:e})", x);
+            let y = x as f32;
+            assert_eq!(next_up(y).to_bits(), y.next_up().to_bits(), "next_up({:e}f32)", y);
+            assert_eq!(next_down(y).to_bits(), y.next_down().to_bits(), "next_down({:e}f32)", y);
+        }
+        assert_eq!(next_up(f64::MAX), f64::INFINITY);
+        assert_eq!(next_up(f64::INFINITY), f64::INFINITY);
+        assert_eq!(next_down(f64::NEG_INFINITY), f64::NEG_INFINITY);
+        assert!(next_up(f32::NAN).is_nan());
+    }
+
+    #[test]
+    fn exact_value_explains_the_precision_demo() {
+        // The literal from the ch03_4 precision demo
+        let report = inspect_float("1.2345678901234567890".parse::<f32>().unwrap());
+        assert_eq!(report.exact.as_deref(), Some("1.2345678806304931640625"));
+        assert_eq!(inspect_float(0.5f64).exact.as_deref(), Some("0.5"));
+        assert_eq!(inspect_float(1024.0f64).exact.as_deref(), Some("1024"));
+        assert_eq!(inspect_float(f64::from_bits(1)).exact, None);
+        assert_eq!(category_name(inspect_float(f64::NAN).layout.category), "NaN");
+    }
+}
@@ -0,0 +1,142 @@
+// doc_sync.rs
+// Many chapter docs show the full source of their binary. To keep such a copy
+// from drifting, put a marker line directly above the code fence:
+//
+//   <!-- sync: src/bin/ch04_6_for.rs -->
+//   ```rust
+//   ...
+//   ```
+//
+// `sync` replaces everything between the fences with the current file contents.
+// Docs without a marker are left alone.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const MARKER_START: &str = "<!-- sync: ";
+const MARKER_END: &str = " -->";
+
+#[derive(Debug)]
+pub enum SyncError {
+    /// A marker line is not followed by an opening code fence.
+    MissingFence { line: usize },
+    /// A synced code block has no closing fence.
+    Unclosed { line: usize },
+    /// The file named by a marker could not be read.
+    Source { path: String, error: io::Error },
+    /// A doc could not be read or written.
+    Io { path: PathBuf, error: io::Error },
+    /// One of the errors above, in the given doc.
+    InDoc { path: PathBuf, error: Box<SyncError> },
+}
+
+impl fmt::Display for SyncError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SyncError::MissingFence { line } => write!(f, "line {}: sync marker must be followed by a ``` fence", line),
+            SyncError::Unclosed { line } => write!(f, "line {}: code block is never closed", line),
+            SyncError::Source { path, error } => write!(f, "cannot read {}: {}", path, error),
+            SyncError::Io { path, error } => write!(f, "{}: {}", path.display(), error),
+            SyncError::InDoc { path, error } => write!(f, "{}: {}", path.display(), error),
+        }
+    }
+}
+
+/// The path named by a marker line, if `line` is one.
+fn marker_path(line: &str) -> Option<&str> {
+    line.trim().strip_prefix(MARKER_START)?.strip_suffix(MARKER_END).map(str::trim)
+}
+
+/// Returns `markdown` with every marked code block replaced by the contents
+/// `read` returns for the marker's path.
+pub fn sync(markdown: &str, mut read: impl FnMut(&str) -> io::Result<String>) -> Result<String, SyncError> {
+    let mut out = String::with_capacity(markdown.len());
+    let mut lines = markdown.split_inclusive('\n').enumerate();
+    while let Some((index, line)) = lines.next() {
+        out.push_str(line);
+        let Some(path) = marker_path(line) else {
+            continue;
+        };
+        match lines.next() {
+            Some((_, fence)) if fence.trim_start().starts_with("```") => out.push_str(fence),
+            _ => return Err(SyncError::MissingFence { line: index + 1 }),
+        }
+        let source = read(path).map_err(|error| SyncError::Source { path: path.to_string(), error })?;
+        out.push_str(&source);
+        if !source.ends_with('\n') {
+            out.push('\n');
+        }
+        // Drop the old block body, keep the closing fence
+        let closing = lines.by_ref().map(|(_, l)| l).find(|l| l.trim() == "```");
+        out.push_str(closing.ok_or(SyncError::Unclosed { line: index + 2 })?);
+    }
+    Ok(out)
+}
+
+/// Syncs every Markdown file in `root/docs`, with marker paths relative to
+/// `root`. Returns the docs that were out of date; with `check` set they are
+/// only reported, not rewritten.
+pub fn sync_docs(root: &Path, check: bool) -> Result<Vec<PathBuf>, SyncError> {
+    let docs = root.join("docs");
+    let io_error = |path: &Path| {
+        let path = path.to_path_buf();
+        move |error| SyncError::Io { path, error }
+    };
+    let mut paths: Vec<PathBuf> = fs::read_dir(&docs)
+        .map_err(io_error(&docs))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "md"))
+        .collect();
+    paths.sort();
+
+    let mut stale = Vec::new();
+    for path in paths {
+        let text = fs::read_to_string(&path).map_err(io_error(&path))?;
+        let synced = sync(&text, |source| fs::read_to_string(root.join(source)))
+            .map_err(|e| SyncError::InDoc { path: path.clone(), error: Box::new(e) })?;
+        if synced != text {
+            if !check {
+                fs::write(&path, &synced).map_err(io_error(&path))?;
+            }
+            stale.push(path);
+        }
+    }
+    Ok(stale)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source(path: &str) -> io::Result<String> {
+        match path {
+            "src/bin/demo.rs" => Ok("fn main() {\n    println!(\"new\");\n}\n".to_string()),
+            _ => Err(io::Error::new(io::ErrorKind::NotFound, "no such file")),
+        }
+    }
+
+    #[test]
+    fn replaces_only_marked_blocks() {
+        let doc = "# Demo\n\n<!-- sync: src/bin/demo.rs -->\n```rust\nfn main() {}\n```\n\n```rust\nfn untouched() {}\n```\n";
+        let synced = sync(doc, source).unwrap();
+        assert_eq!(
+            synced,
+            "# Demo\n\n<!-- sync: src/bin/demo.rs -->\n```rust\nfn main() {\n    println!(\"new\");\n}\n```\n\n```rust\nfn untouched() {}\n```\n"
+        );
+        // Already in sync: nothing changes
+        assert_eq!(sync(&synced, source).unwrap(), synced);
+    }
+
+    #[test]
+    fn reports_malformed_markers() {
+        let no_fence = "<!-- sync: src/bin/demo.rs -->\ntext\n";
+        assert!(matches!(sync(no_fence, source), Err(SyncError::MissingFence { line: 1 })));
+        let unclosed = "x\n<!-- sync: src/bin/demo.rs -->\n```rust\nfn main() {}\n";
+        assert!(matches!(sync(unclosed, source), Err(SyncError::Unclosed { line: 3 })));
+        let missing = "<!-- sync: src/bin/gone.rs -->\n```rust\n```\n";
+        let err = sync(missing, source).unwrap_err();
+        assert_eq!(err.to_string(), "cannot read src/bin/gone.rs: no such file");
+    }
+}
@@ -0,0 +1,21 @@
+// lib.rs
+// Maintenance tools for the course repository.
+//   doc_sync   keeps code blocks in docs/ identical to the files they copy
+//   readme     generates the run-command list in README.md and README_zh.md
+//   snapshot   compares program output with a stored snapshot file
+// The `rcrash-tools` binary runs the first two; tests use all three.
+
+pub mod doc_sync;
+pub mod readme;
+pub mod snapshot;
+
+use std::path::{Path, PathBuf};
+
+/// The repository root, found from this crate's own location (crates/rcrash-tools).
+pub fn workspace_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .ancestors()
+        .nth(2)
+        .expect("rcrash-tools lives two levels below the workspace root")
+        .to_path_buf()
+}
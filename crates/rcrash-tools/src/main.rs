@@ -0,0 +1,49 @@
+// main.rs
+// Repository maintenance commands, run from anywhere in the workspace:
+//   cargo run -p rcrash-tools -- sync-docs [--check]
+//   cargo run -p rcrash-tools -- readme [--check]
+// With --check nothing is written, and the exit code is 1 if anything is stale.
+
+use rcrash_core::fatal::{die, usage_error, ExitCode};
+use rcrash_tools::{doc_sync, readme, workspace_root};
+use std::env;
+use std::path::PathBuf;
+
+const USAGE: &str = "\
+Usage: rcrash-tools <command> [--check]
+
+Commands:
+  sync-docs   Copy source files into docs code blocks marked with <!-- sync: path -->
+  readme      Regenerate the run-command list in README.md and README_zh.md";
+
+fn report(stale: Vec<PathBuf>, check: bool) {
+    let root = workspace_root();
+    for path in &stale {
+        let shown = path.strip_prefix(&root).unwrap_or(path).display();
+        println!("{} {}", if check { "stale  " } else { "updated" }, shown);
+    }
+    if check && !stale.is_empty() {
+        die(ExitCode::Failure, "files are out of date; run without --check to update them");
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (command, check) = match args.as_slice() {
+        [command] => (command.as_str(), false),
+        [command, flag] if flag == "--check" => (command.as_str(), true),
+        _ => usage_error(USAGE, "expected a command"),
+    };
+    let root = workspace_root();
+    match command {
+        "sync-docs" => match doc_sync::sync_docs(&root, check) {
+            Ok(stale) => report(stale, check),
+            Err(e) => die(ExitCode::IoErr, e),
+        },
+        "readme" => match readme::update_readmes(&root, check) {
+            Ok(stale) => report(stale, check),
+            Err(e) => die(ExitCode::DataErr, e),
+        },
+        other => usage_error(USAGE, format_args!("unknown command '{}'", other)),
+    }
+}
@@ -0,0 +1,182 @@
+// readme.rs
+// Generates the "run any chapter example" command list in the READMEs.
+//
+// The chapter table is the source of truth. For each row, in table order, the
+// commands are taken from the linked doc's closing "Run the code:" block. A row
+// whose doc has no such block but whose source is a chapter binary gets
+// `cargo run --bin <name>`. The list is written between two marker lines:
+//
+//   <!-- generated:run-commands -->
+//   ```bash
+//   ...
+//   ```
+//   <!-- /generated:run-commands -->
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub const BEGIN: &str = "<!-- generated:run-commands -->";
+pub const END: &str = "<!-- /generated:run-commands -->";
+pub const READMES: [&str; 2] = ["README.md", "README_zh.md"];
+
+#[derive(Debug)]
+pub enum ReadmeError {
+    /// The README has no complete BEGIN/END marker pair.
+    MissingMarkers,
+    Io { path: PathBuf, error: io::Error },
+}
+
+impl fmt::Display for ReadmeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReadmeError::MissingMarkers => write!(f, "missing `{}` / `{}` markers", BEGIN, END),
+            ReadmeError::Io { path, error } => write!(f, "{}: {}", path.display(), error),
+        }
+    }
+}
+
+/// One row of the chapter table: the link targets in its source and doc columns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    pub source: Option<String>,
+    pub doc: Option<String>,
+}
+
+/// The first Markdown link target in `cell`, as in `[text](target)`.
+fn link_target(cell: &str) -> Option<String> {
+    let start = cell.find("](")? + 2;
+    let end = start + cell[start..].find(')')?;
+    Some(cell[start..end].to_string())
+}
+
+/// Rows of the chapter table: `| no | title | [source](...) | [doc](...) |`.
+pub fn table_rows(readme: &str) -> Vec<Row> {
+    readme
+        .lines()
+        .filter_map(|line| {
+            let cells: Vec<&str> = line.strip_prefix('|')?.split('|').collect();
+            let [_, _, source, doc, ..] = cells.as_slice() else {
+                return None;
+            };
+            let doc = link_target(doc).filter(|d| d.starts_with("docs/"))?;
+            Some(Row { source: link_target(source), doc: Some(doc) })
+        })
+        .collect()
+}
+
+/// The commands in a doc's closing "Run the code:" ```sh block.
+pub fn run_block(doc: &str) -> Vec<String> {
+    let Some(start) = doc.rfind("Run the code:") else {
+        return Vec::new();
+    };
+    doc[start..]
+        .lines()
+        .skip_while(|line| !line.starts_with("```"))
+        .skip(1)
+        .take_while(|line| !line.starts_with("```"))
+        .filter(|line| !line.trim().is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// The command list for `rows`, reading docs through `read_doc`. Repeated
+/// commands are listed once, at their first position.
+pub fn run_commands(rows: &[Row], mut read_doc: impl FnMut(&str) -> Option<String>) -> Vec<String> {
+    let mut commands: Vec<String> = Vec::new();
+    for row in rows {
+        let mut row_commands = row.doc.as_deref().and_then(&mut read_doc).map(|d| run_block(&d)).unwrap_or_default();
+        if row_commands.is_empty()
+            && let Some(name) = row.source.as_deref().and_then(|s| s.strip_prefix("src/bin/")?.strip_suffix(".rs"))
+        {
+            row_commands.push(format!("cargo run --bin {}", name));
+        }
+        for command in row_commands {
+            if !commands.contains(&command) {
+                commands.push(command);
+            }
+        }
+    }
+    commands
+}
+
+/// Replaces the text between the markers with a ```bash block of `commands`.
+pub fn replace_generated(readme: &str, commands: &[String]) -> Result<String, ReadmeError> {
+    let begin = readme.find(BEGIN).ok_or(ReadmeError::MissingMarkers)? + BEGIN.len();
+    let end = begin + readme[begin..].find(END).ok_or(ReadmeError::MissingMarkers)?;
+    let mut block = String::from("\n```bash\n");
+    for command in commands {
+        block.push_str(command);
+        block.push('\n');
+    }
+    block.push_str("```\n");
+    Ok(format!("{}{}{}", &readme[..begin], block, &readme[end..]))
+}
+
+/// Regenerates the command list in both READMEs under `root`. Returns the
+/// READMEs that were out of date; with `check` set they are not rewritten.
+pub fn update_readmes(root: &Path, check: bool) -> Result<Vec<PathBuf>, ReadmeError> {
+    let mut stale = Vec::new();
+    for name in READMES {
+        let path = root.join(name);
+        let text = fs::read_to_string(&path).map_err(|error| ReadmeError::Io { path: path.clone(), error })?;
+        let commands = run_commands(&table_rows(&text), |doc| fs::read_to_string(root.join(doc)).ok());
+        let updated = replace_generated(&text, &commands)?;
+        if updated != text {
+            if !check {
+                fs::write(&path, &updated).map_err(|error| ReadmeError::Io { path: path.clone(), error })?;
+            }
+            stale.push(path);
+        }
+    }
+    Ok(stale)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const README: &str = "\
+intro
+<!-- generated:run-commands -->
+old
+<!-- /generated:run-commands -->
+
+| No.  | Title | Source | Doc |
+|------|-------|--------|-----|
+| 1.1  | One   | [a.rs](src/bin/a.rs) | [a.md](docs/a.md) |
+| 1.2  | Two   | [lib.rs](crates/x/src/lib.rs) | [b.md](docs/b.md) |
+| 1.3  | Three | —      | [c.md](docs/c.md) |
+";
+
+    fn doc(path: &str) -> Option<String> {
+        match path {
+            "docs/a.md" => Some("# A\n".to_string()),
+            "docs/b.md" => Some("text\n---\n\nRun the code:\n\n```sh\ncargo run -- b\ncargo test --lib b\n```\n".to_string()),
+            "docs/c.md" => Some("Run the code:\n\n```sh\ncargo run -- b\n```\n".to_string()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn commands_follow_the_table() {
+        let rows = table_rows(README);
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0].source.as_deref(), Some("src/bin/a.rs"));
+        assert_eq!(rows[2].source, None);
+        let commands = run_commands(&rows, doc);
+        assert_eq!(commands, ["cargo run --bin a", "cargo run -- b", "cargo test --lib b"]);
+    }
+
+    #[test]
+    fn replaces_between_markers() {
+        let commands = vec!["cargo run --bin a".to_string()];
+        let updated = replace_generated(README, &commands).unwrap();
+        assert!(updated.starts_with(
+            "intro\n<!-- generated:run-commands -->\n```bash\ncargo run --bin a\n```\n<!-- /generated:run-commands -->\n"
+        ));
+        assert_eq!(replace_generated(&updated, &commands).unwrap(), updated);
+        assert!(matches!(replace_generated("no markers", &commands), Err(ReadmeError::MissingMarkers)));
+    }
+}
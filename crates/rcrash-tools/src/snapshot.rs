@@ -0,0 +1,70 @@
+// snapshot.rs
+// Snapshot testing: compare a program's output with a stored copy.
+// Set UPDATE_SNAPSHOTS=1 to (re)write the stored copies instead of comparing.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+/// Compares `actual` with `dir/name.txt`, panicking at the first differing line.
+/// With UPDATE_SNAPSHOTS=1, writes `actual` as the new snapshot instead.
+pub fn assert_snapshot(dir: &Path, name: &str, actual: &str) {
+    let update = env::var_os("UPDATE_SNAPSHOTS").is_some_and(|v| v == "1");
+    check_snapshot(dir, name, actual, update);
+}
+
+fn check_snapshot(dir: &Path, name: &str, actual: &str, update: bool) {
+    let path = dir.join(format!("{}.txt", name));
+    if update {
+        fs::create_dir_all(dir).unwrap_or_else(|e| panic!("{}: {}", dir.display(), e));
+        fs::write(&path, actual).unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+        return;
+    }
+    // A missing file must fail, or a deleted snapshot would pass without checking anything
+    if !path.exists() {
+        panic!("{}: missing snapshot, rerun with UPDATE_SNAPSHOTS=1", path.display());
+    }
+    let expected = fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+    if let Some(message) = first_difference(&expected, actual) {
+        panic!("{} does not match the snapshot {}\n{}\n(rerun with UPDATE_SNAPSHOTS=1 to accept the new output)", name, path.display(), message);
+    }
+}
+
+/// Describes the first line where the two texts differ, or `None` if they are equal.
+fn first_difference(expected: &str, actual: &str) -> Option<String> {
+    if expected == actual {
+        return None;
+    }
+    let (mut expected_lines, mut actual_lines) = (expected.lines(), actual.lines());
+    for line in 1.. {
+        match (expected_lines.next(), actual_lines.next()) {
+            (Some(e), Some(a)) if e == a => continue,
+            (None, None) => return Some("the texts differ only in line endings".to_string()),
+            (e, a) => {
+                let show = |l: Option<&str>| l.map_or("<end of output>".to_string(), |l| format!("{:?}", l));
+                return Some(format!("line {}:\n  expected {}\n  actual   {}", line, show(e), show(a)));
+            }
+        }
+    }
+    unreachable!("the loop returns at the first difference")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_the_first_differing_line() {
+        assert_eq!(first_difference("a\nb\n", "a\nb\n"), None);
+        assert_eq!(first_difference("a\nb\n", "a\nc\n").unwrap(), "line 2:\n  expected \"b\"\n  actual   \"c\"");
+        assert_eq!(first_difference("a\n", "a\nb\n").unwrap(), "line 2:\n  expected <end of output>\n  actual   \"b\"");
+        assert_eq!(first_difference("a\n", "a").unwrap(), "the texts differ only in line endings");
+    }
+
+    #[test]
+    #[should_panic(expected = "missing snapshot, rerun with UPDATE_SNAPSHOTS=1")]
+    fn a_missing_snapshot_fails() {
+        let dir = env::temp_dir().join("rcrash-tools-no-snapshots");
+        check_snapshot(&dir, "absent", "output\n", false);
+    }
+}
@@ -0,0 +1,17 @@
+// repo.rs
+// Fails when a synced doc or the generated README command list is out of date.
+// Fix with `cargo run -p rcrash-tools -- sync-docs` / `-- readme`.
+
+use rcrash_tools::{doc_sync, readme, workspace_root};
+
+#[test]
+fn docs_are_in_sync_with_sources() {
+    let stale = doc_sync::sync_docs(&workspace_root(), true).unwrap();
+    assert!(stale.is_empty(), "out of date, run `cargo run -p rcrash-tools -- sync-docs`: {:?}", stale);
+}
+
+#[test]
+fn readme_run_commands_are_current() {
+    let stale = readme::update_readmes(&workspace_root(), true).unwrap();
+    assert!(stale.is_empty(), "out of date, run `cargo run -p rcrash-tools -- readme`: {:?}", stale);
+}
@@ -0,0 +1,22 @@
+// checkout.rs
+// Files in benches/ are run by `cargo bench`. With `harness = false` in
+// Cargo.toml this is a plain `main`, so it works on stable Rust without a
+// benchmarking crate.
+
+use project_layout::{checkout, Config, LineItem, UserRegistry};
+use std::hint::black_box;
+use std::time::Instant;
+
+fn main() {
+    let mut users = UserRegistry::new();
+    let user = users.register("bench user").expect("valid name").clone();
+    let items: Vec<LineItem> = (0..100).map(|i| LineItem::new("item", 100 + i)).collect();
+    let config = Config::default();
+
+    const RUNS: u32 = 100_000;
+    let start = Instant::now();
+    for _ in 0..RUNS {
+        black_box(checkout(black_box(&user), black_box(&items), &config)).expect("small amounts");
+    }
+    println!("checkout of 100 items: {:?} per run", start.elapsed() / RUNS);
+}
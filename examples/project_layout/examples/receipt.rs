@@ -0,0 +1,18 @@
+// receipt.rs
+// Files in examples/ are extra binaries that use the library like an outside
+// crate would. Run with: cargo run -p project-layout --example receipt
+
+use project_layout::{checkout, Config, LineItem, UserRegistry};
+
+fn main() {
+    let mut users = UserRegistry::new();
+    for name in ["grace hopper", "alan turing"] {
+        users.register(name).expect("distinct names");
+    }
+    let config = Config { currency: "GBP".to_string(), tax_basis_points: 1750 };
+    let items = [LineItem::new("Compiler", 9900), LineItem::new("Bug jar", 350)];
+    for id in 1..=2 {
+        let user = users.find(id).expect("registered above");
+        print!("{}", checkout(user, &items[..id as usize], &config).expect("small amounts"));
+    }
+}
@@ -0,0 +1,16 @@
+// config.rs
+// A flat module: `mod config;` in lib.rs loads this file.
+
+/// Shop settings. Money is kept in cents to avoid floating-point rounding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    pub currency: String,
+    /// Tax in hundredths of a percent: 2000 is 20%.
+    pub tax_basis_points: u32,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config { currency: "EUR".to_string(), tax_basis_points: 2000 }
+    }
+}
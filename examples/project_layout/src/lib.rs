@@ -0,0 +1,39 @@
+// lib.rs
+// A small shop that exists to show the project layout from ch05_08.
+//
+// Module tree and the file each module lives in:
+//   crate                      src/lib.rs
+//   ├── config                 src/config.rs                flat module (foo.rs style)
+//   ├── utils                  src/utils/mod.rs             folder module (mod.rs style)
+//   │   ├── money              src/utils/money.rs
+//   │   └── text               src/utils/text.rs
+//   └── services               src/services.rs              folder module (bar.rs + bar/ style)
+//       ├── payment            src/services/payment.rs
+//       └── user               src/services/user.rs         (bar.rs + bar/ style again)
+//           └── registry       src/services/user/registry.rs
+//
+// `config` and `utils` are private. The public API is re-exported below, so
+// users write `project_layout::Config` and never depend on where it lives.
+
+mod config;
+mod utils;
+pub mod services;
+
+pub use config::Config;
+pub use services::payment::{checkout, Invoice, LineItem};
+pub use services::user::registry::{RegisterError, UserRegistry};
+pub use services::user::User;
+
+/// Registers a demo customer and checks out `items` (`name=cents`, e.g.
+/// `book=1299`), returning the printed invoice. Used by this crate's binary and
+/// by the rcrash launcher (`cargo run -- layout`).
+pub fn demo(items: &[String]) -> Result<String, String> {
+    let mut items: Vec<LineItem> = items.iter().map(|text| text.parse()).collect::<Result<_, _>>()?;
+    if items.is_empty() {
+        items = vec![LineItem::new("Rust Book", 3999), LineItem::new("Ferris plush", 1550)];
+    }
+    let mut users = UserRegistry::new();
+    let user = users.register("ada lovelace").map_err(|e| e.to_string())?;
+    let invoice = checkout(user, &items, &Config::default())?;
+    Ok(invoice.to_string())
+}
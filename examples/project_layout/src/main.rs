@@ -0,0 +1,17 @@
+// main.rs
+// Binary entry point: a thin wrapper around the library, as ch05_08 recommends.
+// Usage: cargo run -p project-layout -- [name=cents ...]
+
+use std::env;
+use std::process;
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match project_layout::demo(&args) {
+        Ok(invoice) => print!("{}", invoice),
+        Err(e) => {
+            eprintln!("error: {}", e);
+            process::exit(1);
+        }
+    }
+}
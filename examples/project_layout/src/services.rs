@@ -0,0 +1,6 @@
+// services.rs
+// A folder module in the newer style: `pub mod services;` in lib.rs loads this
+// file, and its submodules live in src/services/. No mod.rs is needed.
+
+pub mod payment;
+pub mod user;
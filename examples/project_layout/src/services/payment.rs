@@ -0,0 +1,78 @@
+// services/payment.rs
+// A plain file inside the services/ folder: `pub mod payment;` in services.rs.
+
+use super::user::User;
+use crate::config::Config;
+use crate::utils::money::{format_cents, share};
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineItem {
+    pub name: String,
+    pub cents: u64,
+}
+
+impl LineItem {
+    pub fn new(name: &str, cents: u64) -> LineItem {
+        LineItem { name: name.to_string(), cents }
+    }
+}
+
+/// Parses `name=cents`, e.g. `book=1299`.
+impl FromStr for LineItem {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<LineItem, String> {
+        let (name, cents) = s.split_once('=').ok_or_else(|| format!("`{}`: expected name=cents", s))?;
+        let cents = cents.parse().map_err(|e| format!("`{}`: {}", s, e))?;
+        Ok(LineItem::new(name, cents))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Invoice {
+    pub customer: String,
+    pub customer_id: u32,
+    pub items: Vec<LineItem>,
+    pub subtotal: u64,
+    pub tax: u64,
+    pub currency: String,
+}
+
+impl Invoice {
+    pub fn total(&self) -> u64 {
+        self.subtotal + self.tax
+    }
+}
+
+/// Builds the invoice, or an error if the subtotal, tax or total would not fit
+/// in a `u64` of cents.
+pub fn checkout(user: &User, items: &[LineItem], config: &Config) -> Result<Invoice, String> {
+    let overflow = || format!("checkout for {}: the amount does not fit in a u64 of cents", user.name);
+    let subtotal = items.iter().try_fold(0u64, |sum, item| sum.checked_add(item.cents)).ok_or_else(overflow)?;
+    let tax = share(subtotal, config.tax_basis_points).ok_or_else(overflow)?;
+    // Checked once here, so `Invoice::total` cannot overflow
+    subtotal.checked_add(tax).ok_or_else(overflow)?;
+    Ok(Invoice {
+        customer: user.name.clone(),
+        // Reading the pub(super) field directly: `payment` is inside `services`
+        customer_id: user.id,
+        items: items.to_vec(),
+        subtotal,
+        tax,
+        currency: config.currency.clone(),
+    })
+}
+
+impl fmt::Display for Invoice {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Invoice for {} (#{})", self.customer, self.customer_id)?;
+        for item in &self.items {
+            writeln!(f, "  {:<20} {:>10}", item.name, format_cents(item.cents))?;
+        }
+        writeln!(f, "  {:<20} {:>10}", "subtotal", format_cents(self.subtotal))?;
+        writeln!(f, "  {:<20} {:>10}", "tax", format_cents(self.tax))?;
+        writeln!(f, "  {:<20} {:>10} {}", "total", format_cents(self.total()), self.currency)
+    }
+}
@@ -0,0 +1,18 @@
+// services/user.rs
+// A module with its own submodule: `registry` lives in src/services/user/registry.rs.
+
+pub mod registry;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct User {
+    /// Only the registry hands out ids, so the field is not `pub`.
+    /// `pub(super)` lets `services` (and so `payment`) read it.
+    pub(super) id: u32,
+    pub name: String,
+}
+
+impl User {
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+}
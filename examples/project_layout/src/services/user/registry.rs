@@ -0,0 +1,60 @@
+// services/user/registry.rs
+
+use super::User;
+use crate::utils::text::title_case;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegisterError {
+    EmptyName,
+    Duplicate(String),
+}
+
+impl fmt::Display for RegisterError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RegisterError::EmptyName => write!(f, "a user needs a name"),
+            RegisterError::Duplicate(name) => write!(f, "user `{}` already exists", name),
+        }
+    }
+}
+
+/// Hands out user ids in registration order, starting at 1.
+#[derive(Debug, Default)]
+pub struct UserRegistry {
+    users: Vec<User>,
+}
+
+impl UserRegistry {
+    pub fn new() -> UserRegistry {
+        UserRegistry::default()
+    }
+
+    /// Registers `name`, normalised to title case.
+    pub fn register(&mut self, name: &str) -> Result<&User, RegisterError> {
+        let name = title_case(name);
+        if name.is_empty() {
+            return Err(RegisterError::EmptyName);
+        }
+        if self.users.iter().any(|u| u.name == name) {
+            return Err(RegisterError::Duplicate(name));
+        }
+        let id = self.users.len() as u32 + 1;
+        // Setting `id` works here: it is pub(super) in `user`, so visible in all
+        // of `services`, and this module is inside `services`
+        self.users.push(User { id, name });
+        Ok(self.users.last().expect("just pushed"))
+    }
+
+    pub fn find(&self, id: u32) -> Option<&User> {
+        self.users.iter().find(|u| u.id == id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.users.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.users.is_empty()
+    }
+}
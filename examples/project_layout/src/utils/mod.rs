@@ -0,0 +1,9 @@
+// utils/mod.rs
+// A folder module in the older mod.rs style: `mod utils;` in lib.rs loads this
+// file, and the `mod` lines below load the other files in src/utils/.
+//
+// `utils` is private to the crate, so `pub` here means "visible to the rest of
+// this crate" and nothing leaks into the public API.
+
+pub mod money;
+pub mod text;
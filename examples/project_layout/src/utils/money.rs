@@ -0,0 +1,14 @@
+// utils/money.rs
+
+/// `1299` cents as `12.99`.
+pub fn format_cents(cents: u64) -> String {
+    format!("{}.{:02}", cents / 100, cents % 100)
+}
+
+/// `basis_points / 10000` of `cents`, rounded half up, or `None` if the result
+/// does not fit in a `u64`. The product is taken in `u128`, where it cannot
+/// overflow.
+pub fn share(cents: u64, basis_points: u32) -> Option<u64> {
+    let scaled = (u128::from(cents) * u128::from(basis_points) + 5_000) / 10_000;
+    u64::try_from(scaled).ok()
+}
@@ -0,0 +1,15 @@
+// utils/text.rs
+
+/// Capitalises the first letter of each word: `ada lovelace` -> `Ada Lovelace`.
+pub fn title_case(s: &str) -> String {
+    s.split_whitespace()
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars.flat_map(char::to_lowercase)).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
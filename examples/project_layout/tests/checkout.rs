@@ -0,0 +1,50 @@
+// checkout.rs
+
+mod common;
+
+use project_layout::{checkout, Config, LineItem};
+
+#[test]
+fn invoice_adds_tax() {
+    let registry = common::registry_with(&["ada"]);
+    let invoice = checkout(common::first_user(&registry), &common::items(&[("a", 1000), ("b", 250)]), &Config::default()).unwrap();
+    assert_eq!((invoice.subtotal, invoice.tax, invoice.total()), (1250, 250, 1500));
+    assert_eq!(invoice.customer_id, 1);
+
+    let half = Config { currency: "USD".to_string(), tax_basis_points: 5 };
+    let invoice = checkout(common::first_user(&registry), &common::items(&[("a", 1000)]), &half).unwrap();
+    // 0.05% of 10.00 is half a cent, which rounds up
+    assert_eq!(invoice.tax, 1);
+    assert!(invoice.to_string().ends_with("10.01 USD\n"));
+}
+
+#[test]
+fn demo_parses_items() {
+    let out = project_layout::demo(&["book=1299".to_string()]).unwrap();
+    assert!(out.starts_with("Invoice for Ada Lovelace (#1)\n"));
+    assert!(out.contains("book"));
+    assert!(out.contains("15.59 EUR"));
+    assert_eq!("pen=x".parse::<LineItem>().unwrap_err(), "`pen=x`: invalid digit found in string");
+    assert!(project_layout::demo(&["pen".to_string()]).is_err());
+}
+
+#[test]
+fn huge_amounts_are_an_error_not_a_panic() {
+    let registry = common::registry_with(&["ada"]);
+    let user = common::first_user(&registry);
+    let config = Config::default();
+
+    // The tax product needs more than 64 bits, but the tax itself fits
+    let invoice = checkout(user, &common::items(&[("a", 10_000_000_000_000_000)]), &config).unwrap();
+    assert_eq!(invoice.tax, 2_000_000_000_000_000);
+
+    // Subtotal, tax and total each overflow on their own
+    assert!(checkout(user, &common::items(&[("a", u64::MAX), ("b", 1)]), &config).is_err());
+    assert!(checkout(user, &common::items(&[("a", u64::MAX)]), &Config { tax_basis_points: 20_000, ..Config::default() }).is_err());
+    // 20% tax on 90% of u64::MAX fits, but the total does not
+    assert!(checkout(user, &common::items(&[("a", u64::MAX / 10 * 9)]), &config).is_err());
+
+    let err = project_layout::demo(&[format!("a={}", u64::MAX), "b=1".to_string()]).unwrap_err();
+    assert!(err.contains("does not fit"), "{}", err);
+    assert!(project_layout::demo(&["book=10000000000000000".to_string()]).is_ok());
+}
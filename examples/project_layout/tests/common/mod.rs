@@ -0,0 +1,27 @@
+// common/mod.rs
+// Helpers shared by the integration tests. Each file directly in tests/ is
+// compiled as its own crate, so a helper file there would be run as a test crate
+// too. A folder with mod.rs is not, so this is where shared code goes.
+// Each test file pulls it in with `mod common;`.
+
+// Each test crate compiles its own copy, and not every file uses every helper
+#![allow(dead_code)]
+
+use project_layout::{LineItem, User, UserRegistry};
+
+/// A registry with the given users registered in order.
+pub fn registry_with(names: &[&str]) -> UserRegistry {
+    let mut registry = UserRegistry::new();
+    for name in names {
+        registry.register(name).expect("test names are valid and distinct");
+    }
+    registry
+}
+
+pub fn first_user(registry: &UserRegistry) -> &User {
+    registry.find(1).expect("registry has a first user")
+}
+
+pub fn items(pairs: &[(&str, u64)]) -> Vec<LineItem> {
+    pairs.iter().map(|&(name, cents)| LineItem::new(name, cents)).collect()
+}
@@ -0,0 +1,24 @@
+// users.rs
+// Integration tests see only the public API, like any other crate would:
+// `project_layout::utils` is private and cannot be named here.
+
+mod common;
+
+use project_layout::{RegisterError, UserRegistry};
+
+#[test]
+fn ids_follow_registration_order() {
+    let registry = common::registry_with(&["ada", "grace hopper", "alan"]);
+    assert_eq!(registry.len(), 3);
+    assert_eq!(registry.find(2).map(|u| u.name.as_str()), Some("Grace Hopper"));
+    assert_eq!(common::first_user(&registry).id(), 1);
+    assert!(registry.find(4).is_none());
+}
+
+#[test]
+fn names_are_validated() {
+    let mut registry = UserRegistry::new();
+    assert_eq!(registry.register("   "), Err(RegisterError::EmptyName));
+    registry.register("ada lovelace").unwrap();
+    assert_eq!(registry.register("ADA LOVELACE"), Err(RegisterError::Duplicate("Ada Lovelace".to_string())));
+}
@@ -64,4 +64,19 @@ fn main() {
     print_message(&m2);
     print_message(&m3);
     print_message(&m4);
+    // 4. Converting both ways with a library enum (see crates/rcrash-core/src/status.rs)
+    use rcrash_core::status::Status as HttpStatus;
+    let code: u16 = HttpStatus::NotFound.into();
+    println!("{} -> {}", HttpStatus::NotFound, code);
+    match HttpStatus::try_from(503) {
+        Ok(s) => println!("503 -> {}, server error: {}", s.reason(), s.is_server_error()),
+        Err(e) => println!("503 -> error: {}", e),
+    }
+    match "418 I'm a teapot".parse::<HttpStatus>() {
+        Ok(s) => println!("parsed {:?}, client error: {}", s, s.is_client_error()),
+        Err(e) => println!("parse error: {}", e),
+    }
+    if let Err(e) = HttpStatus::try_from(299) {
+        println!("299 -> error: {}", e);
+    }
 }
@@ -0,0 +1,63 @@
+// ch03_11_message_repl.rs
+// 3.11 Enums: a command REPL
+// Reads commands such as `move 10 20`, parses them into `Message` values
+// and dispatches each one to a `Canvas` state machine.
+
+use rcrash_core::message::{Canvas, Event, Message};
+use std::io::{self, BufRead, Write};
+
+fn print_event(event: &Event) {
+    match event {
+        Event::Moved { from, to } => println!("cursor {:?} -> {:?}", from, to),
+        Event::Wrote(stroke) => println!(
+            "wrote {:?} at {:?} in rgb{:?}",
+            stroke.text, stroke.at, stroke.color
+        ),
+        Event::ColorChanged { from, to } => println!("color rgb{:?} -> rgb{:?}", from, to),
+        Event::Stopped => println!("Bye!"),
+    }
+}
+
+fn main() {
+    println!("Commands: quit | move <x> <y> | write \"<text>\" | color <r> <g> <b>");
+    let mut canvas = Canvas::new();
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+
+    while canvas.is_running() {
+        print!("> ");
+        io::stdout().flush().expect("Failed to flush stdout");
+        let line = match lines.next() {
+            Some(Ok(line)) => line,
+            Some(Err(e)) => {
+                eprintln!("Failed to read line: {}", e);
+                break;
+            }
+            None => break, // EOF behaves like quit
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let message: Message = match line.parse() {
+            Ok(m) => m,
+            Err(e) => {
+                // Point a caret at the offending column, below the prompt
+                if let Some(column) = e.column() {
+                    println!("  {}^", " ".repeat(column - 1));
+                }
+                println!("error: {}", e);
+                continue;
+            }
+        };
+        match canvas.apply(message) {
+            Ok(event) => print_event(&event),
+            Err(e) => println!("error: {}", e),
+        }
+    }
+
+    println!("Final cursor: {:?}, color: rgb{:?}", canvas.cursor(), canvas.color());
+    for stroke in canvas.strokes() {
+        println!("  {:?} at {:?} in rgb{:?}", stroke.text, stroke.at, stroke.color);
+    }
+}
@@ -54,9 +54,24 @@ fn main() {
     let val: Result<u8, _> = 300i32.try_into(); // will Err (overflow)
     println!("300i32.try_into::<u8>(): {:?}", val);
 
-    // --- std::mem::transmute (DANGEROUS!) ---
-    use std::mem::transmute;
+    // --- Numbers to bytes: safe and with an explicit byte order ---
+    // (instead of `unsafe { transmute::<u32, [u8; 4]>(a) }`, whose result depends on the platform)
+    use rcrash_core::codec::{self, ByteReader, ByteWriter, Endian};
     let a: u32 = 0x61626364;
-    let b: [u8; 4] = unsafe { transmute(a) };
-    println!("transmute u32 to [u8;4]: {:?}", b); // platform-dependent
+    println!("u32 to big-endian bytes: {:?}", a.to_be_bytes()); // always [97, 98, 99, 100]
+    println!("u32 to little-endian bytes: {:?}", a.to_le_bytes()); // always [100, 99, 98, 97]
+    println!("back from big-endian: {:#x}", u32::from_be_bytes(*b"abcd"));
+    println!("f32 1.0 as big-endian bytes: {:02x?}", codec::to_be_bytes(1.0f32));
+    println!("from 3 bytes: {:?}", codec::from_le_bytes::<u32>(&[1, 2, 3])); // Err, not UB
+
+    let mut buf = [0u8; 8];
+    let mut writer = ByteWriter::new(&mut buf, Endian::Big);
+    writer.write(0x0102u16).unwrap();
+    writer.write_uleb128(300).unwrap(); // varint: 2 bytes instead of 8
+    println!("written: {:02x?}", writer.written());
+
+    let mut reader = ByteReader::new(&buf, Endian::Big);
+    let x: u16 = reader.read().unwrap();
+    let y = reader.read_uleb128().unwrap();
+    println!("read back: {:#06x}, {}; reading a u64 now: {:?}", x, y, reader.read::<u64>());
 }
@@ -1,3 +1,5 @@
+use rcrash_core::type_inspect::{inspect_float, next_up, FloatLayout};
+
 fn main() {
     // Integer types (default: i32)
     let a = -42; // i32 by default
@@ -17,6 +19,13 @@ fn main() {
     let f32_long = 1.2345678901234567890_f32;
     println!("f64_long = {:.20} (f64, more precision)", f64_long);
     println!("f32_long = {:.20} (f32, less precision)", f32_long);
+    // Why: f32 keeps 23 mantissa bits, f64 keeps 52. The stored value is the
+    // nearest number of the form 1.mantissa × 2^exponent.
+    let f32_bits = FloatLayout::of(f32_long);
+    let f64_bits = FloatLayout::of(f64_long);
+    println!("f32_long bits = {} -> exactly {}", f32_bits.bit_string(), inspect_float(f32_long).exact.unwrap());
+    println!("f64_long bits = {} -> exactly {}", f64_bits.bit_string(), inspect_float(f64_long).exact.unwrap());
+    println!("gap to the next f32 = {:e}, next f64 = {:e}", next_up(f32_long) - f32_long, next_up(f64_long) - f64_long);
 
     // Boolean type (default: bool)
     let is_active = true;
@@ -0,0 +1,70 @@
+// ch03_6_overflow_calc.rs
+// 3.6 Integer overflow: a calculator REPL
+// Evaluates expressions such as `200 * 2` in any integer type under one of the
+// five overflow policies, and shows the result and the overflow flag.
+
+use rcrash_core::int_type::IntType;
+use rcrash_core::overflow_calc::{self, Policy};
+use std::io::{self, BufRead, Write};
+
+const HELP: &str = "\
+Enter an expression (+ - * / % << >> ** and parentheses), or:
+  type <t>          i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize
+  policy <p>        panic | wrapping | checked | saturating | overflowing
+  compare <expr>    evaluate under every policy
+  help | quit";
+
+fn evaluate(ty: IntType, policies: &[Policy], input: &str) {
+    let expr = match overflow_calc::parse(input) {
+        Ok(expr) => expr,
+        Err(e) => {
+            println!("error: {}", e);
+            return;
+        }
+    };
+    for &policy in policies {
+        match overflow_calc::evaluate(ty, policy, &expr) {
+            Ok(report) => println!("{}", report),
+            Err(e) => println!("{:<11} error: {}", policy.name(), e),
+        }
+    }
+}
+
+fn main() {
+    println!("{}", HELP);
+    let mut ty = IntType::U8;
+    let mut policy = Policy::Wrapping;
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+
+    loop {
+        print!("[{} {}]> ", ty.name(), policy.name());
+        io::stdout().flush().expect("Failed to flush stdout");
+        let line = match lines.next() {
+            Some(Ok(line)) => line,
+            Some(Err(e)) => {
+                eprintln!("Failed to read line: {}", e);
+                break;
+            }
+            None => break, // EOF behaves like quit
+        };
+        let line = line.trim();
+        let (command, rest) = line.split_once(' ').unwrap_or((line, ""));
+
+        match command {
+            "" => {}
+            "quit" | "exit" => break,
+            "help" => println!("{}", HELP),
+            "type" => match overflow_calc::parse_type(rest.trim()) {
+                Ok(t) => ty = t,
+                Err(e) => println!("error: {}", e),
+            },
+            "policy" => match rest.trim().parse() {
+                Ok(p) => policy = p,
+                Err(e) => println!("error: {}", e),
+            },
+            "compare" => evaluate(ty, &Policy::ALL, rest),
+            _ => evaluate(ty, &[policy], line),
+        }
+    }
+}
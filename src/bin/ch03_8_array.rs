@@ -1,3 +1,5 @@
+use rcrash_core::index_parse;
+
 fn main() {
     // Creating an array with explicit type and length
     let mut arr: [i32; 5] = [1, 2, 3, 4, 5];
@@ -61,4 +63,21 @@ fn main() {
     } else {
         println!("No second character");
     }
+
+    // The same checks as a reusable helper: parse, bounds-check and slice in one
+    // call, with a typed error instead of is_ok()/unwrap()
+    for input in ["2", "-1", "1..3", "..=2", "7", "3..1", "x"] {
+        match index_parse::get_range(&arr, input) {
+            Ok(items) => println!("arr[{}] -> {:?}", input, items),
+            Err(e) => println!("arr[{}] -> error: {}", input, e),
+        }
+    }
+    // For strings the helper also refuses to cut a character in half
+    let word = "héllo";
+    for input in ["0..3", "0..2"] {
+        match index_parse::str_range(word, input) {
+            Ok(part) => println!("{:?}[{}] -> {:?}", word, input, part),
+            Err(e) => println!("{:?}[{}] -> error: {}", word, input, e),
+        }
+    }
 }
@@ -0,0 +1,39 @@
+// ch03_8_matrix.rs
+// 3.8 Arrays: nested arrays as a const-generic matrix
+// `[[T; C]; R]` has its size in its type, so a Matrix built on it can check
+// dimensions at compile time.
+
+use rcrash_core::matrix::Matrix;
+
+fn main() {
+    // A 2×3 matrix is a [[i32; 3]; 2] underneath
+    let a = Matrix::new([[1, 2, 3], [4, 5, 6]]);
+    println!("a (2×3):\n{}", a);
+    println!("a[(1, 2)] = {}", a[(1, 2)]);
+    println!("a.get(2, 0) = {:?} (out of bounds)", a.get(2, 0));
+
+    // Transpose swaps the const parameters: Matrix<i32, 2, 3> -> Matrix<i32, 3, 2>
+    let t = a.transpose();
+    println!("a transposed (3×2):\n{}", t);
+
+    // (2×3) · (3×2) = 2×2
+    let product = a * t;
+    println!("a · aᵀ (2×2):\n{}", product);
+
+    // Element-wise add and sub need identical shapes
+    let b = Matrix::new([[10, 20, 30], [40, 50, 60]]);
+    println!("a + b:\n{}", a + b);
+    println!("b - a:\n{}", b - a);
+
+    // Identity only exists for square matrices
+    let id: Matrix<f64, 3, 3> = Matrix::identity();
+    println!("identity (3×3), printed with {{:.1}}:\n{:.1}", id);
+
+    // Mutating through IndexMut
+    let mut rotation = Matrix::new([[0.0, -1.0], [1.0, 0.0]]);
+    rotation[(0, 0)] = 0.5;
+    println!("rotation after rotation[(0, 0)] = 0.5:\n{:.2}", rotation);
+
+    // This would not compile: a 2×3 times a 2×3 has no matching inner dimension
+    // let bad = a * b;
+}
@@ -1,3 +1,5 @@
+use rcrash_core::index_parse::str_range;
+
 fn main() {
     // Array slice
     let arr = [10, 20, 30, 40, 50];
@@ -24,4 +26,19 @@ fn main() {
     println!("String: {}", s);
     println!("First word: {}", hello);
     println!("Rest of string: {}", rest);
+
+    // The same slices through index_parse::str_range, which returns an error
+    // instead of panicking when a range is out of bounds or cuts a character
+    for input in ["0..5", "7..", "0..50"] {
+        match str_range(&s, input) {
+            Ok(part) => println!("s[{}] -> {:?}", input, part),
+            Err(e) => println!("s[{}] -> error: {}", input, e),
+        }
+    }
+    // 'é' takes two bytes, so byte 2 is inside it; &word[0..2] would panic
+    let word = "héllo";
+    match str_range(word, "0..2") {
+        Ok(part) => println!("{:?}[0..2] -> {:?}", word, part),
+        Err(e) => println!("{:?}[0..2] -> error: {}", word, e),
+    }
 }
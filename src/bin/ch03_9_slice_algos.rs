@@ -0,0 +1,52 @@
+// ch03_9_slice_algos.rs
+// 3.9 Slices: hand-written algorithms on &[T] and &mut [T]
+// Each call is printed next to its std equivalent.
+
+use rcrash_core::slice_algos;
+
+fn main() {
+    // Borrow-splitting: two &mut views into one array, checked by the compiler
+    let mut arr = [1, 2, 3, 4, 5, 6];
+    let (left, right) = arr.split_at_mut(3);
+    left[0] = 10;
+    right[0] = 40;
+    std::mem::swap(&mut left[2], &mut right[2]);
+    println!("split_at_mut(3), then write to both halves: {:?}", arr);
+
+    // Binary search on a sorted slice
+    let sorted = [1, 3, 5, 7, 9, 11];
+    for target in [7, 8] {
+        println!(
+            "binary_search({:?}, {}) = {:?} (std: {:?})",
+            sorted,
+            target,
+            slice_algos::binary_search(&sorted, &target),
+            sorted.binary_search(&target)
+        );
+    }
+
+    // In-place reverse and rotate
+    let mut letters = ['a', 'b', 'c', 'd', 'e'];
+    slice_algos::reverse(&mut letters);
+    println!("reverse: {:?}", letters);
+    slice_algos::rotate_left(&mut letters, 2);
+    println!("rotate_left by 2: {:?}", letters);
+    slice_algos::rotate_right(&mut letters, 2);
+    println!("rotate_right by 2: {:?}", letters);
+
+    // Partition a mutable slice in place
+    let mut numbers = [7, 2, 9, 4, 1, 8, 6];
+    let evens = slice_algos::partition(&mut numbers, |x| x % 2 == 0);
+    let (even, odd) = numbers.split_at(evens);
+    println!("partition by even: {:?} | {:?}", even, odd);
+
+    // Sliding-window maximum
+    let temps = [3, 1, 4, 1, 5, 9, 2, 6];
+    println!("window max (k = 3) of {:?}: {:?}", temps, slice_algos::sliding_window_max(&temps, 3));
+
+    // First word, as a &str borrowed from the original String
+    let s = String::from("  Hello, Rustaceans!");
+    println!("first_word({:?}) = {:?}", s, slice_algos::first_word(&s));
+    println!("split_first_word = {:?}", slice_algos::split_first_word(&s));
+    println!("words = {:?}", slice_algos::words(&s));
+}
@@ -1,3 +1,5 @@
+use rcrash_core::counter::{self, OnLimit};
+
 fn add(a: i32, b: i32) -> i32 {
     a + b
 }
@@ -36,4 +38,25 @@ fn main() {
     c.inc();
     c.inc();
     println!("Counter value = {}", c.get());
+
+    // The library version of Counter is also an iterator (crates/rcrash-core/src/counter.rs)
+    let by_three: Vec<i32> = counter::Counter::new(0).step(3).until(10).collect();
+    println!("step 3 until 10:      {:?}", by_three);
+    let down: Vec<i32> = counter::Counter::new(10).step(-4).until(0).collect();
+    println!("step -4 until 0:      {:?}", down);
+    let saturate: Vec<i32> = counter::Counter::new(0)
+        .step(4)
+        .until(10)
+        .on_limit(OnLimit::Saturate)
+        .collect();
+    println!("saturating step 4:    {:?}", saturate);
+    let wrap: Vec<i32> = counter::Counter::new(0).step(4).until(10).wrapping().take(7).collect();
+    println!("wrapping step 4:      {:?}", wrap);
+    let mut both_ends = counter::Counter::new(1).until(6);
+    println!(
+        "front {:?}, back {:?}, {} left",
+        both_ends.next(),
+        both_ends.next_back(),
+        both_ends.len()
+    );
 }
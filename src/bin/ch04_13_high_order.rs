@@ -1,5 +1,8 @@
 // Demonstration of high-order functions in Rust
 
+use rcrash_core::combinators::{apply_n, compose, curry, flip, memoize, once, pipe, uncurry};
+use std::cell::Cell;
+
 fn apply_twice<F>(f: F, x: i32) -> i32
 where
     F: Fn(i32) -> i32,
@@ -31,4 +34,44 @@ fn main() {
     }
     let triple = make_multiplier(3);
     println!("triple(6) = {}", triple(6));
+
+    // Generic combinators (crates/rcrash-core/src/combinators.rs)
+    let inc_then_triple = compose(make_multiplier(3), |x| x + 1);
+    println!("compose(triple, inc)(4) = {}", inc_then_triple(4));
+    let mut trim_then_len = pipe(str::trim, str::len);
+    println!("pipe(trim, len)(\"  hi  \") = {}", trim_then_len("  hi  "));
+    let add = curry(|a: i32, b: i32| a + b);
+    println!("curry(add)(100)(23) = {}", add(100)(23));
+    println!("uncurry(curry(add))(1, 2) = {}", uncurry(&add)(1, 2));
+    println!("flip(-)(8, 3) = {}", flip(|a: i32, b: i32| a - b)(8, 3));
+    println!("apply_n(square, 2, 2) = {}", apply_n(square, 2, 2));
+
+    // Fn: only reads its captures, so it can be shared and called anywhere
+    let offset = 10;
+    let add_offset = |x| x + offset;
+    println!("Fn:     {} {}", add_offset(1), add_offset(2));
+
+    // FnMut: changes its captures, so every call needs `&mut` access
+    let mut history = Vec::new();
+    let step = |x: i32| {
+        history.push(x);
+        x * 2
+    };
+    println!("FnMut:  apply_n(double, 3, 1) = {}", apply_n(step, 3, 1));
+    println!("        it saw {:?}", history);
+
+    // memoize turns an Fn into an FnMut: the cache is its mutable capture
+    let calls = Cell::new(0);
+    let mut slow_len = memoize(|s: &str| {
+        calls.set(calls.get() + 1);
+        s.chars().count()
+    });
+    let lens: Vec<usize> = ["rust", "crab", "rust", "rust"].into_iter().map(&mut slow_len).collect();
+    println!("memoize: lengths {:?} with {} real calls", lens, calls.get());
+
+    // FnOnce: moves out of its captures, so it can run only once;
+    // `once` wraps it so later calls return None instead of failing to compile
+    let message = String::from("launch");
+    let mut fire = once(move |()| message);
+    println!("FnOnce: {:?} then {:?}", fire(()), fire(()));
 }
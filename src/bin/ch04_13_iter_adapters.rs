@@ -0,0 +1,74 @@
+// ch04_13_iter_adapters.rs
+// The map/filter/fold pipeline from ch04_13_high_order, rebuilt on the
+// hand-written adapters in crates/rcrash-core/src/my_iter.rs, with a trace that shows laziness.
+
+use rcrash_core::my_iter::MyIterExt;
+
+fn main() {
+    let numbers = [1, 2, 3, 4, 5];
+
+    // Same results as the std adapters
+    let squares: Vec<_> = numbers.iter().my_map(|&x| x * x).collect();
+    println!("squares = {:?}", squares);
+    let even: Vec<_> = numbers.iter().my_filter(|&&x| x % 2 == 0).collect();
+    println!("even numbers = {:?}", even);
+    let sum: i32 = numbers.iter().my_map(|&x| x * x).my_filter(|x| x % 2 == 1).sum();
+    println!("sum of odd squares = {}", sum);
+
+    // Laziness: each item goes through the whole pipeline before the next one starts
+    println!("\nTrace of numbers.iter().my_map(square).my_filter(odd).my_take_while(< 20):");
+    let pipeline = numbers
+        .iter()
+        .my_map(|&x| {
+            println!("  map     {} -> {}", x, x * x);
+            x * x
+        })
+        .my_filter(|x| {
+            println!("  filter  {} is odd: {}", x, x % 2 == 1);
+            x % 2 == 1
+        })
+        .my_take_while(|&x| {
+            println!("  take_while {} < 20: {}", x, x < 20);
+            x < 20
+        });
+    println!("  (pipeline built, nothing has run yet)");
+    for x in pipeline {
+        println!("  => {}", x);
+    }
+
+    // The other adapters
+    let names = ["ann", "bob", "cy"];
+    let pairs: Vec<_> = names.iter().my_zip(1..).collect();
+    println!("\nzip     = {:?}", pairs);
+    let chained: Vec<_> = (1..3).my_chain(7..9).collect();
+    println!("chain   = {:?}", chained);
+    let flat: Vec<_> = vec![vec![1, 2], vec![], vec![3]].into_iter().my_flatten().collect();
+    println!("flatten = {:?}", flat);
+    let running: Vec<_> = numbers
+        .iter()
+        .my_scan(0, |total, &x| {
+            *total += x;
+            Some(*total)
+        })
+        .collect();
+    println!("scan    = {:?} (running totals)", running);
+
+    // Peekable: group equal neighbours by looking one item ahead
+    let mut digits = "1122233".chars().my_peekable();
+    let mut groups = Vec::new();
+    while let Some(c) = digits.next() {
+        let mut run = c.to_string();
+        while let Some(same) = digits.next_if(|&next| next == c) {
+            run.push(same);
+        }
+        groups.push(run);
+    }
+    println!("peekable groups = {:?}", groups);
+
+    // size_hint: what each adapter can promise about its length
+    println!("\nsize_hint of 1..10 after each adapter:");
+    println!("  my_map          {:?}", (1..10).my_map(|x| x).size_hint());
+    println!("  my_filter       {:?}", (1..10).my_filter(|_| true).size_hint());
+    println!("  my_zip(0..4)    {:?}", (1..10).my_zip(0..4).size_hint());
+    println!("  my_chain(0..4)  {:?}", (1..10).my_chain(0..4).size_hint());
+}
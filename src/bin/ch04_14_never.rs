@@ -1,4 +1,10 @@
 // Demonstration of diverging (never type) functions in Rust
+// Usage: ch04_14_never [number]   prints the square root of a perfect square
+
+use rcrash_core::fatal::{usage_error, ExitCode, OrDie};
+use std::env;
+
+const USAGE: &str = "usage: ch04_14_never [number]";
 
 // A diverging function never returns, its return type is `!` (never type)
 fn never_returns() -> ! {
@@ -17,4 +23,22 @@ fn main() {
     // never_returns();
     // infinite_loop();
     println!("This line is after diverging functions (unreachable if called)");
+
+    // `!` coerces into any type, so a diverging call can stand in for a value.
+    // This is what rcrash_core::fatal builds on: `or_die` returns the value or exits.
+    let args: Vec<String> = env::args().skip(1).collect();
+    let text = match args.as_slice() {
+        [] => return,
+        [text] => text,
+        _ => usage_error(USAGE, "expected at most one number"),
+    };
+    let n: u64 = text.parse().or_die(ExitCode::DataErr, format_args!("`{}` is not a number", text));
+    let root = exact_sqrt(n).or_die(ExitCode::DataErr, format_args!("{} is not a perfect square", n));
+    println!("sqrt({}) = {}", n, root);
+}
+
+// Some(root) when root * root == n
+fn exact_sqrt(n: u64) -> Option<u64> {
+    let root = n.isqrt();
+    (root * root == n).then_some(root)
 }
@@ -3,6 +3,7 @@
 
 use std::io;
 use rand::{Rng, rng};
+use rcrash_core::fatal::{die, ExitCode, OrDie};
 use std::cmp::Ordering;
 
 fn main() {
@@ -14,7 +15,11 @@ fn main() {
     loop {
         println!("Please input your guess:");
         let mut guess = String::new();
-        io::stdin().read_line(&mut guess).expect("Failed to read line");
+        let bytes = io::stdin().read_line(&mut guess).or_die(ExitCode::IoErr, "failed to read line");
+        // 0 bytes means stdin was closed: no guess will ever come
+        if bytes == 0 {
+            die(ExitCode::NoInput, format!("no more input; the number was {}", secret_number));
+        }
         let guess: u32 = match guess.trim().parse() {
             Ok(num) => num,
             Err(_) => {
@@ -0,0 +1,70 @@
+// ch04_2_expr_repl.rs
+// 4.2 Expressions: a REPL for a tiny Rust-like expression language
+// Try `1 + 2 * 3`, `let x = { let a = 10; a * 2 }; x`,
+// `let mut i = 0; loop { i = i + 1; if i > 5 { break i * 10 } }`
+// or `match 7 { 1 => 10, 2..=9 => 20, _ => 30 }`.
+// Pass a program as an argument to run it once instead.
+
+use rcrash_core::expr_lang::{self, Interpreter, Stmt, Value};
+use std::env;
+use std::io::{self, BufRead, Write};
+
+/// More `{` or `(` than closing ones means the input continues on the next line.
+fn is_incomplete(source: &str) -> bool {
+    let depth = source.chars().fold(0i32, |depth, c| match c {
+        '{' | '(' => depth + 1,
+        '}' | ')' => depth - 1,
+        _ => depth,
+    });
+    depth > 0
+}
+
+fn run(interp: &mut Interpreter, source: &str) {
+    match interp.run(source) {
+        Ok(value) => {
+            println!("{}", value);
+            // Point out the `;` that turned the last value into ()
+            let ends_with_expr_statement = expr_lang::parse(source)
+                .is_ok_and(|p| p.tail.is_none() && matches!(p.stmts.last(), Some(Stmt::Expr(_))));
+            if value == Value::Unit && ends_with_expr_statement {
+                println!("note: the last expression ends with `;`, so the value is `()`");
+            }
+        }
+        Err(e) => println!("{}", e.render(source)),
+    }
+}
+
+fn main() {
+    let mut interp = Interpreter::new();
+    let args: Vec<String> = env::args().skip(1).collect();
+    if !args.is_empty() {
+        run(&mut interp, &args.join(" "));
+        return;
+    }
+
+    println!("Enter an expression; `let` bindings are kept between lines. Ctrl-D to quit.");
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    let mut source = String::new();
+    loop {
+        print!("{}", if source.is_empty() { "> " } else { ". " });
+        io::stdout().flush().expect("Failed to flush stdout");
+        let line = match lines.next() {
+            Some(Ok(line)) => line,
+            Some(Err(e)) => {
+                eprintln!("Failed to read line: {}", e);
+                break;
+            }
+            None => break, // EOF
+        };
+        source.push_str(&line);
+        source.push('\n');
+        if is_incomplete(&source) {
+            continue;
+        }
+        if !source.trim().is_empty() {
+            run(&mut interp, source.trim_end());
+        }
+        source.clear();
+    }
+}
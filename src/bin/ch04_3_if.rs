@@ -1,3 +1,5 @@
+use rcrash_core::grading::Scale;
+
 fn main() {
     // Basic if/else
     let n = 7;
@@ -26,6 +28,11 @@ fn main() {
     };
     println!("grade {} -> level {}", grade, level);
 
+    // The same score on the shared scale from crates/rcrash-core/data/standard.txt, which
+    // splits "D or lower" into D (60..=69) and F (0..60) like ch04_7_match
+    let standard = Scale::standard();
+    println!("standard scale: grade {} -> {}", grade, standard.grade(grade).unwrap_or("out of range"));
+
     // if let (pattern matching shortcut)
     let opt = Some(10);
     if let Some(val) = opt {
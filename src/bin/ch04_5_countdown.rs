@@ -0,0 +1,62 @@
+// ch04_5_countdown.rs
+// 4.5 while loops: a real-time countdown and stopwatch
+// Usage:
+//   cargo run --bin ch04_5_countdown -- 1m30s        count down, then "Liftoff!"
+//   cargo run --bin ch04_5_countdown -- stopwatch    count up until `q`
+//   cargo run --bin ch04_5_countdown -- stopwatch 10s
+// Press Enter to pause or resume, type `q` and Enter to stop.
+
+use rcrash_core::countdown::{self, Control, Event, Mode, SystemClock, Timer};
+use std::env;
+use std::io::{self, BufRead, Write};
+use std::process;
+use std::sync::mpsc;
+use std::thread;
+
+fn parse_mode(args: &[String]) -> Result<Mode, String> {
+    let duration = |text: &String| countdown::parse_duration(text).map_err(|e| format!("{}: {}", text, e));
+    match args {
+        [] => Ok(Mode::Countdown(countdown::parse_duration("3s").expect("valid"))),
+        [first, rest @ ..] if first == "stopwatch" => match rest {
+            [] => Ok(Mode::Stopwatch(None)),
+            [limit] => Ok(Mode::Stopwatch(Some(duration(limit)?))),
+            _ => Err("usage: ch04_5_countdown stopwatch [limit]".to_string()),
+        },
+        [total] => Ok(Mode::Countdown(duration(total)?)),
+        _ => Err("usage: ch04_5_countdown [duration | stopwatch [limit]]".to_string()),
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let mode = parse_mode(&args).unwrap_or_else(|e| {
+        eprintln!("error: {}", e);
+        process::exit(2);
+    });
+
+    // Keyboard input arrives on its own thread and is forwarded as Control messages
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        for line in io::stdin().lock().lines() {
+            let control = match line.as_deref().map(str::trim) {
+                Ok("q") | Ok("quit") | Err(_) => Control::Stop,
+                Ok(_) => Control::Toggle,
+            };
+            if tx.send(control).is_err() || control == Control::Stop {
+                break;
+            }
+        }
+    });
+
+    println!("Enter: pause/resume, q + Enter: stop");
+    let timer = Timer::new(SystemClock, mode);
+    timer.run(&rx, |event| {
+        // `\r` returns to the start of the line and `\x1b[K` clears what was there,
+        // so each tick redraws one line instead of printing a new one
+        print!("\r\x1b[K{}", countdown::status_line(event));
+        if matches!(event, Event::Finished | Event::Stopped(_)) {
+            println!();
+        }
+        io::stdout().flush().expect("Failed to flush stdout");
+    });
+}
@@ -1,3 +1,5 @@
+use rcrash_core::grading::Scale;
+
 fn main() {
     // Basic match statement
     let n = 2;
@@ -58,4 +60,6 @@ fn main() {
         _ => "F",
     };
     println!("grade = {}", grade);
+    // The same bands, loaded from crates/rcrash-core/data/standard.txt instead of hard-coded
+    println!("standard scale: {} -> {:?}", score, Scale::standard().grade(score));
 }
@@ -32,4 +32,24 @@ fn main() {
 
     // The following line would not compile (private function):
     // let _ = math::private_add(2, 3);
+
+    // The same idea at library scale: rcrash_core::math keeps its helpers in private
+    // submodules and re-exports only the public functions (crates/rcrash-core/src/math.rs)
+    println!("\ngcd(48, 18) = {}", rcrash_core::math::gcd(48, 18));
+    println!("lcm(4, 6) = {:?}", rcrash_core::math::lcm(4, 6));
+    println!("7^128 mod 13 = {}", rcrash_core::math::mod_pow(7, 128, 13));
+    println!("primes <= 30: {:?}", rcrash_core::math::sieve(30));
+    for n in [561, 2_147_483_647, 18_446_744_073_709_551_557] {
+        println!("is_prime({}) = {}", n, rcrash_core::math::is_prime(n));
+    }
+    for n in [360, 600_851_475_143, u64::MAX] {
+        let factors: Vec<String> = rcrash_core::math::factorize(n)
+            .into_iter()
+            .map(|(p, k)| if k == 1 { p.to_string() } else { format!("{}^{}", p, k) })
+            .collect();
+        println!("{} = {}", n, factors.join(" * "));
+    }
+    // These would not compile: the submodules and their helpers are private
+    // let _ = rcrash_core::math::arith::mul_mod(2, 3, 5);
+    // let _ = rcrash_core::math::primes::pollard_rho(8051);
 }
@@ -1,6 +1,8 @@
 // ch05_04_struct_visibility.rs
 // Demonstration of struct and field visibility in Rust
 
+use rcrash_core::rectangle::Rectangle;
+
 mod mymod {
     // Struct is public, but fields are private by default
     pub struct PublicStruct {
@@ -20,6 +22,8 @@ mod mymod {
             self.y
         }
         // Public setter for private field y
+        // It accepts any value, so keeping `y` private protects nothing here.
+        // `Rectangle` in main() shows private fields that guard an invariant.
         pub fn set_y(&mut self, new_y: i32) {
             self.y = new_y;
         }
@@ -65,4 +69,29 @@ fn main() {
     ap.a = 50;
     ap.b = 60;
     println!("After set: AllPublic.a = {}, b = {}", ap.a, ap.b);
+
+    // Private fields with an invariant: sides are always positive and finite.
+    // The builder checks each side; `build` only exists once both are set.
+    let mut rect = Rectangle::builder()
+        .width(3.0)
+        .and_then(|b| b.height(4.0))
+        .expect("valid sides")
+        .origin(1.0, 1.0)
+        .build();
+    println!("Rectangle: {}, area = {}", rect, rect.area());
+    // let rect = Rectangle::builder().width(3.0)?.build(); // Error: no `build` without a height
+    // rect.width = -1.0;                                  // Error: field `width` is private
+
+    match Rectangle::builder().width(-2.0) {
+        Ok(_) => println!("built a rectangle with width -2?"),
+        Err(e) => println!("Builder rejected: {}", e),
+    }
+    if let Err(e) = rect.set_height(0.0) {
+        println!("set_height(0) rejected: {}; still {}", e, rect);
+    }
+    rect.scale(2.0).expect("doubling keeps sides positive");
+    println!("After scale(2): {}", rect);
+    // The sides can be given in either order
+    let square = Rectangle::builder().height(2.0).and_then(|b| b.width(2.0)).expect("valid sides").build();
+    println!("Square? {}", square.is_square());
 }
@@ -2,6 +2,8 @@
 // 6.2 Generics as Function Parameter Types
 // Demonstrates how to use generics as function parameter types in Rust.
 
+use rcrash_core::sorting::{argmax, clamp, merge_sort_by, min_max, quick_sort};
+
 /// Returns the larger of two values.
 /// Works for any type that implements the PartialOrd and Copy traits.
 pub fn max<T: PartialOrd + Copy>(a: T, b: T) -> T {
@@ -26,4 +28,17 @@ fn main() {
     let pair = ("hello", "world");
     let swapped = swap(pair);
     println!("swap({:?}) = {:?}", pair, swapped);
+
+    // The same `T: Ord` bound scales from two values to whole slices
+    let scores = [72, 95, 88, 95, 61];
+    println!("min_max({:?}) = {:?}", scores, min_max(&scores));
+    println!("argmax({:?}) = {:?}", scores, argmax(&scores));
+    println!("clamp(120, 0, 100) = {}", clamp(120, 0, 100));
+
+    let mut words = vec!["pear", "fig", "banana", "kiwi", "apple"];
+    quick_sort(&mut words);
+    println!("quick_sort: {:?}", words);
+    // A comparator closure instead of `Ord`: by length, ties keep their order
+    merge_sort_by(&mut words, |a, b| a.len().cmp(&b.len()));
+    println!("merge_sort_by(len): {:?}", words);
 }
@@ -0,0 +1,102 @@
+// ch06_02_sort_timing.rs
+// 6.2 Generic sorting algorithms: timing comparison
+// Usage: ch06_02_sort_timing [n]   sorts n elements (default 20000) with each algorithm
+// Times in a debug build are mostly overhead; use `cargo run --release` to compare.
+
+use rcrash_core::fatal::{usage_error, ExitCode, OrDie};
+use rcrash_core::sorting::{heap_sort, insertion_sort, merge_sort, quick_sort};
+use std::env;
+use std::time::{Duration, Instant};
+
+const USAGE: &str = "usage: ch06_02_sort_timing [n]";
+
+type Sort = fn(&mut [u32]);
+
+/// Insertion sort is O(n²); above this size it is skipped.
+const INSERTION_LIMIT: usize = 20_000;
+
+/// A small xorshift generator, so the inputs are the same on every run
+/// without pulling in the `rand` crate.
+struct XorShift(u64);
+
+impl XorShift {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
+
+fn inputs(n: usize) -> Vec<(&'static str, Vec<u32>)> {
+    let mut rng = XorShift(0x2545_f491_4f6c_dd1d);
+    let random: Vec<u32> = (0..n).map(|_| rng.next() as u32).collect();
+    let few_unique: Vec<u32> = (0..n).map(|_| (rng.next() % 8) as u32).collect();
+    let sorted: Vec<u32> = (0..n as u32).collect();
+    let reversed: Vec<u32> = sorted.iter().rev().copied().collect();
+    let mut nearly_sorted = sorted.clone();
+    for _ in 0..n / 100 {
+        let (a, b) = (rng.next() as usize % n.max(1), rng.next() as usize % n.max(1));
+        nearly_sorted.swap(a, b);
+    }
+    vec![
+        ("random", random),
+        ("few unique", few_unique),
+        ("sorted", sorted),
+        ("reversed", reversed),
+        ("nearly sorted", nearly_sorted),
+    ]
+}
+
+/// Sorts a copy of `input`, checks the result and returns the time taken.
+fn time(sort: Sort, input: &[u32], expected: &[u32]) -> Duration {
+    let mut data = input.to_vec();
+    let start = Instant::now();
+    sort(&mut data);
+    let elapsed = start.elapsed();
+    assert_eq!(data, expected, "sort produced a wrong result");
+    elapsed
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let n: usize = match args.as_slice() {
+        [] => 20_000,
+        [text] => text.parse().or_die(ExitCode::DataErr, format_args!("`{}` is not a count", text)),
+        _ => usage_error(USAGE, "expected at most one count"),
+    };
+
+    let algorithms: [(&str, Sort); 6] = [
+        ("insertion", insertion_sort),
+        ("merge", merge_sort),
+        ("quick", quick_sort),
+        ("heap", heap_sort),
+        ("slice::sort", <[u32]>::sort),
+        ("sort_unstable", <[u32]>::sort_unstable),
+    ];
+
+    println!("Sorting {} u32 values (times in ms)", n);
+    print!("{:<14}", "input");
+    for (name, _) in &algorithms {
+        print!("{:>14}", name);
+    }
+    println!();
+
+    for (input_name, input) in inputs(n) {
+        let mut expected = input.clone();
+        expected.sort();
+        print!("{:<14}", input_name);
+        for (name, sort) in &algorithms {
+            if *name == "insertion" && n > INSERTION_LIMIT {
+                print!("{:>14}", "skipped");
+                continue;
+            }
+            let elapsed = time(*sort, &input, &expected);
+            print!("{:>14.3}", elapsed.as_secs_f64() * 1000.0);
+        }
+        println!();
+    }
+    if n > INSERTION_LIMIT {
+        println!("(insertion sort is skipped above {} elements)", INSERTION_LIMIT);
+    }
+}
@@ -37,4 +37,33 @@ fn main() {
 
     let p3 = Point::new(0.0_f64, 5.0_f64);
     println!("p3: {:?}, distance from origin: {}", p3, p3.distance_from_origin());
+
+    vector_math();
+}
+
+/// The same idea taken further in `rcrash_core::point`: the std operator
+/// traits (`Add`, `Sub`, `Neg`, `Mul<T>`, `AddAssign`) and `Display` are
+/// implemented for any `T` that supports the underlying operation.
+fn vector_math() {
+    // This `Point` shadows the one defined above, inside this function only
+    use rcrash_core::point::Point;
+
+    let a = Point::new(3, 4);
+    let b = Point::new(1, -2);
+    println!("{} + {} = {}", a, b, a + b);
+    println!("{} - {} = {}", a, b, a - b);
+    println!("-{} = {}, {} * 3 = {}", a, -a, a, a * 3);
+    println!("dot = {}, cross = {}", a.dot(b), a.cross(b));
+
+    let mut position = Point::new(0.0_f32, 0.0);
+    let velocity = Point::new(1.5_f32, 2.0);
+    for _ in 0..2 {
+        position += velocity;
+    }
+    println!("after two steps: {}", position);
+
+    // distance and lerp need `Float`, implemented for f32 and f64 only
+    let (start, end) = (Point::new(1.0_f64, 2.0), Point::new(4.0_f64, 6.0));
+    println!("distance {} -> {} = {}", start, end, start.distance(end));
+    println!("halfway: {:.2}", start.lerp(end, 0.5));
 }
@@ -0,0 +1,98 @@
+// ch06_04_geometry.rs
+// 6.4 Generic structs: a 2D geometry worked example on top of Point<T>
+// Usage: ch06_04_geometry [out.svg]   prints the results; with a path, also draws them
+// Open the SVG file in a browser to see the polygon, the hull and the intersections.
+
+use rcrash_core::fatal::{usage_error, ExitCode, OrDie};
+use rcrash_core::geometry::{convex_hull, BoundingBox, Polygon, Segment, Svg};
+use rcrash_core::point::Point;
+use std::env;
+
+const USAGE: &str = "usage: ch06_04_geometry [out.svg]";
+
+/// A fixed cloud of points, spread by a small linear congruential generator so
+/// the output is the same on every run.
+fn scattered_points(n: usize) -> Vec<Point<f64>> {
+    let mut state: u32 = 12345;
+    let mut next = move || {
+        state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+        f64::from((state >> 16) % 100) / 10.0
+    };
+    (0..n).map(|_| Point::new(next() + 12.0, next())).collect()
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let out = match args.as_slice() {
+        [] => None,
+        [path] => Some(path),
+        _ => usage_error(USAGE, "expected at most one output path"),
+    };
+
+    // A house outline, counter-clockwise
+    let house = Polygon::new(vec![
+        Point::new(0.0, 0.0),
+        Point::new(8.0, 0.0),
+        Point::new(8.0, 5.0),
+        Point::new(4.0, 9.0),
+        Point::new(0.0, 5.0),
+    ])
+    .or_die(ExitCode::Failure, "house");
+    println!("house: {} vertices", house.vertices().len());
+    println!("  area = {}, perimeter = {:.3}", house.area(), house.perimeter());
+    println!("  counter-clockwise: {}", house.is_counter_clockwise());
+    let bounds = house.bounding_box();
+    println!("  bounding box: {} to {}", bounds.min, bounds.max);
+
+    // (8, 2) lies on the right wall; points on an edge count as inside
+    let probes = [Point::new(4.0, 4.0), Point::new(4.0, 8.5), Point::new(7.0, 8.0), Point::new(8.0, 2.0)];
+    for p in probes {
+        let verdict = if house.contains(p) { "inside" } else { "outside" };
+        println!("  {} is {}", p, verdict);
+    }
+
+    // A line across the house: where does it cross the walls?
+    let beam = Segment::new(Point::new(-2.0, 2.0), Point::new(10.0, 8.0));
+    println!("beam {} -> {}:", beam.start, beam.end);
+    let crossings: Vec<Point<f64>> = house.edges().filter_map(|wall| beam.intersection(&wall)).collect();
+    for c in &crossings {
+        println!("  crosses a wall at {:.2}", c);
+    }
+
+    // Integer segments: the intersection test needs no division, so it is exact
+    let a = Segment::new(Point::new(0, 0), Point::new(6, 3));
+    let b = Segment::new(Point::new(2, 1), Point::new(4, 2));
+    let c = Segment::new(Point::new(0, 3), Point::new(6, 0));
+    println!("{}-{} and {}-{} intersect: {} (collinear, overlapping)", a.start, a.end, b.start, b.end, a.intersects(&b));
+    println!("{}-{} and {}-{} intersect: {}", a.start, a.end, c.start, c.end, a.intersects(&c));
+
+    // Convex hull of a point cloud
+    let cloud = scattered_points(40);
+    let hull = convex_hull(&cloud);
+    let cloud_bounds = BoundingBox::of(cloud.iter().copied()).or_die(ExitCode::Failure, "empty cloud");
+    println!("cloud of {} points, hull of {}:", cloud.len(), hull.len());
+    for p in &hull {
+        println!("  {}", p);
+    }
+    let hull_polygon = Polygon::new(hull).or_die(ExitCode::Failure, "hull");
+    println!("  hull area = {:.2}, box = {:.1} x {:.1}", hull_polygon.area(), cloud_bounds.width(), cloud_bounds.height());
+
+    if let Some(path) = out {
+        let mut svg = Svg::new();
+        svg.polygon(&house, "fill:#f4e3c1;stroke:#8a5a2b")
+            .segment(&beam, "stroke:#c0392b;stroke-dasharray:0.3")
+            .polygon(&hull_polygon, "fill:#d6eaf8;stroke:#2471a3");
+        for p in &cloud {
+            svg.point(*p, "fill:#1b4f72;stroke:none");
+        }
+        for p in &crossings {
+            svg.point(*p, "fill:#c0392b;stroke:none");
+        }
+        for p in probes {
+            let style = if house.contains(p) { "fill:#27ae60;stroke:none" } else { "fill:#7f8c8d;stroke:none" };
+            svg.point(p, style);
+        }
+        svg.save(path).or_die(ExitCode::IoErr, format_args!("cannot write {}", path));
+        println!("wrote {}", path);
+    }
+}
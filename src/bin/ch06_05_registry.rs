@@ -0,0 +1,122 @@
+// ch06_05_registry.rs
+// 6.5 Traits: trait objects, a registry and static vs dynamic dispatch
+// Usage: ch06_05_registry            shows the registry
+//        ch06_05_registry bench [n]  times static and dynamic dispatch over n items (default 1000000)
+// Benchmark times in a debug build are mostly overhead; use `cargo run --release`.
+
+use rcrash_core::fatal::{usage_error, ExitCode, OrDie};
+use rcrash_core::registry::{Book, Company, Describable, Person, Registry};
+use std::env;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const USAGE: &str = "usage: ch06_05_registry [bench [n]]";
+
+// Both sides take a slice of boxes, so the memory layout is the same and only
+// the dispatch differs.
+
+// Static dispatch: one copy of the function per `T`, with the call resolved
+// (and usually inlined) at compile time. All items must be the same type.
+fn total_headcount_static<T: Describable>(items: &[Box<T>]) -> usize {
+    items.iter().map(|item| item.headcount()).sum()
+}
+
+fn total_len_static<T: Describable>(items: &[Box<T>]) -> usize {
+    items.iter().map(|item| item.describe().len()).sum()
+}
+
+// Dynamic dispatch: one copy of the function; each call goes through the
+// vtable pointer stored next to the data pointer. Items may differ in type.
+fn total_headcount_dyn(items: &[Box<dyn Describable>]) -> usize {
+    items.iter().map(|item| item.headcount()).sum()
+}
+
+fn total_len_dyn(items: &[Box<dyn Describable>]) -> usize {
+    items.iter().map(|item| item.describe().len()).sum()
+}
+
+fn show_registry() {
+    let mut registry = Registry::new();
+    registry.register(Person::new("Alice", 30));
+    registry.register(Company::new("Acme Corp", 100));
+    registry.register(Book::new("Dune", "Frank Herbert"));
+    registry.register(Person::new("Bob", 25));
+    registry.register(Company::new("Initech", 12));
+
+    println!("{} entries:", registry.len());
+    for item in registry.iter() {
+        println!("  [{:<13}] {:<10} {}", item.category(), item.short_name(), item.describe());
+    }
+
+    for category in registry.categories() {
+        let names: Vec<String> = registry.by_category(category).map(|item| item.short_name()).collect();
+        println!("{}: {}", category, names.join(", "));
+    }
+
+    // Downcasting: back from `dyn Describable` to the concrete type
+    let oldest = registry.of_type::<Person>().max_by_key(|p| p.age);
+    println!("oldest person: {:?}", oldest);
+    let largest = registry.of_type::<Company>().max_by_key(|c| c.employee_count);
+    println!("largest company: {:?}", largest.map(|c| &c.name));
+    println!("total headcount: {}", registry.headcount());
+
+    // `Debug` is a supertrait, so `{:?}` works on the trait object itself
+    if let Some(first) = registry.iter().next() {
+        println!("first entry: {:?}", first);
+    }
+}
+
+/// Timed runs per measurement, after one untimed warm-up run.
+const RUNS: usize = 9;
+
+/// Runs `f` once to warm up, then `RUNS` more times, and returns its result
+/// with the median time of the timed runs.
+fn time<R>(f: impl Fn() -> R) -> (R, Duration) {
+    let result = black_box(f());
+    let mut times: Vec<Duration> = (0..RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .collect();
+    times.sort();
+    (result, times[RUNS / 2])
+}
+
+/// Times the same items, once as `Box<T>` and once as `Box<dyn Describable>`.
+fn bench_type<T: Describable>(label: &str, n: usize, make: impl Fn(usize) -> T) {
+    let items: Vec<Box<T>> = (0..n).map(|i| Box::new(make(i))).collect();
+    let boxed: Vec<Box<dyn Describable>> = (0..n).map(|i| Box::new(make(i)) as Box<dyn Describable>).collect();
+
+    let (a, static_time) = time(|| total_headcount_static(black_box(&items)));
+    let (b, dyn_time) = time(|| total_headcount_dyn(black_box(&boxed)));
+    assert_eq!(a, b);
+    println!("{:<22}{:>12.3}{:>12.3}", format!("{} headcount", label), ms(static_time), ms(dyn_time));
+
+    let (a, static_time) = time(|| total_len_static(black_box(&items)));
+    let (b, dyn_time) = time(|| total_len_dyn(black_box(&boxed)));
+    assert_eq!(a, b);
+    println!("{:<22}{:>12.3}{:>12.3}", format!("{} describe", label), ms(static_time), ms(dyn_time));
+}
+
+fn bench(n: usize) {
+    println!("{} items per type, median of {} runs (times in ms)", n, RUNS);
+    println!("{:<22}{:>12}{:>12}", "type / method", "static", "dyn");
+    bench_type("Person", n, |i| Person::new("Alice", (i % 90) as u32));
+    bench_type("Company", n, |i| Company::new("Acme", i % 100));
+}
+
+fn ms(d: Duration) -> f64 {
+    d.as_secs_f64() * 1000.0
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match args.as_slice() {
+        [] => show_registry(),
+        [cmd] if cmd == "bench" => bench(1_000_000),
+        [cmd, n] if cmd == "bench" => bench(n.parse().or_die(ExitCode::DataErr, format_args!("`{}` is not a count", n))),
+        _ => usage_error(USAGE, "unknown arguments"),
+    }
+}
@@ -2,10 +2,14 @@
 // 8.4 Custom Errors and the ? Operator in Rust
 // Demonstrates defining custom error types and using the ? operator for error propagation.
 
+use rcrash_core::fatal::{die, usage_error, ExitCode};
+use std::env;
 use std::fs::File;
 use std::io::{self, Read};
 use std::num::ParseIntError;
 
+const USAGE: &str = "usage: ch08_04_custom_error [path]   (default: number.txt)";
+
 // Define a custom error type that can wrap multiple error kinds
 #[derive(Debug)]
 enum MyError {
@@ -46,9 +50,24 @@ fn read_and_parse_number(path: &str) -> Result<i32, MyError> {
     Ok(number)
 }
 
+// Each kind of failure gets its own exit code, so scripts can tell them apart
+fn exit_code(e: &MyError) -> ExitCode {
+    match e {
+        MyError::Io(e) if e.kind() == io::ErrorKind::NotFound => ExitCode::NoInput,
+        MyError::Io(_) => ExitCode::IoErr,
+        MyError::Parse(_) => ExitCode::DataErr,
+    }
+}
+
 fn main() {
-    match read_and_parse_number("number.txt") {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let path = match args.as_slice() {
+        [] => "number.txt",
+        [path] => path.as_str(),
+        _ => usage_error(USAGE, "too many arguments"),
+    };
+    match read_and_parse_number(path) {
         Ok(n) => println!("The number is: {}", n),
-        Err(e) => eprintln!("Error: {}", e),
+        Err(e) => die(exit_code(&e), format_args!("{}: {}", path, e)),
     }
 }
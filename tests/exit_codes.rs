@@ -0,0 +1,83 @@
+// exit_codes.rs
+// `die`, `usage_error` and `OrDie` end the process, so they are tested from the
+// outside: run the chapter binaries that use them and check the exit status
+// and what they wrote to stderr.
+
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn run(bin: &str, args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(bin)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to start binary");
+    child.stdin.take().expect("piped stdin").write_all(stdin.as_bytes()).expect("failed to write stdin");
+    child.wait_with_output().expect("failed to wait for binary")
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+#[test]
+fn or_die_exits_with_the_chosen_code() {
+    let never = env!("CARGO_BIN_EXE_ch04_14_never");
+
+    let ok = run(never, &["16"], "");
+    assert_eq!(ok.status.code(), Some(0));
+    assert!(String::from_utf8_lossy(&ok.stdout).contains("sqrt(16) = 4"));
+
+    // Result::or_die: the message is "context: error"
+    let not_a_number = run(never, &["x"], "");
+    assert_eq!(not_a_number.status.code(), Some(65));
+    assert!(stderr(&not_a_number).starts_with("error: `x` is not a number: invalid digit"));
+
+    // Option::or_die: the message is just the context
+    let not_square = run(never, &["15"], "");
+    assert_eq!(not_square.status.code(), Some(65));
+    assert_eq!(stderr(&not_square), "error: 15 is not a perfect square\n");
+}
+
+#[test]
+fn usage_error_prints_usage_and_exits_with_2() {
+    let output = run(env!("CARGO_BIN_EXE_ch04_14_never"), &["1", "2"], "");
+    assert_eq!(output.status.code(), Some(2));
+    assert_eq!(stderr(&output), "error: expected at most one number\n\nusage: ch04_14_never [number]\n");
+}
+
+#[test]
+#[cfg(feature = "guess")]
+fn guessing_game_dies_when_input_ends() {
+    let guess = env!("CARGO_BIN_EXE_ch04_15_guess");
+    let output = run(guess, &[], "50\nnope\n");
+    assert_eq!(output.status.code(), Some(66));
+    assert!(stderr(&output).starts_with("error: no more input; the number was "));
+
+    // All 100 guesses: one of them wins, so the game exits normally
+    let all: String = (1..=100).map(|n| format!("{}\n", n)).collect();
+    assert_eq!(run(guess, &[], &all).status.code(), Some(0));
+}
+
+#[test]
+fn file_errors_map_to_distinct_codes() {
+    let bin = env!("CARGO_BIN_EXE_ch08_04_custom_error");
+    let dir = std::env::temp_dir().join(format!("rcrash-exit-codes-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).expect("failed to create temp dir");
+    let good = dir.join("good.txt");
+    let bad = dir.join("bad.txt");
+    std::fs::write(&good, "42\n").expect("failed to write temp file");
+    std::fs::write(&bad, "forty-two\n").expect("failed to write temp file");
+
+    let output = run(bin, &[good.to_str().unwrap()], "");
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "The number is: 42\n");
+    assert_eq!(run(bin, &[bad.to_str().unwrap()], "").status.code(), Some(65));
+    let missing = dir.join("missing.txt");
+    assert_eq!(run(bin, &[missing.to_str().unwrap()], "").status.code(), Some(66));
+    assert_eq!(run(bin, &["a", "b"], "").status.code(), Some(2));
+
+    std::fs::remove_dir_all(&dir).expect("failed to remove temp dir");
+}
@@ -0,0 +1,81 @@
+// snapshots.rs
+// Snapshot tests: each chapter binary below is run without arguments and its
+// stdout is compared with tests/snapshots/<name>.txt.
+//
+// After adding a binary or an intended change to its output, accept the output with
+//   UPDATE_SNAPSHOTS=1 cargo test --test snapshots
+// and review the diff of tests/snapshots/ before committing.
+//
+// Binaries that read stdin, depend on time, files or HashMap order, or panic on
+// purpose are not listed.
+
+use rcrash_tools::snapshot::assert_snapshot;
+use std::path::Path;
+use std::process::Command;
+
+fn check(name: &str, bin: &str) {
+    let output = Command::new(bin).output().expect("failed to start binary");
+    assert!(output.status.success(), "{} exited with {}", name, output.status);
+    let stdout = String::from_utf8(output.stdout).expect("stdout is not UTF-8");
+    assert_snapshot(&Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/snapshots"), name, &stdout);
+}
+
+macro_rules! snapshots {
+    ($($name:ident,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                check(stringify!($name), env!(concat!("CARGO_BIN_EXE_", stringify!($name))));
+            }
+        )*
+    };
+}
+
+snapshots! {
+    ch03_3_var_mut,
+    ch03_4_basic_types,
+    ch03_6_integer_overflow,
+    ch03_7_tuple,
+    ch03_8_array,
+    ch03_8_matrix,
+    ch03_9_slice,
+    ch03_9_slice_algos,
+    ch03_10_struct,
+    ch03_11_enum,
+    ch03_12_comment,
+    ch03_13_println,
+    ch03_14_cast,
+    ch04_2_expr,
+    ch04_3_if,
+    ch04_4_loop,
+    ch04_5_while,
+    ch04_6_for,
+    ch04_7_match,
+    ch04_8_if_let,
+    ch04_9_while_let,
+    ch04_10_fn_method,
+    ch04_11_fn_closure,
+    ch04_13_high_order,
+    ch04_13_iter_adapters,
+    ch04_14_never,
+    ch05_02_mod,
+    ch05_03_pub,
+    ch05_04_struct_visibility,
+    ch05_05_use_binding,
+    ch05_06_super_self,
+    ch06_02_generic_fn_param,
+    ch06_03_generic_struct,
+    ch06_04_generic_struct_impl,
+    ch06_04_geometry,
+    ch06_05_registry,
+    ch06_05_traits,
+    ch06_06_derive,
+    ch07_02_ownership,
+    ch07_03_borrowing,
+    ch07_04_lifetime,
+    ch07_05_lifetime_annotation,
+    ch08_02_unrecoverable,
+    ch09_02_box,
+    ch09_03_rc,
+    ch09_04_vec,
+}